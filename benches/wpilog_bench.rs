@@ -1,5 +1,8 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use pprof::criterion::Output;
+use wpilog_polars::datalog::DataLogReader;
+use wpilog_polars::struct_support::{PolarsConverter, StructDeserializer, StructValue};
+use wpilog_polars::StructRegistry;
 use wpilog_polars::WpilogParser;
 
 /// Helper function to create a WPILog file with specified number of records
@@ -84,6 +87,130 @@ fn create_wpilog_with_records(num_records: usize) -> Vec<u8> {
     data
 }
 
+/// Helper function to create a WPILog file with one `double[]` entry holding
+/// `array_len` elements per record
+fn create_wpilog_with_double_arrays(num_records: usize, array_len: usize) -> Vec<u8> {
+    let mut data = Vec::new();
+
+    // Header
+    data.extend_from_slice(b"WPILOG");
+    data.extend_from_slice(&[0x00, 0x01]); // Version 1.0
+    data.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]); // Extra header length = 0
+
+    // Entry 1: double[] type
+    data.push(0x00); // Header byte
+    data.push(0x00); // Entry ID = 0 (control)
+    data.push(32); // Payload size
+    data.push(0x01); // Timestamp = 1
+    data.push(0x00); // Control type = Start
+    data.extend_from_slice(&[0x01, 0x00, 0x00, 0x00]); // Entry ID = 1
+    data.extend_from_slice(&[0x07, 0x00, 0x00, 0x00]); // Name length = 7
+    data.extend_from_slice(b"samples"); // Name
+    data.extend_from_slice(&[0x08, 0x00, 0x00, 0x00]); // Type length = 8
+    data.extend_from_slice(b"double[]"); // Type
+    data.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]); // Metadata length = 0
+
+    let payload_size = (array_len * 8) as u32;
+    for i in 0..num_records {
+        // Header byte: 1-byte entry ID, 4-byte payload size, 4-byte timestamp
+        data.push(0x3C);
+        data.push(0x01); // Entry ID = 1
+        data.extend_from_slice(&payload_size.to_le_bytes());
+        data.extend_from_slice(&((i as u32 + 1) * 20_000).to_le_bytes());
+        for j in 0..array_len {
+            data.extend_from_slice(&((i * array_len + j) as f64).to_le_bytes());
+        }
+    }
+
+    data
+}
+
+/// Helper function to append a START control record for `entry`
+fn push_start(data: &mut Vec<u8>, entry: u32, name: &str, type_name: &str) {
+    let payload_size = 17 + name.len() + type_name.len();
+    data.push(0x00); // Header byte
+    data.push(0x00); // Entry ID = 0 (control)
+    data.push(payload_size as u8); // Payload size
+    data.push(0x01); // Timestamp = 1
+    data.push(0x00); // Control type = Start
+    data.extend_from_slice(&entry.to_le_bytes());
+    data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+    data.extend_from_slice(name.as_bytes());
+    data.extend_from_slice(&(type_name.len() as u32).to_le_bytes());
+    data.extend_from_slice(type_name.as_bytes());
+    data.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]); // Metadata length = 0
+}
+
+/// Helper function to create a WPILog file with one struct array entry holding
+/// `array_len` `ModuleState { double speed; double angle }` elements per record
+fn create_wpilog_with_struct_arrays(num_records: usize, array_len: usize) -> Vec<u8> {
+    let mut data = Vec::new();
+
+    // Header
+    data.extend_from_slice(b"WPILOG");
+    data.extend_from_slice(&[0x00, 0x01]); // Version 1.0
+    data.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]); // Extra header length = 0
+
+    // Entry 1: the struct schema, Entry 2: the struct array
+    push_start(&mut data, 1, "/.schema/struct:ModuleState", "structschema");
+    let schema = b"double speed;double angle";
+    data.push(0x00); // Header byte
+    data.push(0x01); // Entry ID = 1
+    data.push(schema.len() as u8); // Payload size
+    data.push(0x01); // Timestamp = 1
+    data.extend_from_slice(schema);
+    push_start(&mut data, 2, "states", "struct:ModuleState[]");
+
+    let payload_size = (array_len * 16) as u32;
+    for i in 0..num_records {
+        // Header byte: 1-byte entry ID, 4-byte payload size, 4-byte timestamp
+        data.push(0x3C);
+        data.push(0x02); // Entry ID = 2
+        data.extend_from_slice(&payload_size.to_le_bytes());
+        data.extend_from_slice(&((i as u32 + 1) * 20_000).to_le_bytes());
+        for j in 0..array_len {
+            data.extend_from_slice(&((i + j) as f64 * 0.5).to_le_bytes());
+            data.extend_from_slice(&(j as f64 * 1.25).to_le_bytes());
+        }
+    }
+
+    data
+}
+
+/// Helper function to create a WPILog file with `num_columns` double entries, each
+/// logged once per row for `num_rows` rows
+fn create_wide_wpilog(num_columns: usize, num_rows: usize) -> Vec<u8> {
+    let mut data = Vec::new();
+
+    // Header
+    data.extend_from_slice(b"WPILOG");
+    data.extend_from_slice(&[0x00, 0x01]); // Version 1.0
+    data.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]); // Extra header length = 0
+
+    for column in 0..num_columns {
+        push_start(
+            &mut data,
+            column as u32 + 1,
+            &format!("/Robot/Signal{}", column),
+            "double",
+        );
+    }
+
+    for row in 0..num_rows {
+        let timestamp = (row as u32 + 1) * 20_000;
+        for column in 0..num_columns {
+            // Header byte: 2-byte entry ID, 1-byte payload size, 4-byte timestamp
+            data.push(0x31);
+            data.extend_from_slice(&(column as u16 + 1).to_le_bytes());
+            data.push(0x08); // Payload size = 8
+            data.extend_from_slice(&timestamp.to_le_bytes());
+            data.extend_from_slice(&((row + column) as f64).to_le_bytes());
+        }
+    }
+
+    data
+}
+
 fn benchmark_parse_small(c: &mut Criterion) {
     let data = create_wpilog_with_records(100);
     let size = data.len();
@@ -165,6 +292,104 @@ fn benchmark_schema_inference(c: &mut Criterion) {
     });
 }
 
+fn benchmark_double_array(c: &mut Criterion) {
+    let data = create_wpilog_with_double_arrays(1_000, 1_000);
+    let size = data.len();
+
+    let mut group = c.benchmark_group("double_array");
+    group.throughput(Throughput::Bytes(size as u64));
+    group.sample_size(10);
+
+    // Decoding a single double[1000] record in isolation
+    let reader = DataLogReader::new(&data);
+    let record = reader
+        .records()
+        .unwrap()
+        .map(|r| r.unwrap())
+        .find(|r| !r.is_control())
+        .unwrap();
+    group.bench_function("decode_1000_elements", |b| {
+        b.iter(|| black_box(black_box(&record).get_double_array().unwrap()));
+    });
+
+    group.bench_function(BenchmarkId::new("parse_1k_records", size), |b| {
+        b.iter(|| {
+            let df = WpilogParser::from_bytes(black_box(data.clone())).unwrap();
+            black_box(df);
+        });
+    });
+
+    group.finish();
+}
+
+fn benchmark_struct_array(c: &mut Criterion) {
+    // Four swerve module states per record, as logged by most drivetrains
+    let data = create_wpilog_with_struct_arrays(20_000, 4);
+    let size = data.len();
+
+    let mut group = c.benchmark_group("struct_array");
+    group.throughput(Throughput::Bytes(size as u64));
+    group.sample_size(10);
+
+    group.bench_function(BenchmarkId::new("parse_20k_records", size), |b| {
+        b.iter(|| {
+            let df = WpilogParser::from_bytes(black_box(data.clone())).unwrap();
+            black_box(df);
+        });
+    });
+
+    group.finish();
+}
+
+fn benchmark_wide_log(c: &mut Criterion) {
+    // As many columns as a full robot log, every one logged on every row
+    let data = create_wide_wpilog(400, 1_000);
+    let size = data.len();
+
+    let mut group = c.benchmark_group("wide_log");
+    group.throughput(Throughput::Bytes(size as u64));
+    group.sample_size(10);
+
+    group.bench_function(BenchmarkId::new("parse_400_columns", size), |b| {
+        b.iter(|| {
+            let df = WpilogParser::from_bytes(black_box(data.clone())).unwrap();
+            black_box(df);
+        });
+    });
+
+    group.finish();
+}
+
+fn benchmark_sparse_struct_column(c: &mut Criterion) {
+    let mut registry = StructRegistry::new();
+    registry
+        .register("Pose2d".to_string(), "double x; double y; double theta")
+        .unwrap();
+    let deserializer = StructDeserializer::new(&registry);
+
+    // A struct entry updated on every third row of a 10k row DataFrame
+    let values: Vec<Option<StructValue>> = (0..10_000)
+        .map(|i| {
+            (i % 3 == 0).then(|| {
+                let data: Vec<u8> = [i as f64, 2.0 * i as f64, 0.5]
+                    .iter()
+                    .flat_map(|v| v.to_le_bytes())
+                    .collect();
+                deserializer.deserialize("Pose2d", &data).unwrap()
+            })
+        })
+        .collect();
+
+    let converter = PolarsConverter::new(&registry);
+    c.bench_function("sparse_struct_column_10k", |b| {
+        b.iter(|| {
+            converter
+                .optional_values_to_series("Pose2d", black_box(&values))
+                .unwrap()
+        });
+    });
+}
+
 criterion_group! {
     name = benches;
     config = Criterion::default().with_profiler(pprof::criterion::PProfProfiler::new(100, Output::Flamegraph(None)));
@@ -173,6 +398,10 @@ criterion_group! {
         benchmark_parse_medium,
         benchmark_parse_large,
         benchmark_parse_very_large,
-        benchmark_schema_inference
+        benchmark_schema_inference,
+        benchmark_double_array,
+        benchmark_struct_array,
+        benchmark_wide_log,
+        benchmark_sparse_struct_column
 }
 criterion_main!(benches);
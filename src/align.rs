@@ -0,0 +1,137 @@
+//! Aligning a parsed log's timestamps to a reference entry.
+//!
+//! FRC analyses usually care about time relative to an event that is itself logged,
+//! such as the start of a match. Shifting the `timestamp` column so the reference
+//! entry's first value lands at t=0 makes logs from different matches comparable.
+
+use crate::error::{Result, WpilogError};
+use polars::prelude::*;
+
+/// Shifts the `timestamp` column of `df` so that t=0 is the timestamp of the first
+/// non-null value of the `reference_entry` column.
+///
+/// Rows before the reference get negative timestamps; no rows are removed. The
+/// aligned column is Int64, even for the UInt64 timestamps of
+/// [`TimestampKind::UInt64`](crate::TimestampKind::UInt64).
+///
+/// # Errors
+///
+/// Returns [`WpilogError::InvalidEntry`] if `df` has no `reference_entry` column or
+/// the column holds no values, and [`WpilogError::Other`] if a timestamp doesn't
+/// fit in an Int64.
+pub fn align_to_entry(mut df: DataFrame, reference_entry: &str) -> Result<DataFrame> {
+    let reference = df.column(reference_entry).map_err(|_| {
+        WpilogError::InvalidEntry(format!("Reference entry '{}' not found", reference_entry))
+    })?;
+    let written = reference.is_not_null();
+
+    let timestamps = df
+        .column("timestamp")?
+        .strict_cast(&DataType::Int64)
+        .map_err(|e| WpilogError::Other(format!("Timestamps can't be aligned as Int64: {}", e)))?;
+    let timestamps = timestamps.i64()?;
+    let origin = written
+        .into_no_null_iter()
+        .position(|logged| logged)
+        .and_then(|row| timestamps.get(row))
+        .ok_or_else(|| {
+            WpilogError::InvalidEntry(format!(
+                "Reference entry '{}' has no values",
+                reference_entry
+            ))
+        })?;
+
+    let aligned = (timestamps - origin).with_name("timestamp".into());
+    df.with_column(aligned.into_series())?;
+    Ok(df)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> DataFrame {
+        df!(
+            "timestamp" => [1_000i64, 2_000, 3_000, 4_000],
+            "/DriverStation/MatchTime" => [None, None, Some(15.0f64), Some(14.9)],
+            "speed" => [Some(0.0f64), Some(0.5), None, Some(1.0)],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_align_to_entry() {
+        let df = align_to_entry(sample(), "/DriverStation/MatchTime").unwrap();
+
+        let timestamps: Vec<Option<i64>> = df
+            .column("timestamp")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(
+            timestamps,
+            vec![Some(-2_000), Some(-1_000), Some(0), Some(1_000)]
+        );
+        assert_eq!(df.width(), 3);
+    }
+
+    #[test]
+    fn test_align_to_missing_entry() {
+        match align_to_entry(sample(), "/DriverStation/Enabled") {
+            Err(WpilogError::InvalidEntry(msg)) => {
+                assert_eq!(msg, "Reference entry '/DriverStation/Enabled' not found")
+            }
+            other => panic!("Expected InvalidEntry, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_align_to_empty_entry() {
+        let df = df!(
+            "timestamp" => [1_000i64],
+            "never" => [None::<f64>],
+        )
+        .unwrap();
+
+        assert!(matches!(
+            align_to_entry(df, "never"),
+            Err(WpilogError::InvalidEntry(_))
+        ));
+    }
+
+    #[test]
+    fn test_align_uint64_timestamps() {
+        let mut df = sample();
+        df.with_column(Series::new(
+            "timestamp".into(),
+            [1_000u64, 2_000, 3_000, 4_000],
+        ))
+        .unwrap();
+        let df = align_to_entry(df, "/DriverStation/MatchTime").unwrap();
+
+        let timestamps: Vec<Option<i64>> = df
+            .column("timestamp")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(
+            timestamps,
+            vec![Some(-2_000), Some(-1_000), Some(0), Some(1_000)]
+        );
+
+        let mut df = sample();
+        df.with_column(Series::new(
+            "timestamp".into(),
+            [1_000u64, 2_000, u64::MAX, 4_000],
+        ))
+        .unwrap();
+        assert!(matches!(
+            align_to_entry(df, "/DriverStation/MatchTime"),
+            Err(WpilogError::Other(_))
+        ));
+    }
+}
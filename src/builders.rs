@@ -7,6 +7,7 @@
 //! - Proper Polars List serialization for array types
 
 use crate::error::{Result, WpilogError};
+use crate::options::{EnumOutput, ProtobufDecoder, TimestampKind};
 use crate::struct_support::{PolarsConverter, StructRegistry};
 use crate::types::{PolarsDataType, PolarsValue};
 use polars::prelude::*;
@@ -16,6 +17,9 @@ pub struct ColumnBuilder {
     name: String,
     dtype: PolarsDataType,
     values: Vec<Option<PolarsValue>>,
+    categorical: bool,
+    enum_output: EnumOutput,
+    protobuf_dtype: Option<PolarsDataType>,
 }
 
 impl ColumnBuilder {
@@ -25,9 +29,33 @@ impl ColumnBuilder {
             name,
             dtype,
             values: Vec::with_capacity(capacity),
+            categorical: false,
+            enum_output: EnumOutput::Raw,
+            protobuf_dtype: None,
         }
     }
 
+    /// Builds string columns as `Categorical` instead of `String`.
+    pub fn with_categorical(mut self, categorical: bool) -> Self {
+        self.categorical = categorical;
+        self
+    }
+
+    /// Sets how enum-annotated struct fields are built.
+    pub fn with_enum_output(mut self, enum_output: EnumOutput) -> Self {
+        self.enum_output = enum_output;
+        self
+    }
+
+    /// Builds a `proto:*` column with the type `decoder` declares for its message,
+    /// rather than as strings.
+    pub fn with_protobuf_decoder(mut self, decoder: Option<&ProtobufDecoder>) -> Self {
+        if let (PolarsDataType::Protobuf(message_name), Some(decoder)) = (&self.dtype, decoder) {
+            self.protobuf_dtype = decoder.dtype(message_name);
+        }
+        self
+    }
+
     /// Adds a value to the builder.
     pub fn push(&mut self, value: Option<PolarsValue>) {
         self.values.push(value);
@@ -48,6 +76,13 @@ impl ColumnBuilder {
         self.values.is_empty()
     }
 
+    /// Builds a `<name>__present` Boolean series that is true in rows where a value
+    /// was pushed, even if the value itself builds as null.
+    pub fn presence(&self) -> Series {
+        let present: Vec<bool> = self.values.iter().map(Option::is_some).collect();
+        Series::new(format!("{}__present", self.name).into(), present)
+    }
+
     /// Builds a Polars Series from the accumulated values.
     pub fn build(self, registry: Option<&StructRegistry>) -> Result<Series> {
         match self.dtype {
@@ -84,6 +119,23 @@ impl ColumnBuilder {
                     .collect();
                 Ok(Series::new(self.name.as_str().into(), values))
             }
+            PolarsDataType::Int8
+            | PolarsDataType::Int16
+            | PolarsDataType::Int32
+            | PolarsDataType::UInt8
+            | PolarsDataType::UInt16
+            | PolarsDataType::UInt32 => {
+                let values: Vec<Option<i64>> = self
+                    .values
+                    .into_iter()
+                    .map(|opt| match opt {
+                        Some(PolarsValue::Int64(v)) => Some(v),
+                        _ => None,
+                    })
+                    .collect();
+                let series = Series::new(self.name.as_str().into(), values);
+                Ok(series.cast(&self.dtype.to_polars_dtype())?)
+            }
             PolarsDataType::Boolean => {
                 let values: Vec<Option<bool>> = self
                     .values
@@ -104,7 +156,23 @@ impl ColumnBuilder {
                         _ => None,
                     })
                     .collect();
-                Ok(Series::new(self.name.as_str().into(), values))
+                let series = Series::new(self.name.as_str().into(), values);
+                if self.categorical {
+                    Ok(series.cast(&DataType::from_categories(Categories::global()))?)
+                } else {
+                    Ok(series)
+                }
+            }
+            PolarsDataType::Binary => {
+                let values: BinaryChunked = self
+                    .values
+                    .iter()
+                    .map(|opt| match opt {
+                        Some(PolarsValue::Binary(v)) => Some(v.as_slice()),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(values.with_name(self.name.as_str().into()).into_series())
             }
             PolarsDataType::BooleanArray => {
                 let values: Vec<Option<Series>> = self
@@ -169,7 +237,7 @@ impl ColumnBuilder {
             PolarsDataType::Struct(ref struct_name) => {
                 // Convert struct values to Polars structs
                 if let Some(reg) = registry {
-                    let converter = PolarsConverter::new(reg);
+                    let converter = PolarsConverter::new(reg).with_enum_output(self.enum_output);
 
                     // Collect struct values, preserving None for sparse data
                     let struct_values: Vec<Option<crate::struct_support::StructValue>> = self
@@ -201,7 +269,7 @@ impl ColumnBuilder {
             PolarsDataType::StructArray(ref struct_name) => {
                 // Convert struct array values to Polars List(Struct)
                 if let Some(reg) = registry {
-                    let converter = PolarsConverter::new(reg);
+                    let converter = PolarsConverter::new(reg).with_enum_output(self.enum_output);
 
                     // Collect struct array values, preserving None for sparse data
                     let struct_array_values: Vec<Option<Vec<crate::struct_support::StructValue>>> =
@@ -213,9 +281,12 @@ impl ColumnBuilder {
                             })
                             .collect();
 
+                    // Every row's inner series must have exactly this dtype, or the
+                    // rows can't be combined into one List column
+                    let struct_dtype = converter.schema_to_dtype(struct_name)?;
+
                     if struct_array_values.is_empty() {
                         // Return empty list series
-                        let struct_dtype = converter.schema_to_dtype(struct_name)?;
                         let list_dtype = DataType::List(Box::new(struct_dtype));
                         return Ok(Series::new_empty(self.name.as_str().into(), &list_dtype));
                     }
@@ -225,12 +296,14 @@ impl ColumnBuilder {
                     for opt_structs in struct_array_values {
                         let series = match opt_structs {
                             Some(structs) if !structs.is_empty() => {
-                                // Convert array of structs to a series
-                                converter.values_to_series(struct_name, &structs)?
+                                // Convert array of structs to a series, normalized to the
+                                // schema's field order and types
+                                converter
+                                    .values_to_series(struct_name, &structs)?
+                                    .cast(&struct_dtype)?
                             }
                             _ => {
                                 // Empty or null array
-                                let struct_dtype = converter.schema_to_dtype(struct_name)?;
                                 Series::new_empty("".into(), &struct_dtype)
                             }
                         };
@@ -247,6 +320,20 @@ impl ColumnBuilder {
                     ))
                 }
             }
+            PolarsDataType::Protobuf(_) => {
+                // Decoded columns take the type the decoder declares for the message;
+                // undecoded values are strings
+                let dtype = match &self.protobuf_dtype {
+                    Some(PolarsDataType::Protobuf(_)) | None => PolarsDataType::String,
+                    Some(dtype) => dtype.clone(),
+                };
+                ColumnBuilder { dtype, ..self }.build(registry)
+            }
+            PolarsDataType::ProtobufArray(_) => ColumnBuilder {
+                dtype: PolarsDataType::String,
+                ..self
+            }
+            .build(registry),
         }
     }
 }
@@ -256,6 +343,9 @@ pub struct DataFrameBuilder<'a> {
     timestamp: Vec<i64>,
     columns: Vec<ColumnBuilder>,
     registry: Option<&'a StructRegistry>,
+    flatten_structs: bool,
+    presence_columns: bool,
+    timestamp_kind: TimestampKind,
 }
 
 impl<'a> DataFrameBuilder<'a> {
@@ -268,7 +358,7 @@ impl<'a> DataFrameBuilder<'a> {
     ) -> Self {
         let columns = column_names
             .into_iter()
-            .zip(column_types.into_iter())
+            .zip(column_types)
             .map(|(name, dtype)| ColumnBuilder::new(name, dtype, capacity))
             .collect();
 
@@ -276,6 +366,9 @@ impl<'a> DataFrameBuilder<'a> {
             timestamp: Vec::with_capacity(capacity),
             columns,
             registry: None,
+            flatten_structs: false,
+            presence_columns: false,
+            timestamp_kind: TimestampKind::Int64,
         }
     }
 
@@ -285,6 +378,57 @@ impl<'a> DataFrameBuilder<'a> {
         self
     }
 
+    /// Builds string columns as `Categorical` instead of `String`.
+    pub fn with_categorical_strings(mut self, categorical: bool) -> Self {
+        self.columns = self
+            .columns
+            .into_iter()
+            .map(|c| c.with_categorical(categorical))
+            .collect();
+        self
+    }
+
+    /// Sets how enum-annotated struct fields are built.
+    pub fn with_enum_output(mut self, enum_output: EnumOutput) -> Self {
+        self.columns = self
+            .columns
+            .into_iter()
+            .map(|c| c.with_enum_output(enum_output))
+            .collect();
+        self
+    }
+
+    /// Builds `proto:*` columns with the types `decoder` declares.
+    pub fn with_protobuf_decoder(mut self, decoder: Option<&ProtobufDecoder>) -> Self {
+        self.columns = self
+            .columns
+            .into_iter()
+            .map(|c| c.with_protobuf_decoder(decoder))
+            .collect();
+        self
+    }
+
+    /// Builds struct columns as one column per leaf field, named `parent.child`.
+    pub fn with_flattened_structs(mut self, flatten: bool) -> Self {
+        self.flatten_structs = flatten;
+        self
+    }
+
+    /// Follows each column with a `<name>__present` column marking the rows where
+    /// it was updated.
+    pub fn with_presence_columns(mut self, presence: bool) -> Self {
+        self.presence_columns = presence;
+        self
+    }
+
+    /// Sets the data type of the timestamp column. With `TimestampKind::UInt64`,
+    /// pushed timestamps are reinterpreted as unsigned, so values above `i64::MAX`
+    /// can be carried as negative `i64`s.
+    pub fn with_timestamp_kind(mut self, kind: TimestampKind) -> Self {
+        self.timestamp_kind = kind;
+        self
+    }
+
     /// Adds a row to the builder.
     /// Values is a sparse map from column index to value.
     pub fn push_row(&mut self, timestamp: i64, values: &[Option<PolarsValue>]) {
@@ -304,14 +448,33 @@ impl<'a> DataFrameBuilder<'a> {
         let mut columns = Vec::with_capacity(self.columns.len() + 1);
 
         // Add timestamp column first
-        columns.push(Series::new("timestamp".into(), self.timestamp).into());
+        let timestamp = match self.timestamp_kind {
+            TimestampKind::Int64 => Series::new("timestamp".into(), self.timestamp),
+            TimestampKind::UInt64 => Series::new(
+                "timestamp".into(),
+                self.timestamp
+                    .iter()
+                    .map(|&t| t as u64)
+                    .collect::<Vec<u64>>(),
+            ),
+        };
+        columns.push(timestamp.into());
 
         // Add all other columns
         for builder in self.columns {
-            columns.push(builder.build(self.registry)?.into());
+            let presence = self.presence_columns.then(|| builder.presence());
+            let series = builder.build(self.registry)?;
+            if self.flatten_structs {
+                flatten_struct(series, &mut columns)?;
+            } else {
+                columns.push(series.into());
+            }
+            if let Some(presence) = presence {
+                columns.push(presence.into());
+            }
         }
 
-        DataFrame::new(columns).map_err(|e| WpilogError::PolarsError(e))
+        DataFrame::new(columns).map_err(WpilogError::PolarsError)
     }
 
     /// Returns the number of rows currently in the builder.
@@ -325,6 +488,27 @@ impl<'a> DataFrameBuilder<'a> {
     }
 }
 
+/// Appends a struct series to `columns` as one series per leaf field, named
+/// `parent.child`, recursing into nested structs. Other series, including lists of
+/// structs, are appended unchanged.
+///
+/// Rows where the struct is null are null in every field.
+fn flatten_struct(series: Series, columns: &mut Vec<Column>) -> Result<()> {
+    if !matches!(series.dtype(), DataType::Struct(_)) {
+        columns.push(series.into());
+        return Ok(());
+    }
+
+    let valid = series.is_not_null();
+    for field in series.struct_()?.fields_as_series() {
+        let name = PlSmallStr::from(format!("{}.{}", series.name(), field.name()));
+        let nulls = Series::full_null(name.clone(), field.len(), field.dtype());
+        let field = field.zip_with(&valid, &nulls)?.with_name(name);
+        flatten_struct(field, columns)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -342,6 +526,33 @@ mod tests {
         assert_eq!(series.name(), "test");
     }
 
+    #[test]
+    fn test_presence_columns() {
+        let mut builder = DataFrameBuilder::new(
+            vec!["a".to_string(), "b".to_string()],
+            vec![PolarsDataType::Float64, PolarsDataType::Boolean],
+            3,
+        )
+        .with_presence_columns(true);
+        builder.push_row(1, &[Some(PolarsValue::Float64(1.0)), None]);
+        builder.push_row(2, &[None, Some(PolarsValue::Boolean(true))]);
+        builder.push_row(3, &[Some(PolarsValue::Null), None]);
+
+        let df = builder.build().unwrap();
+
+        let names: Vec<&str> = df.get_column_names().iter().map(|n| n.as_str()).collect();
+        assert_eq!(names, ["timestamp", "a", "a__present", "b", "b__present"]);
+        let present: Vec<Option<bool>> = df
+            .column("a__present")
+            .unwrap()
+            .bool()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(present, [Some(true), Some(false), Some(true)]);
+        assert_eq!(df.column("a").unwrap().null_count(), 2);
+    }
+
     #[test]
     fn test_column_builder_int64_array() {
         let mut builder = ColumnBuilder::new("test".to_string(), PolarsDataType::Int64Array, 10);
@@ -354,6 +565,68 @@ mod tests {
         assert_eq!(series.len(), 3);
     }
 
+    #[test]
+    fn test_column_builder_categorical_string() {
+        let mut builder = ColumnBuilder::new("state".to_string(), PolarsDataType::String, 10)
+            .with_categorical(true);
+
+        builder.push(Some(PolarsValue::String("Auto".to_string())));
+        builder.push(None);
+        builder.push(Some(PolarsValue::String("Teleop".to_string())));
+        builder.push(Some(PolarsValue::String("Auto".to_string())));
+
+        let series = builder.build(None).unwrap();
+        assert!(matches!(series.dtype(), DataType::Categorical(..)));
+        assert_eq!(series.len(), 4);
+        assert_eq!(series.null_count(), 1);
+        assert!(series.is_null().get(1).unwrap());
+    }
+
+    #[test]
+    fn test_column_builder_struct_array_with_empty_rows() {
+        use crate::struct_support::{FieldValue, StructValue};
+
+        let mut registry = StructRegistry::new();
+        registry
+            .register(
+                "ModuleState".to_string(),
+                "double speed; char id; uint8 flags:3",
+            )
+            .unwrap();
+
+        let state = |speed: f64| StructValue {
+            struct_name: "ModuleState".to_string(),
+            fields: [
+                ("speed".to_string(), FieldValue::Float64(speed)),
+                ("id".to_string(), FieldValue::Char('A')),
+                ("flags".to_string(), FieldValue::Int64(5)),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        let mut builder = ColumnBuilder::new(
+            "states".to_string(),
+            PolarsDataType::StructArray("ModuleState".to_string()),
+            10,
+        );
+        builder.push(Some(PolarsValue::StructArray(vec![])));
+        builder.push(Some(PolarsValue::StructArray(vec![state(1.0), state(2.0)])));
+        builder.push(None);
+        builder.push(Some(PolarsValue::StructArray(vec![state(3.0)])));
+
+        let series = builder.build(Some(&registry)).unwrap();
+        let struct_dtype = PolarsConverter::new(&registry)
+            .schema_to_dtype("ModuleState")
+            .unwrap();
+        assert_eq!(series.dtype(), &DataType::List(Box::new(struct_dtype)));
+
+        let lengths: Vec<usize> = (0..4)
+            .map(|i| series.list().unwrap().get_as_series(i).unwrap().len())
+            .collect();
+        assert_eq!(lengths, [0, 2, 0, 1]);
+    }
+
     #[test]
     fn test_dataframe_builder() {
         let mut builder = DataFrameBuilder::new(
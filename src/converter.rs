@@ -5,12 +5,72 @@
 //! 2. Second pass: Accumulate data into column builders
 
 use crate::builders::DataFrameBuilder;
-use crate::datalog::{DataLogReader, DataLogRecord};
+use crate::datalog::{DataLogIterator, DataLogReader, DataLogRecord};
+use crate::diagnostics::{Diagnostics, UnresolvedStruct};
 use crate::error::{Result, WpilogError};
-use crate::schema::WpilogSchema;
-use crate::struct_support::{StructDeserializer, StructRegistry};
-use crate::types::{PolarsDataType, PolarsValue};
+use crate::options::{ErrorPolicy, ParseOptions, TimestampKind, UnknownTypePolicy};
+use crate::schema::{ColumnInfo, WpilogSchema};
+use crate::struct_support::{SchemaParser, StructDeserializer, StructRegistry};
+use crate::types::{normalize_type_name, PolarsDataType, PolarsValue};
 use polars::prelude::*;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// The result of converting a WPILog file with options.
+#[derive(Debug, Clone)]
+pub struct ParsedLog {
+    pub dataframe: DataFrame,
+    pub diagnostics: Diagnostics,
+    unwritten_entries: Vec<String>,
+    schema: WpilogSchema,
+}
+
+impl ParsedLog {
+    /// Names of entries that had a START record but never received a data record,
+    /// in column order.
+    ///
+    /// Their columns are entirely null, unlike columns whose entries were written but
+    /// happen to hold only nulls.
+    pub fn unwritten_entries(&self) -> &[String] {
+        &self.unwritten_entries
+    }
+
+    /// Metadata for an entry column, keyed like the fields of
+    /// [`WpilogSchema::to_arrow_schema`]: `wpilog.entry_id` holds the ID of the entry
+    /// that produced the column and `wpilog.type` its WPILog type string.
+    ///
+    /// Polars DataFrames do not carry per-field metadata, so it is read from
+    /// [`schema`](Self::schema). Returns `None` for `timestamp` and unknown columns.
+    pub fn field_metadata(&self, column: &str) -> Option<HashMap<String, String>> {
+        self.schema
+            .columns()
+            .iter()
+            .find(|c| c.name == column)
+            .map(ColumnInfo::field_metadata)
+    }
+
+    /// The schema the DataFrame was built from, with the WPILog type and entry ID of
+    /// every column.
+    pub fn schema(&self) -> &WpilogSchema {
+        &self.schema
+    }
+}
+
+/// Limits on the rows a conversion produces.
+#[derive(Debug, Clone, Copy, Default)]
+struct RowBounds {
+    /// Keep only the last this many microseconds of the log.
+    tail_us: Option<u64>,
+    /// Stop after this many rows.
+    max_rows: Option<usize>,
+    /// Hand off rows in chunks of this many rows.
+    chunk_rows: Option<usize>,
+    /// Read only the records in `[start, end)`, given as record-aligned offsets.
+    byte_range: Option<(usize, usize)>,
+}
+
+/// Receives each complete chunk of rows during a chunked conversion.
+type ChunkSink<'c> = &'c mut dyn FnMut(DataFrame) -> Result<()>;
 
 /// Converts WPILog binary data to a Polars DataFrame.
 pub struct WpilogConverter;
@@ -18,6 +78,39 @@ pub struct WpilogConverter;
 impl WpilogConverter {
     /// Converts WPILog data from a byte slice to a Polars DataFrame.
     pub fn from_bytes(data: &[u8]) -> Result<DataFrame> {
+        Ok(Self::from_bytes_with_options(data, &ParseOptions::default())?.dataframe)
+    }
+
+    /// Converts WPILog data from a byte slice using the given options.
+    /// Returns the DataFrame along with any diagnostics collected during conversion.
+    pub fn from_bytes_with_options(data: &[u8], options: &ParseOptions) -> Result<ParsedLog> {
+        Self::convert(data, StructRegistry::new(), options)
+    }
+
+    /// Converts WPILog data from a byte slice, also returning the schema built while
+    /// converting it.
+    ///
+    /// Useful for the WPILog type information a DataFrame does not carry, without
+    /// reading the data again to infer the schema.
+    pub fn from_bytes_with_schema(data: &[u8]) -> Result<(DataFrame, WpilogSchema)> {
+        let parsed = Self::convert(data, StructRegistry::new(), &ParseOptions::default())?;
+        Ok((parsed.dataframe, parsed.schema))
+    }
+
+    /// Converts WPILog data using a pre-populated struct registry.
+    ///
+    /// Struct schemas found in the file are registered on top of `registry`, so
+    /// externally-known definitions can resolve structs the file references but
+    /// does not define.
+    pub fn from_bytes_with_registry(data: &[u8], registry: StructRegistry) -> Result<DataFrame> {
+        Ok(Self::convert(data, registry, &ParseOptions::default())?.dataframe)
+    }
+
+    /// Infers the schema and registers the file's struct definitions without
+    /// accumulating any data.
+    ///
+    /// Structs whose definitions are missing or invalid are left out of the registry.
+    pub fn infer_schema_and_registry(data: &[u8]) -> Result<(WpilogSchema, StructRegistry)> {
         let reader = DataLogReader::new(data);
 
         if !reader.is_valid() {
@@ -26,31 +119,309 @@ impl WpilogConverter {
             ));
         }
 
+        let (registry, schema) = Self::build_registry_and_schema(
+            &reader,
+            StructRegistry::new(),
+            &ParseOptions::default(),
+            &mut Diagnostics::new(),
+        )?;
+        Ok((schema, registry))
+    }
+
+    /// Converts only the last `window_us` microseconds of WPILog data.
+    ///
+    /// The data is scanned three times: once for the schema, once reading only
+    /// record headers to find the latest data timestamp, and once more decoding
+    /// only the records within `[max - window_us, max]`. Records before the window are not decoded, except
+    /// for each entry's latest one, which seeds a first row at the window start so
+    /// every entry that was already written starts with its last-known value.
+    pub fn from_bytes_tail(data: &[u8], window_us: u64) -> Result<DataFrame> {
+        let bounds = RowBounds {
+            tail_us: Some(window_us),
+            ..Default::default()
+        };
+        Ok(Self::convert_bounded(
+            data,
+            StructRegistry::new(),
+            &ParseOptions::default(),
+            bounds,
+            None,
+        )?
+        .dataframe)
+    }
+
+    /// Converts only the first `n_rows` rows of WPILog data.
+    ///
+    /// A row is a distinct timestamp, so `n_rows` bounds the DataFrame's height.
+    /// Accumulation stops as soon as the last row is complete, which bounds both
+    /// time and memory; the schema pass still reads every record header. Entries
+    /// first written after those rows keep their columns, entirely null.
+    pub fn from_bytes_head(data: &[u8], n_rows: usize) -> Result<DataFrame> {
+        let bounds = RowBounds {
+            max_rows: Some(n_rows),
+            ..Default::default()
+        };
+        Ok(Self::convert_bounded(
+            data,
+            StructRegistry::new(),
+            &ParseOptions::default(),
+            bounds,
+            None,
+        )?
+        .dataframe)
+    }
+
+    /// Converts only the records in the byte range `range` of WPILog data, using a
+    /// schema and registry inferred beforehand, e.g. by
+    /// [`infer_schema_and_registry`](Self::infer_schema_and_registry).
+    ///
+    /// This lets one large log be parsed as independent ranges, e.g. in parallel,
+    /// with every range producing the same columns. `range` must start and end on
+    /// record boundaries, which [`next_record_boundary`](crate::next_record_boundary)
+    /// finds; a record straddling the end of the range is not read. FINISH records
+    /// before the range are not seen, so an entry finished earlier in the log keeps
+    /// any data written to it within the range.
+    pub fn from_bytes_range_with_schema(
+        data: &[u8],
+        range: Range<usize>,
+        schema: &WpilogSchema,
+        registry: &StructRegistry,
+    ) -> Result<DataFrame> {
+        let reader = DataLogReader::new(data);
+        let header_end = reader.records()?.offset();
+        if range.start < header_end || range.start > range.end || range.end > data.len() {
+            return Err(WpilogError::Other(format!(
+                "Byte range {}..{} is outside the records of a {}-byte log",
+                range.start,
+                range.end,
+                data.len()
+            )));
+        }
+
+        let mut diagnostics = Diagnostics::new();
+        let mut schema = schema.clone();
+        Self::downgrade_unresolved_structs(&mut schema, registry, &mut diagnostics);
+
+        let bounds = RowBounds {
+            byte_range: Some((range.start, range.end)),
+            ..Default::default()
+        };
+        let (dataframe, _) = Self::accumulate_data(
+            reader,
+            &schema,
+            registry.clone(),
+            &ParseOptions::default(),
+            bounds,
+            None,
+            &mut diagnostics,
+        )?;
+        Ok(dataframe)
+    }
+
+    /// Converts WPILog data using a pre-populated struct registry and the given options.
+    pub(crate) fn convert(
+        data: &[u8],
+        registry: StructRegistry,
+        options: &ParseOptions,
+    ) -> Result<ParsedLog> {
+        Self::convert_bounded(data, registry, options, RowBounds::default(), None)
+    }
+
+    /// Converts WPILog data in chunks of at most `chunk_rows` rows, passing each
+    /// chunk to `on_chunk` as soon as it is complete so the whole DataFrame is never
+    /// held in memory.
+    ///
+    /// Every chunk has the same columns, and at least one chunk is passed even if the
+    /// log has no rows. Returns the diagnostics of the whole conversion.
+    pub(crate) fn convert_chunked(
+        data: &[u8],
+        registry: StructRegistry,
+        options: &ParseOptions,
+        chunk_rows: usize,
+        on_chunk: ChunkSink,
+    ) -> Result<Diagnostics> {
+        if chunk_rows == 0 {
+            return Err(WpilogError::Other(
+                "Chunk size must be at least one row".to_string(),
+            ));
+        }
+
+        let mut chunks = 0;
+        let mut counted = |chunk: DataFrame| {
+            chunks += 1;
+            on_chunk(chunk)
+        };
+        let bounds = RowBounds {
+            chunk_rows: Some(chunk_rows),
+            ..Default::default()
+        };
+        let parsed = Self::convert_bounded(data, registry, options, bounds, Some(&mut counted))?;
+
+        // The rows after the last full chunk
+        if chunks == 0 || parsed.dataframe.height() > 0 {
+            on_chunk(parsed.dataframe)?;
+        }
+        Ok(parsed.diagnostics)
+    }
+
+    /// Converts WPILog data, keeping only the rows within `bounds`.
+    ///
+    /// With `bounds.chunk_rows`, full chunks are passed to `on_chunk` and the
+    /// returned DataFrame holds only the remaining rows.
+    fn convert_bounded(
+        data: &[u8],
+        registry: StructRegistry,
+        options: &ParseOptions,
+        bounds: RowBounds,
+        on_chunk: Option<ChunkSink>,
+    ) -> Result<ParsedLog> {
+        let reader = DataLogReader::new(data);
+
+        if !reader.is_valid() {
+            return Err(WpilogError::InvalidFormat(
+                "Invalid WPILog file header".to_string(),
+            ));
+        }
+
+        let mut diagnostics = Diagnostics::new();
+
         // First pass: build struct registry and infer schema
-        let (registry, schema) = Self::build_registry_and_schema(&reader)?;
+        let (registry, mut schema) =
+            Self::build_registry_and_schema(&reader, registry, options, &mut diagnostics)?;
+        Self::downgrade_unresolved_structs(&mut schema, &registry, &mut diagnostics);
 
         // Second pass: accumulate data
-        Self::accumulate_data(reader, &schema, registry)
+        let (dataframe, unwritten_entries) = Self::accumulate_data(
+            reader,
+            &schema,
+            registry,
+            options,
+            bounds,
+            on_chunk,
+            &mut diagnostics,
+        )?;
+
+        Ok(ParsedLog {
+            dataframe,
+            diagnostics,
+            unwritten_entries,
+            schema,
+        })
+    }
+
+    /// Chooses the column type for an entry whose WPILog type is not recognized,
+    /// according to `options.unknown_type_policy`.
+    fn unknown_type_dtype(
+        type_name: &str,
+        entry_name: &str,
+        options: &ParseOptions,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<PolarsDataType> {
+        match options.unknown_type_policy {
+            UnknownTypePolicy::String => {
+                diagnostics.warn(format!(
+                    "Unknown WPILog type '{}' for '{}', treating as binary string",
+                    type_name, entry_name
+                ));
+                Ok(PolarsDataType::String)
+            }
+            UnknownTypePolicy::Binary => Ok(PolarsDataType::Binary),
+            UnknownTypePolicy::Error => Err(WpilogError::SchemaError(format!(
+                "Unknown WPILog type '{}' for entry '{}'",
+                type_name, entry_name
+            ))),
+        }
+    }
+
+    /// Applies `rename` to the schema's column names, rejecting a renamed column
+    /// whose new name is already taken, including by a `<name>__present` column
+    /// when `presence_columns` is set.
+    fn rename_columns(
+        schema: &mut WpilogSchema,
+        rename: &HashMap<String, String>,
+        presence_columns: bool,
+    ) -> Result<()> {
+        for column in schema.columns_mut() {
+            if let Some(new_name) = rename.get(&column.name) {
+                column.name = new_name.clone();
+            }
+        }
+
+        let presence_names: Vec<String> = if presence_columns {
+            schema
+                .columns()
+                .iter()
+                .map(|c| format!("{}__present", c.name))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let mut seen = std::collections::HashSet::from(["timestamp"]);
+        seen.extend(presence_names.iter().map(String::as_str));
+        for column in schema.columns() {
+            if !seen.insert(column.name.as_str()) && rename.values().any(|n| *n == column.name) {
+                return Err(WpilogError::SchemaError(format!(
+                    "Renamed column '{}' collides with another column",
+                    column.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects a column named like the `<name>__present` column generated for
+    /// another column.
+    fn check_presence_names(schema: &WpilogSchema) -> Result<()> {
+        let names: std::collections::HashSet<&str> =
+            schema.columns().iter().map(|c| c.name.as_str()).collect();
+        for column in schema.columns() {
+            if let Some(base) = column.name.strip_suffix("__present") {
+                if names.contains(base) {
+                    return Err(WpilogError::SchemaError(format!(
+                        "Entry '{}' collides with the presence column of entry '{}'",
+                        column.name, base
+                    )));
+                }
+            }
+        }
+        Ok(())
     }
 
     /// First pass: builds struct registry from structschema entries and infers schema.
     /// Optimized to use a single loop by processing both struct schemas and main schema columns simultaneously.
-    fn build_registry_and_schema(reader: &DataLogReader) -> Result<(StructRegistry, WpilogSchema)> {
-        let mut registry = StructRegistry::new();
+    /// Structs found in the file are registered into `registry`, which may already hold definitions.
+    pub(crate) fn build_registry_and_schema(
+        reader: &DataLogReader,
+        mut registry: StructRegistry,
+        options: &ParseOptions,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<(StructRegistry, WpilogSchema)> {
         let mut schema = WpilogSchema::new();
         let mut schema_entries = std::collections::HashMap::new();
         let mut schema_defs = std::collections::HashMap::new();
         let mut finished_entries = std::collections::HashSet::new();
+        let mut data_entries = std::collections::HashSet::new();
 
         // Single pass: collect struct schema definitions AND infer main schema simultaneously
-        for record_result in reader.records()? {
+        for record_result in reader.records()?.with_recovery(options.recover) {
             let record = record_result?;
 
+            if !record.is_control() {
+                data_entries.insert(record.entry);
+            }
+
             if record.is_start() {
-                let start_data = record.get_start_data()?;
+                let start_data = match record.get_start_data() {
+                    Ok(start_data) => start_data,
+                    Err(e) => {
+                        // Reported by the second pass
+                        Self::skip_malformed_control(&record, e, options, None)?;
+                        continue;
+                    }
+                };
 
                 // Check if this is a struct schema definition entry
-                if start_data.type_name == "structschema" {
+                if normalize_type_name(&start_data.type_name) == "structschema" {
                     // Store the mapping from entry ID to schema name
                     // Format: "/.schema/struct:StructName" -> "StructName"
                     let simple_name =
@@ -62,12 +433,50 @@ impl WpilogConverter {
                     schema_entries.insert(start_data.entry, simple_name);
                 } else {
                     // This is a regular data column - add to schema (unless already finished)
-                    if !finished_entries.contains(&start_data.entry) {
-                        let dtype = PolarsDataType::from_wpilog_type(&start_data.type_name)?;
+                    if let Some(existing) = schema.get_column_by_entry(start_data.entry) {
+                        // A repeated START without a FINISH, e.g. after a reconnect. As
+                        // with a reused entry ID, the first declaration is kept
+                        if existing.name != start_data.name
+                            || existing.type_name != start_data.type_name
+                        {
+                            diagnostics.warn(format!(
+                                "Entry {} restarted as '{}' ({}) while declared as '{}' ({}); keeping the first declaration and skipping its later records",
+                                start_data.entry,
+                                start_data.name,
+                                start_data.type_name,
+                                existing.name,
+                                existing.type_name
+                            ));
+                        }
+                    } else if !finished_entries.contains(&start_data.entry) {
+                        let (type_name, known) = match options.type_overrides.get(&start_data.name)
+                        {
+                            Some(type_name) => {
+                                (type_name, PolarsDataType::from_known_wpilog_type(type_name))
+                            }
+                            None => (
+                                &start_data.type_name,
+                                PolarsDataType::from_known_start(
+                                    &start_data.type_name,
+                                    &start_data.name,
+                                    &start_data.metadata,
+                                ),
+                            ),
+                        };
+                        let dtype = match known {
+                            Some(dtype) => dtype,
+                            None => Self::unknown_type_dtype(
+                                type_name,
+                                &start_data.name,
+                                options,
+                                diagnostics,
+                            )?,
+                        };
                         let column = crate::schema::ColumnInfo {
                             entry_id: start_data.entry,
                             name: start_data.name,
                             dtype,
+                            type_name: start_data.type_name,
                             nullable: true,
                             metadata: start_data.metadata,
                         };
@@ -76,21 +485,42 @@ impl WpilogConverter {
                 }
             } else if record.is_finish() {
                 // Track finished entries (needed by both processes)
-                let entry_id = record.get_finish_entry()?;
-                finished_entries.insert(entry_id);
+                match record.get_finish_entry() {
+                    Ok(entry_id) => {
+                        finished_entries.insert(entry_id);
+                    }
+                    Err(e) => Self::skip_malformed_control(&record, e, options, None)?,
+                }
             } else if !record.is_control() && schema_entries.contains_key(&record.entry) {
-                // This is struct schema data - collect it immediately
+                // This is struct schema data - collect it immediately if it parses
                 let struct_name = schema_entries.get(&record.entry).unwrap().clone();
-                let schema_text = record.get_string();
-                schema_defs.insert(struct_name, schema_text);
+                let raw_text = record.get_string();
+                match SchemaParser::prepare(&raw_text) {
+                    Ok(schema_text) => {
+                        schema_defs.insert(struct_name, schema_text.to_string());
+                    }
+                    Err(e) => diagnostics.warn(format!(
+                        "Invalid schema for struct '{}': {}",
+                        struct_name, e
+                    )),
+                }
             }
         }
 
-        // Validate that we found at least one column
-        if schema.num_columns() == 0 {
-            return Err(WpilogError::SchemaError(
-                "No columns found in WPILog file".to_string(),
-            ));
+        if options.drop_empty_columns {
+            schema.retain_columns(|c| data_entries.contains(&c.entry_id));
+        }
+        if let Some(types) = &options.only_types {
+            schema.retain_columns(|c| types.iter().any(|t| c.matches_type(t)));
+        }
+        if !options.rename.is_empty() {
+            Self::rename_columns(&mut schema, &options.rename, options.presence_columns)?;
+        }
+        if options.presence_columns {
+            Self::check_presence_names(&schema)?;
+        }
+        if options.sort_columns {
+            schema.sort_columns_by_name();
         }
 
         // Register structs with dependency resolution (retry until all are registered or no progress)
@@ -119,30 +549,146 @@ impl WpilogConverter {
 
         // Report any structs that couldn't be registered
         if registered.len() < schema_defs.len() {
-            for (struct_name, _) in &schema_defs {
-                if !registered.contains(struct_name) {
-                    eprintln!(
-                        "Warning: Failed to register struct '{}' - possible missing dependency",
+            // A dependency cycle can never be resolved and indicates a broken schema
+            let unregistered: std::collections::HashMap<String, String> = schema_defs
+                .iter()
+                .filter(|(name, _)| !registered.contains(*name))
+                .map(|(name, text)| (name.clone(), text.clone()))
+                .collect();
+            if let Some(cycle) = StructRegistry::find_dependency_cycle(&unregistered) {
+                return Err(WpilogError::SchemaError(format!(
+                    "Struct dependency cycle: {}",
+                    cycle.join(" -> ")
+                )));
+            }
+
+            let mut names: Vec<&String> = unregistered.keys().collect();
+            names.sort_unstable();
+            for struct_name in names {
+                let missing_dependencies =
+                    registry.missing_dependencies(&unregistered[struct_name]);
+                if missing_dependencies.is_empty() {
+                    diagnostics.warn(format!(
+                        "Failed to register struct '{}' - possible missing dependency",
                         struct_name
-                    );
+                    ));
+                } else {
+                    diagnostics.warn(format!(
+                        "Failed to register struct '{}' - missing dependencies: {}",
+                        struct_name,
+                        missing_dependencies.join(", ")
+                    ));
                 }
+                diagnostics.unresolved_structs.push(UnresolvedStruct {
+                    name: struct_name.clone(),
+                    missing_dependencies,
+                });
             }
         }
 
         Ok((registry, schema))
     }
 
-    /// Second pass: accumulates data into a DataFrame.
+    /// Changes struct and struct array columns whose struct never registered to
+    /// `Binary`, so their raw bytes are kept instead of failing to decode.
+    pub(crate) fn downgrade_unresolved_structs(
+        schema: &mut WpilogSchema,
+        registry: &StructRegistry,
+        diagnostics: &mut Diagnostics,
+    ) {
+        for column in schema.columns_mut() {
+            let struct_name = match &column.dtype {
+                PolarsDataType::Struct(name) | PolarsDataType::StructArray(name) => name,
+                _ => continue,
+            };
+            if registry.get(struct_name).is_some() {
+                continue;
+            }
+            diagnostics.warn(format!(
+                "Struct '{}' of column '{}' has no schema, storing raw bytes",
+                struct_name, column.name
+            ));
+            column.dtype = PolarsDataType::Binary;
+        }
+    }
+
+    /// Handles a control record that is truncated or has an unknown control type.
+    ///
+    /// The record is skipped unless `strict_control_records` is set and `on_error`
+    /// is [`ErrorPolicy::Abort`]. A warning is recorded only when `diagnostics` is
+    /// given, so a record read by both passes is reported once.
+    fn skip_malformed_control(
+        record: &DataLogRecord,
+        reason: impl std::fmt::Display,
+        options: &ParseOptions,
+        diagnostics: Option<&mut Diagnostics>,
+    ) -> Result<()> {
+        let error = WpilogError::ParseError(format!(
+            "Malformed control record at timestamp {}: {}",
+            record.timestamp, reason
+        ));
+        if options.strict_control_records && options.on_error == ErrorPolicy::Abort {
+            return Err(error);
+        }
+        if let Some(diagnostics) = diagnostics {
+            diagnostics.warn(format!("Skipped record: {}", error));
+        }
+        Ok(())
+    }
+
+    /// Finds the latest data record timestamp, reading only record headers.
+    ///
+    /// Timestamps are compared unsigned. With `TimestampKind::Int64`, a timestamp
+    /// past `i64::MAX` is an error, as in the second pass.
+    fn max_timestamp(reader: &DataLogReader, options: &ParseOptions) -> Result<Option<u64>> {
+        let mut max = None;
+        for record_result in reader.records()?.with_recovery(options.recover) {
+            let record = record_result?;
+            if !record.is_control() {
+                if options.timestamp_kind == TimestampKind::Int64 {
+                    record.timestamp_i64()?;
+                }
+                max = max.max(Some(record.timestamp));
+            }
+        }
+        Ok(max)
+    }
+
+    /// Second pass: accumulates data records into rows.
+    ///
+    /// With `bounds.tail_us`, records before the window start are skipped except for
+    /// each entry's latest one, which is decoded into a first row at the window
+    /// start. With `bounds.max_rows`, accumulation stops once that many rows are
+    /// complete. With `bounds.chunk_rows`, each full chunk of rows is built and passed
+    /// to `on_chunk`, and only the rows after the last full chunk are returned.
+    /// Also returns the names of entries that received no data records.
     fn accumulate_data(
         reader: DataLogReader,
         schema: &WpilogSchema,
         registry: StructRegistry,
-    ) -> Result<DataFrame> {
+        options: &ParseOptions,
+        bounds: RowBounds,
+        mut on_chunk: Option<ChunkSink>,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<(DataFrame, Vec<String>)> {
+        let max_rows = bounds.max_rows;
+        let window_start = match bounds.tail_us {
+            Some(window_us) => {
+                Self::max_timestamp(&reader, options)?.map(|max| max.saturating_sub(window_us))
+            }
+            None => None,
+        };
+
         // Create deserializer for struct data
         let deserializer = StructDeserializer::new(&registry);
 
         // Estimate capacity (rough approximation)
-        let estimated_records = reader.data.len() / 25;
+        let scanned_len = bounds
+            .byte_range
+            .map_or(reader.data.len(), |(start, end)| end - start);
+        let estimated_records = (scanned_len / 25)
+            .min(max_rows.unwrap_or(usize::MAX))
+            .min(bounds.chunk_rows.unwrap_or(usize::MAX));
 
         // Build column names and types
         let column_names: Vec<String> = schema.columns().iter().map(|c| c.name.clone()).collect();
@@ -150,59 +696,194 @@ impl WpilogConverter {
             schema.columns().iter().map(|c| c.dtype.clone()).collect();
 
         // Create builder with registry reference
-        let mut builder = DataFrameBuilder::new(column_names, column_types, estimated_records)
-            .with_registry(&registry);
+        let new_builder = || {
+            DataFrameBuilder::new(
+                column_names.clone(),
+                column_types.clone(),
+                estimated_records,
+            )
+            .with_registry(&registry)
+            .with_categorical_strings(options.categorical_strings)
+            .with_enum_output(options.enum_output)
+            .with_protobuf_decoder(options.protobuf_decoder.as_ref())
+            .with_flattened_structs(options.flatten_structs)
+            .with_presence_columns(options.presence_columns)
+            .with_timestamp_kind(options.timestamp_kind)
+        };
+        let mut builder = new_builder();
 
         // Track which columns have been updated for the current timestamp
         let mut current_timestamp: Option<i64> = None;
         let mut current_values: Vec<Option<PolarsValue>> = vec![None; schema.num_columns()];
         let mut finished_entries = std::collections::HashSet::new();
+        let mut started_entries = std::collections::HashSet::new();
+        let mut declarations = HashMap::new();
+        let mut written_entries = std::collections::HashSet::new();
+        let mut padded_boolean_entries = std::collections::HashSet::new();
+        let mut partial_array_entries = std::collections::HashSet::new();
+        let mut seed_records: Vec<Option<DataLogRecord>> = vec![None; schema.num_columns()];
 
-        for record_result in reader.records()? {
+        // START records before a byte range are not read, but the schema holds them
+        if bounds.byte_range.is_some() {
+            started_entries.extend(schema.columns().iter().map(|c| c.entry_id));
+        }
+
+        let records = match bounds.byte_range {
+            Some((start, end)) => DataLogIterator::new(&reader.data[..end], start),
+            None => reader.records()?,
+        };
+        let mut records = records
+            .with_progress(options.progress.clone())
+            .with_recovery(options.recover);
+        for record_result in records.by_ref() {
             let record = record_result?;
 
             // Skip control records (they were processed in schema inference)
             if record.is_control() {
-                if record.is_finish() {
-                    let entry_id = record.get_finish_entry()?;
-                    finished_entries.insert(entry_id);
+                if record.is_start() {
+                    let start_data = match record.get_start_data() {
+                        Ok(start_data) => start_data,
+                        Err(e) => {
+                            Self::skip_malformed_control(&record, e, options, Some(diagnostics))?;
+                            continue;
+                        }
+                    };
+                    started_entries.insert(start_data.entry);
+                    // A conflicting re-START ends the first declaration, like a FINISH
+                    let conflicting = match declarations.get(&start_data.entry) {
+                        Some((name, type_name)) => {
+                            *name != start_data.name || *type_name != start_data.type_name
+                        }
+                        None => schema
+                            .get_column_by_entry(start_data.entry)
+                            .is_some_and(|c| c.type_name != start_data.type_name),
+                    };
+                    if conflicting {
+                        finished_entries.insert(start_data.entry);
+                    } else {
+                        declarations
+                            .entry(start_data.entry)
+                            .or_insert((start_data.name, start_data.type_name));
+                    }
+                } else if record.is_finish() {
+                    match record.get_finish_entry() {
+                        Ok(entry_id) => {
+                            finished_entries.insert(entry_id);
+                        }
+                        Err(e) => {
+                            Self::skip_malformed_control(&record, e, options, Some(diagnostics))?
+                        }
+                    }
                 }
                 continue;
             }
 
+            if record.is_malformed_control() {
+                let reason = match record.data.first() {
+                    Some(control_type) => format!("unknown control type {}", control_type),
+                    None => "empty payload".to_string(),
+                };
+                Self::skip_malformed_control(&record, reason, options, Some(diagnostics))?;
+                continue;
+            }
+
             // Skip records for finished entries
             if finished_entries.contains(&record.entry) {
                 continue;
             }
 
+            // Data records read before their entry's START are orphans
+            if !started_entries.contains(&record.entry) {
+                diagnostics.orphan_records += 1;
+                if options.drop_orphan_records {
+                    continue;
+                }
+            }
+
             // Get column info for this entry
             // Skip entries that aren't in the schema (e.g., structschema entries)
-            let column_info = match schema.get_column_by_entry(record.entry) {
-                Some(info) => info,
+            let column_index = match schema.column_index(record.entry) {
+                Some(index) => index,
                 None => continue, // Skip this entry
             };
+            let column_info = &schema.columns()[column_index];
 
-            // Find the column index
-            let column_index = schema
-                .columns()
-                .iter()
-                .position(|c| c.entry_id == record.entry)
-                .unwrap();
+            let timestamp = match options.timestamp_kind {
+                TimestampKind::Int64 => record.timestamp_i64()?,
+                // Carried bit-for-bit; the builder reinterprets it as unsigned
+                TimestampKind::UInt64 => record.timestamp as i64,
+            };
+
+            // Before the window, only the latest record of each entry is kept
+            if let Some(start) = window_start {
+                // Compared unsigned, as timestamps past i64::MAX are carried bit-for-bit
+                if record.timestamp < start {
+                    if current_timestamp.is_none() {
+                        seed_records[column_index] = Some(record);
+                    }
+                    continue;
+                }
+
+                // Seed the first row with last-known values
+                if current_timestamp.is_none() {
+                    for (index, seed) in seed_records.iter().enumerate() {
+                        let Some(seed) = seed else { continue };
+                        let column_info = &schema.columns()[index];
+                        if let Some(value) = Self::decode_record(
+                            *seed,
+                            column_info,
+                            &deserializer,
+                            options,
+                            &mut padded_boolean_entries,
+                            &mut partial_array_entries,
+                            diagnostics,
+                        )? {
+                            current_values[index] = Some(value);
+                            written_entries.insert(seed.entry);
+                        }
+                    }
+                    if current_values.iter().any(Option::is_some) {
+                        current_timestamp = Some(start as i64);
+                    }
+                }
+            }
 
             // If this is a new timestamp, flush the previous row
             if let Some(ts) = current_timestamp {
-                if ts != record.timestamp as i64 {
+                if ts != timestamp {
                     builder.push_row(ts, &current_values);
                     current_values = vec![None; schema.num_columns()];
-                    current_timestamp = Some(record.timestamp as i64);
+                    current_timestamp = None;
+
+                    if let (Some(rows), Some(on_chunk)) = (bounds.chunk_rows, on_chunk.as_mut()) {
+                        if builder.len() >= rows {
+                            let chunk = std::mem::replace(&mut builder, new_builder());
+                            on_chunk(Self::finish_frame(chunk.build()?, schema, options)?)?;
+                        }
+                    }
+                }
+            }
+            if current_timestamp.is_none() {
+                if max_rows.is_some_and(|max| builder.len() >= max) {
+                    break;
                 }
-            } else {
-                current_timestamp = Some(record.timestamp as i64);
+                current_timestamp = Some(timestamp);
             }
 
-            // Parse the record value based on its type
-            let value = Self::parse_record_value(&record, &column_info.dtype, &deserializer)?;
+            let Some(value) = Self::decode_record(
+                record,
+                column_info,
+                &deserializer,
+                options,
+                &mut padded_boolean_entries,
+                &mut partial_array_entries,
+                diagnostics,
+            )?
+            else {
+                continue;
+            };
             current_values[column_index] = Some(value);
+            written_entries.insert(record.entry);
         }
 
         // Flush the last row
@@ -210,77 +891,149 @@ impl WpilogConverter {
             builder.push_row(ts, &current_values);
         }
 
+        for &(offset, len) in records.skipped() {
+            diagnostics.skipped_bytes += len;
+            diagnostics.warn(format!(
+                "Skipped {} corrupt bytes at offset {}",
+                len, offset
+            ));
+        }
+
+        let unwritten_entries = schema
+            .columns()
+            .iter()
+            .filter(|c| !written_entries.contains(&c.entry_id))
+            .map(|c| c.name.clone())
+            .collect();
+
         // Build the DataFrame
-        builder.build()
+        let dataframe = Self::finish_frame(builder.build()?, schema, options)?;
+        Ok((dataframe, unwritten_entries))
     }
 
-    /// Parses a data record value based on its type.
-    fn parse_record_value(
-        record: &DataLogRecord,
-        dtype: &PolarsDataType,
-        deserializer: &StructDeserializer,
-    ) -> Result<PolarsValue> {
-        match dtype {
-            PolarsDataType::Float64 => Ok(PolarsValue::Float64(record.get_double()?)),
-            PolarsDataType::Float32 => Ok(PolarsValue::Float32(record.get_float()?)),
-            PolarsDataType::Int64 => Ok(PolarsValue::Int64(record.get_integer()?)),
-            PolarsDataType::Boolean => Ok(PolarsValue::Boolean(record.get_boolean()?)),
-            PolarsDataType::String => Ok(PolarsValue::String(record.get_string())),
-            PolarsDataType::BooleanArray => {
-                Ok(PolarsValue::BooleanArray(record.get_boolean_array()))
-            }
-            PolarsDataType::Int64Array => Ok(PolarsValue::Int64Array(record.get_integer_array()?)),
-            PolarsDataType::Float32Array => {
-                Ok(PolarsValue::Float32Array(record.get_float_array()?))
-            }
-            PolarsDataType::Float64Array => {
-                Ok(PolarsValue::Float64Array(record.get_double_array()?))
-            }
-            PolarsDataType::StringArray => Ok(PolarsValue::StringArray(record.get_string_array()?)),
-            PolarsDataType::Struct(struct_name) => {
-                // Deserialize struct data using the registry
-                let struct_value = deserializer.deserialize(struct_name, &record.data)?;
-                Ok(PolarsValue::Struct(struct_value))
-            }
-            PolarsDataType::StructArray(struct_name) => {
-                // Deserialize struct array data using the registry
-                let struct_schema = deserializer.registry().get(struct_name).ok_or_else(|| {
-                    WpilogError::SchemaError(format!(
-                        "Struct '{}' not found in registry for array deserialization",
-                        struct_name
-                    ))
-                })?;
-
-                let struct_size = struct_schema.total_size;
-                if record.data.len() % struct_size != 0 {
-                    return Err(WpilogError::ParseError(format!(
-                        "Invalid struct array size: {} is not a multiple of struct size {}",
-                        record.data.len(),
-                        struct_size
-                    )));
+    /// Applies the options that transform a built DataFrame: with
+    /// `options.numeric_as_f64`, casts the schema's numeric and boolean columns to
+    /// `Float64`.
+    fn finish_frame(
+        mut df: DataFrame,
+        schema: &WpilogSchema,
+        options: &ParseOptions,
+    ) -> Result<DataFrame> {
+        if options.numeric_as_f64 {
+            for column in schema.columns() {
+                if column.dtype.is_numeric_scalar() && column.dtype != PolarsDataType::Float64 {
+                    let cast = df.column(&column.name)?.cast(&DataType::Float64)?;
+                    df.with_column(cast)?;
                 }
+            }
+        }
+        Ok(df)
+    }
 
-                // Deserialize each struct in the array
-                let num_structs = record.data.len() / struct_size;
-                let mut struct_values = Vec::with_capacity(num_structs);
-
-                for i in 0..num_structs {
-                    let start = i * struct_size;
-                    let end = start + struct_size;
-                    let struct_data = &record.data[start..end];
-                    let struct_value = deserializer.deserialize(struct_name, struct_data)?;
-                    struct_values.push(struct_value);
+    /// Decodes a data record for `column_info`, applying the recovery options.
+    ///
+    /// Returns `None` if the record was skipped under [`ErrorPolicy::SkipRecord`].
+    fn decode_record(
+        record: DataLogRecord,
+        column_info: &ColumnInfo,
+        deserializer: &StructDeserializer,
+        options: &ParseOptions,
+        padded_boolean_entries: &mut std::collections::HashSet<u32>,
+        partial_array_entries: &mut std::collections::HashSet<u32>,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Option<PolarsValue>> {
+        // Parse the record value based on its type
+        let decoded = match record.get_value_as_polars_with_options(
+            &column_info.dtype,
+            Some(deserializer),
+            options,
+        ) {
+            Err(WpilogError::StructArraySizeMismatch {
+                struct_name,
+                struct_size,
+                actual_len,
+            }) if options.truncate_struct_arrays => {
+                diagnostics.warn(format!(
+                    "Truncated struct array for '{}' at timestamp {}: {} bytes is not a multiple of struct '{}' size {}",
+                    column_info.name, record.timestamp, actual_len, struct_name, struct_size
+                ));
+                let whole = DataLogRecord {
+                    data: &record.data[..actual_len - actual_len % struct_size],
+                    ..record
+                };
+                whole.get_value_as_polars_with_options(
+                    &column_info.dtype,
+                    Some(deserializer),
+                    options,
+                )
+            }
+            decoded => decoded,
+        }
+        .map_err(|e| match (options.type_overrides.get(&column_info.name), e) {
+            (Some(type_name), e) => WpilogError::ParseError(format!(
+                "Failed to decode column '{}' with overridden type '{}': {}",
+                column_info.name, type_name, e
+            )),
+            (None, WpilogError::ParseError(msg))
+                if matches!(
+                    column_info.dtype,
+                    PolarsDataType::String | PolarsDataType::StringArray
+                ) =>
+            {
+                WpilogError::ParseError(format!(
+                    "Failed to decode string column '{}': {}",
+                    column_info.name, msg
+                ))
+            }
+            (None, e) => e,
+        });
+        let value = match (decoded, options.on_error) {
+            (Ok(value), _) => value,
+            (Err(e), ErrorPolicy::Abort) => return Err(e),
+            (Err(e), ErrorPolicy::SkipRecord) => {
+                diagnostics.warn(format!(
+                    "Skipped record for '{}' at timestamp {}: {}",
+                    column_info.name, record.timestamp, e
+                ));
+                return Ok(None);
+            }
+        };
+        if column_info.dtype == PolarsDataType::Boolean
+            && record.data.len() > 1
+            && padded_boolean_entries.insert(record.entry)
+        {
+            diagnostics.warn(format!(
+                "Boolean entry '{}' has {}-byte records; decoding the first byte",
+                column_info.name,
+                record.data.len()
+            ));
+        }
+        if options.on_error == ErrorPolicy::SkipRecord {
+            if let Some(size) = column_info.dtype.numeric_array_element_size() {
+                if !record.data.len().is_multiple_of(size)
+                    && partial_array_entries.insert(record.entry)
+                {
+                    diagnostics.warn(format!(
+                        "Array entry '{}' has a {}-byte record at timestamp {}; dropped {} trailing bytes of a partial element",
+                        column_info.name,
+                        record.data.len(),
+                        record.timestamp,
+                        record.data.len() % size
+                    ));
                 }
-
-                Ok(PolarsValue::StructArray(struct_values))
             }
         }
+        Ok(Some(value))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::options::ProtobufDecoder;
+    use crate::test_fixtures::{
+        encode_record, encode_start, encode_start_with_metadata, wpilog_header,
+    };
 
     /// Helper to create a minimal WPILog file for testing
     fn create_test_wpilog() -> Vec<u8> {
@@ -329,4 +1082,1477 @@ mod tests {
         assert!(col_names.iter().any(|s| s.as_str() == "timestamp"));
         assert!(col_names.iter().any(|s| s.as_str() == "test"));
     }
+
+    /// Helper to create a WPILog file whose only data record precedes its START record
+    fn create_orphan_wpilog() -> Vec<u8> {
+        let data = create_test_wpilog();
+        let (header, rest) = data.split_at(12);
+        let (start_record, data_record) = rest.split_at(30);
+
+        let mut reordered = header.to_vec();
+        reordered.extend_from_slice(data_record);
+        reordered.extend_from_slice(start_record);
+        reordered
+    }
+
+    #[test]
+    fn test_header_only_log() {
+        let df = WpilogConverter::from_bytes(&wpilog_header()).unwrap();
+
+        assert_eq!(df.height(), 0);
+        assert_eq!(df.width(), 1);
+        assert_eq!(df.column("timestamp").unwrap().dtype(), &DataType::Int64);
+    }
+
+    #[test]
+    fn test_start_only_log() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_start(2, "mode", "string"));
+
+        let df = WpilogConverter::from_bytes(&data).unwrap();
+
+        assert_eq!(df.height(), 0);
+        assert_eq!(df.width(), 3);
+        assert_eq!(df.column("speed").unwrap().dtype(), &DataType::Float64);
+        assert_eq!(df.column("mode").unwrap().dtype(), &DataType::String);
+    }
+
+    #[test]
+    fn test_progress_reports_completion() {
+        let data = create_test_wpilog();
+        let reports = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = reports.clone();
+        let options = ParseOptions {
+            progress: Some(std::sync::Arc::new(move |processed, total| {
+                sink.lock().unwrap().push((processed, total));
+            })),
+            ..Default::default()
+        };
+
+        WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        let reports = reports.lock().unwrap();
+        assert_eq!(*reports, vec![(data.len() as u64, data.len() as u64)]);
+    }
+
+    #[test]
+    fn test_infer_schema_and_registry() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "/.schema/struct:Point", "structschema"));
+        data.extend(encode_record(1, 1, b"double x; double y"));
+        data.extend(encode_start(2, "point", "struct:Point"));
+
+        let (schema, registry) = WpilogConverter::infer_schema_and_registry(&data).unwrap();
+
+        assert_eq!(schema.num_columns(), 1);
+        assert_eq!(registry.get("Point").unwrap().total_size, 16);
+    }
+
+    #[test]
+    fn test_narrow_integer_entries() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "status", "uint8"));
+        data.extend(encode_start(2, "current", "int16"));
+        data.extend(encode_record(1, 1, &[200]));
+        data.extend(encode_record(2, 1, &(-1200i16).to_le_bytes()));
+
+        let df = WpilogConverter::from_bytes(&data).unwrap();
+
+        let status = df.column("status").unwrap();
+        assert_eq!(status.dtype(), &DataType::UInt8);
+        assert_eq!(status.u8().unwrap().get(0), Some(200));
+        let current = df.column("current").unwrap();
+        assert_eq!(current.dtype(), &DataType::Int16);
+        assert_eq!(current.i16().unwrap().get(0), Some(-1200));
+    }
+
+    #[test]
+    fn test_unwritten_entries() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "written", "double"));
+        data.extend(encode_start(2, "declared", "double"));
+        data.extend(encode_start(3, "empty", "double[]"));
+        data.extend(encode_record(1, 2, &1.0f64.to_le_bytes()));
+        data.extend(encode_record(3, 2, &[]));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+
+        assert_eq!(parsed.unwritten_entries(), &["declared".to_string()]);
+    }
+
+    #[test]
+    fn test_from_bytes_tail() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_start(2, "mode", "string"));
+        data.extend(encode_start(3, "late", "double"));
+        data.extend(encode_record(1, 10, &1.0f64.to_le_bytes()));
+        data.extend(encode_record(2, 20, b"auto"));
+        data.extend(encode_record(1, 30, &2.0f64.to_le_bytes()));
+        data.extend(encode_record(1, 90, &3.0f64.to_le_bytes()));
+        data.extend(encode_record(3, 100, &4.0f64.to_le_bytes()));
+
+        let df = WpilogConverter::from_bytes_tail(&data, 20).unwrap();
+
+        // Seed row at the window start, then the records within the window
+        let timestamps: Vec<Option<i64>> = df
+            .column("timestamp")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(timestamps, vec![Some(80), Some(90), Some(100)]);
+
+        let speeds: Vec<Option<f64>> = df
+            .column("speed")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(speeds, vec![Some(2.0), Some(3.0), None]);
+
+        let modes: Vec<Option<&str>> = df
+            .column("mode")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(modes, vec![Some("auto"), None, None]);
+
+        // A window covering the whole log matches a full parse
+        let full = WpilogConverter::from_bytes(&data).unwrap();
+        assert!(WpilogConverter::from_bytes_tail(&data, 1_000)
+            .unwrap()
+            .equals_missing(&full));
+    }
+
+    #[test]
+    fn test_field_metadata_entry_id() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(4, "first", "double"));
+        data.extend(encode_start(9, "second", "int64"));
+        data.extend(encode_record(9, 2, &5i64.to_le_bytes()));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+
+        assert_eq!(
+            parsed.field_metadata("first").unwrap()["wpilog.entry_id"],
+            "4"
+        );
+        let second = parsed.field_metadata("second").unwrap();
+        assert_eq!(second["wpilog.entry_id"], "9");
+        assert_eq!(second["wpilog.type"], "int64");
+        assert!(parsed.field_metadata("timestamp").is_none());
+    }
+
+    #[test]
+    fn test_entries_at_timestamp_zero_share_one_row() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_start(2, "count", "int64"));
+        data.extend(encode_record(1, 0, &1.5f64.to_le_bytes()));
+        data.extend(encode_record(2, 0, &7i64.to_le_bytes()));
+
+        let df = WpilogConverter::from_bytes(&data).unwrap();
+        assert_eq!(df.height(), 1);
+        assert_eq!(
+            df.column("timestamp").unwrap().i64().unwrap().get(0),
+            Some(0)
+        );
+        assert_eq!(df.column("speed").unwrap().f64().unwrap().get(0), Some(1.5));
+        assert_eq!(df.column("count").unwrap().i64().unwrap().get(0), Some(7));
+    }
+
+    #[test]
+    fn test_distinct_small_timestamps_are_distinct_rows() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_start(2, "count", "int64"));
+        data.extend(encode_record(1, 0, &1.5f64.to_le_bytes()));
+        data.extend(encode_record(2, 1, &7i64.to_le_bytes()));
+        data.extend(encode_record(1, 2, &2.5f64.to_le_bytes()));
+        data.extend(encode_record(2, 2, &8i64.to_le_bytes()));
+
+        let df = WpilogConverter::from_bytes(&data).unwrap();
+        let timestamps: Vec<Option<i64>> = df
+            .column("timestamp")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(timestamps, vec![Some(0), Some(1), Some(2)]);
+
+        let speeds: Vec<Option<f64>> = df
+            .column("speed")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(speeds, vec![Some(1.5), None, Some(2.5)]);
+        let counts: Vec<Option<i64>> = df
+            .column("count")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(counts, vec![None, Some(7), Some(8)]);
+    }
+
+    #[test]
+    fn test_repeated_start_is_idempotent() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_record(1, 1, &1.5f64.to_le_bytes()));
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_record(1, 2, &2.5f64.to_le_bytes()));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+        assert_eq!(parsed.dataframe.width(), 2);
+        let speeds: Vec<Option<f64>> = parsed
+            .dataframe
+            .column("speed")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(speeds, vec![Some(1.5), Some(2.5)]);
+        assert!(parsed.diagnostics.warnings.is_empty());
+
+        let schema =
+            WpilogSchema::infer_from_records(DataLogReader::new(&data).records().unwrap()).unwrap();
+        assert_eq!(schema.num_columns(), 1);
+    }
+
+    #[test]
+    fn test_conflicting_restart_keeps_first_declaration() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_record(1, 1, &1.5f64.to_le_bytes()));
+        data.extend(encode_start(1, "speed", "string"));
+        data.extend(encode_record(1, 2, b"fast"));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+        assert_eq!(parsed.dataframe.width(), 2);
+        let speeds: Vec<Option<f64>> = parsed
+            .dataframe
+            .column("speed")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(speeds, vec![Some(1.5)]);
+        assert_eq!(parsed.diagnostics.warnings.len(), 1);
+        assert!(parsed.diagnostics.warnings[0].contains("keeping the first declaration"));
+
+        let schema =
+            WpilogSchema::infer_from_records(DataLogReader::new(&data).records().unwrap()).unwrap();
+        assert_eq!(schema.num_columns(), 1);
+        assert_eq!(schema.columns()[0].type_name, "double");
+    }
+
+    #[test]
+    fn test_rename_columns() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "/RealOutputs/Drive/LeftVelocity", "double"));
+        data.extend(encode_start(2, "/RealOutputs/Drive/RightVelocity", "int64"));
+        data.extend(encode_start(3, "/RealOutputs/Mode", "string"));
+        data.extend(encode_record(1, 1, &1.5f64.to_le_bytes()));
+        data.extend(encode_record(2, 1, &7i64.to_le_bytes()));
+
+        let options = ParseOptions {
+            rename: HashMap::from([
+                (
+                    "/RealOutputs/Drive/LeftVelocity".to_string(),
+                    "left".to_string(),
+                ),
+                (
+                    "/RealOutputs/Drive/RightVelocity".to_string(),
+                    "right".to_string(),
+                ),
+            ]),
+            ..Default::default()
+        };
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        let names: Vec<&str> = parsed
+            .dataframe
+            .get_column_names()
+            .into_iter()
+            .map(|s| s.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["timestamp", "left", "right", "/RealOutputs/Mode"]
+        );
+        assert_eq!(
+            parsed
+                .dataframe
+                .column("left")
+                .unwrap()
+                .f64()
+                .unwrap()
+                .get(0),
+            Some(1.5)
+        );
+
+        let left = parsed.field_metadata("left").unwrap();
+        assert_eq!(left["wpilog.entry_id"], "1");
+        assert_eq!(left["wpilog.type"], "double");
+        let right = parsed.field_metadata("right").unwrap();
+        assert_eq!(right["wpilog.entry_id"], "2");
+        assert_eq!(right["wpilog.type"], "int64");
+        assert!(parsed
+            .field_metadata("/RealOutputs/Drive/LeftVelocity")
+            .is_none());
+
+        // Renaming onto an existing column or the timestamp column is rejected
+        for taken in ["/RealOutputs/Mode", "timestamp"] {
+            let options = ParseOptions {
+                rename: HashMap::from([(
+                    "/RealOutputs/Drive/LeftVelocity".to_string(),
+                    taken.to_string(),
+                )]),
+                ..Default::default()
+            };
+            assert!(matches!(
+                WpilogConverter::from_bytes_with_options(&data, &options),
+                Err(WpilogError::SchemaError(_))
+            ));
+        }
+
+        // So is renaming onto another column's presence column
+        let options = ParseOptions {
+            rename: HashMap::from([(
+                "/RealOutputs/Mode".to_string(),
+                "/RealOutputs/Drive/LeftVelocity__present".to_string(),
+            )]),
+            presence_columns: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            WpilogConverter::from_bytes_with_options(&data, &options),
+            Err(WpilogError::SchemaError(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_bytes_head() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_start(2, "late", "double"));
+        for ts in [2, 3, 3, 5, 8] {
+            data.extend(encode_record(1, ts, &(ts as f64).to_le_bytes()));
+        }
+        data.extend(encode_record(2, 9, &1.0f64.to_le_bytes()));
+
+        // Two records at t=3 make one row
+        let df = WpilogConverter::from_bytes_head(&data, 3).unwrap();
+        let timestamps: Vec<Option<i64>> = df
+            .column("timestamp")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(timestamps, vec![Some(2), Some(3), Some(5)]);
+        assert_eq!(df.column("late").unwrap().null_count(), 3);
+
+        assert_eq!(
+            WpilogConverter::from_bytes_head(&data, 0).unwrap().height(),
+            0
+        );
+        assert_eq!(
+            WpilogConverter::from_bytes_head(&data, 100)
+                .unwrap()
+                .height(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_with_schema() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "pose", "struct:Pose2d"));
+        data.extend(encode_start(2, "speed", "float"));
+        data.extend(encode_record(2, 2, &1.5f32.to_le_bytes()));
+
+        let (df, schema) = WpilogConverter::from_bytes_with_schema(&data).unwrap();
+
+        assert_eq!(df.width(), schema.num_columns() + 1);
+        let speed = schema.get_column_by_entry(2).unwrap();
+        assert_eq!(speed.name, "speed");
+        assert_eq!(speed.type_name, "float");
+        assert_eq!(speed.dtype, PolarsDataType::Float32);
+        assert_eq!(schema.columns()[0].type_name, "struct:Pose2d");
+    }
+
+    #[test]
+    fn test_recover_skips_corrupt_record() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_record(1, 2, &1.0f64.to_le_bytes()));
+        // Record header with a 4-byte size field far larger than the file
+        data.extend_from_slice(&[0x0c, 0x01, 0xff, 0xff, 0xff, 0xff, 0x03]);
+        data.extend(encode_record(1, 4, &2.0f64.to_le_bytes()));
+        data.extend(encode_record(1, 5, &3.0f64.to_le_bytes()));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+        assert_eq!(parsed.dataframe.height(), 1);
+        assert_eq!(parsed.diagnostics.skipped_bytes, 0);
+
+        let options = ParseOptions {
+            recover: true,
+            ..Default::default()
+        };
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        assert_eq!(parsed.dataframe.height(), 3);
+        assert_eq!(parsed.diagnostics.skipped_bytes, 7);
+        assert_eq!(parsed.diagnostics.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_drop_empty_columns() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "always", "double"));
+        data.extend(encode_start(2, "never", "double"));
+        data.extend(encode_start(3, "sometimes", "double"));
+        data.extend(encode_record(1, 2, &1.0f64.to_le_bytes()));
+        data.extend(encode_record(3, 2, &2.0f64.to_le_bytes()));
+        data.extend(encode_record(1, 3, &3.0f64.to_le_bytes()));
+
+        let options = ParseOptions {
+            drop_empty_columns: true,
+            ..Default::default()
+        };
+        let df = WpilogConverter::from_bytes_with_options(&data, &options)
+            .unwrap()
+            .dataframe;
+
+        let names: Vec<&str> = df.get_column_names().iter().map(|n| n.as_str()).collect();
+        assert_eq!(names, vec!["timestamp", "always", "sometimes"]);
+        assert_eq!(df.column("sometimes").unwrap().null_count(), 1);
+    }
+
+    #[test]
+    fn test_timestamp_overflow_is_error() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "value", "double"));
+
+        // Header byte 0x70: 1-byte entry and size, 8-byte timestamp
+        data.extend_from_slice(&[0x70, 0x01, 0x08]);
+        data.extend_from_slice(&(u64::MAX - 1).to_le_bytes());
+        data.extend_from_slice(&1.0f64.to_le_bytes());
+
+        match WpilogConverter::from_bytes(&data) {
+            Err(WpilogError::ParseError(msg)) => {
+                assert!(msg.contains(&(u64::MAX - 1).to_string()), "{}", msg);
+            }
+            other => panic!("Expected ParseError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_orphan_record_kept_by_default() {
+        let data = create_orphan_wpilog();
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+
+        assert_eq!(parsed.diagnostics.orphan_records, 1);
+        assert_eq!(parsed.dataframe.height(), 1);
+        let values = parsed.dataframe.column("test").unwrap().i64().unwrap();
+        assert_eq!(values.get(0), Some(42));
+    }
+
+    #[test]
+    fn test_orphan_record_dropped() {
+        let data = create_orphan_wpilog();
+        let options = ParseOptions {
+            drop_orphan_records: true,
+            ..Default::default()
+        };
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        assert_eq!(parsed.diagnostics.orphan_records, 1);
+        assert_eq!(parsed.dataframe.height(), 0);
+    }
+
+    #[test]
+    fn test_struct_schema_with_surrounding_whitespace() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "/.schema/struct:Point", "structschema"));
+        data.extend(encode_record(
+            1,
+            1,
+            b"\n struct:Point\n  double x; double y  \n\0",
+        ));
+        data.extend(encode_start(2, "point", "struct:Point"));
+
+        let mut point = 1.5f64.to_le_bytes().to_vec();
+        point.extend_from_slice(&2.5f64.to_le_bytes());
+        data.extend(encode_record(2, 2, &point));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+
+        assert!(parsed.diagnostics.warnings.is_empty());
+        let column = parsed.dataframe.column("point").unwrap();
+        assert!(matches!(column.dtype(), DataType::Struct(_)));
+    }
+
+    #[test]
+    fn test_invalid_struct_schema_reported() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "/.schema/struct:Bad", "structschema"));
+        data.extend(encode_record(1, 1, b"int8 flags:99"));
+        data.extend(encode_start(2, "value", "double"));
+        data.extend(encode_record(2, 2, &1.0f64.to_le_bytes()));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+
+        assert_eq!(parsed.diagnostics.warnings.len(), 1);
+        assert!(parsed.diagnostics.warnings[0].contains("Bad"));
+        assert_eq!(parsed.dataframe.height(), 1);
+    }
+
+    #[test]
+    fn test_struct_without_schema_stored_as_binary() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "pose", "struct:Pose2d"));
+        data.extend(encode_start(2, "speed", "double"));
+        data.extend(encode_record(1, 2, &[1, 2, 3, 4]));
+        data.extend(encode_record(2, 2, &1.0f64.to_le_bytes()));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+
+        assert_eq!(
+            parsed.diagnostics.warnings,
+            ["Struct 'Pose2d' of column 'pose' has no schema, storing raw bytes"]
+        );
+        let column = parsed.dataframe.column("pose").unwrap();
+        assert_eq!(column.binary().unwrap().get(0), Some(&[1, 2, 3, 4][..]));
+        assert_eq!(parsed.schema().columns()[0].type_name, "struct:Pose2d");
+        let speed = parsed.dataframe.column("speed").unwrap().f64().unwrap();
+        assert_eq!(speed.get(0), Some(1.0));
+    }
+
+    #[test]
+    fn test_unsigned_timestamps() {
+        let timestamp = i64::MAX as u64 + 10;
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_record(1, 2, &1.0f64.to_le_bytes()));
+        // 8-byte timestamp field
+        data.extend([0x70, 1, 8]);
+        data.extend(timestamp.to_le_bytes());
+        data.extend(2.0f64.to_le_bytes());
+
+        assert!(WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).is_err());
+
+        let options = ParseOptions {
+            timestamp_kind: TimestampKind::UInt64,
+            ..Default::default()
+        };
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        let timestamps = parsed.dataframe.column("timestamp").unwrap().u64().unwrap();
+        assert_eq!(timestamps.get(0), Some(2));
+        assert_eq!(timestamps.get(1), Some(timestamp));
+        let speed = parsed.dataframe.column("speed").unwrap().f64().unwrap();
+        assert_eq!(speed.get(1), Some(2.0));
+    }
+
+    #[test]
+    fn test_unsigned_timestamps_tail() {
+        let late = i64::MAX as u64 + 10;
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_record(1, 2, &1.0f64.to_le_bytes()));
+        for (timestamp, speed) in [(late - 100, 2.0f64), (late, 3.0)] {
+            data.extend([0x70, 1, 8]);
+            data.extend(timestamp.to_le_bytes());
+            data.extend(speed.to_le_bytes());
+        }
+
+        let options = ParseOptions {
+            timestamp_kind: TimestampKind::UInt64,
+            ..Default::default()
+        };
+        let bounds = RowBounds {
+            tail_us: Some(50),
+            ..Default::default()
+        };
+        let df =
+            WpilogConverter::convert_bounded(&data, StructRegistry::new(), &options, bounds, None)
+                .unwrap()
+                .dataframe;
+
+        let timestamps = df.column("timestamp").unwrap().u64().unwrap();
+        assert_eq!(
+            timestamps.into_no_null_iter().collect::<Vec<_>>(),
+            vec![late - 50, late]
+        );
+        let speed = df.column("speed").unwrap().f64().unwrap();
+        assert_eq!(
+            speed.into_no_null_iter().collect::<Vec<_>>(),
+            vec![2.0, 3.0]
+        );
+
+        assert!(WpilogConverter::convert_bounded(
+            &data,
+            StructRegistry::new(),
+            &ParseOptions::default(),
+            bounds,
+            None,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_convert_chunked() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        for timestamp in 2..7 {
+            data.extend(encode_record(
+                1,
+                timestamp,
+                &(timestamp as f64).to_le_bytes(),
+            ));
+        }
+
+        let mut heights = Vec::new();
+        let mut chunks = Vec::new();
+        WpilogConverter::convert_chunked(
+            &data,
+            StructRegistry::new(),
+            &ParseOptions::default(),
+            2,
+            &mut |chunk: DataFrame| {
+                heights.push(chunk.height());
+                chunks.push(chunk);
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(heights, [2, 2, 1]);
+        let mut combined = chunks[0].clone();
+        for chunk in &chunks[1..] {
+            combined.vstack_mut(chunk).unwrap();
+        }
+        let whole = WpilogConverter::from_bytes(&data).unwrap();
+        assert!(combined.equals_missing(&whole));
+
+        // A log without rows still produces one (empty) chunk
+        let mut empty = wpilog_header();
+        empty.extend(encode_start(1, "speed", "double"));
+        let mut widths = Vec::new();
+        WpilogConverter::convert_chunked(
+            &empty,
+            StructRegistry::new(),
+            &ParseOptions::default(),
+            2,
+            &mut |chunk: DataFrame| {
+                widths.push(chunk.width());
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(widths, [2]);
+
+        assert!(WpilogConverter::convert_chunked(
+            &data,
+            StructRegistry::new(),
+            &ParseOptions::default(),
+            0,
+            &mut |_| Ok(()),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_only_types() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "/.schema/struct:Point", "structschema"));
+        data.extend(encode_record(1, 1, b"double x; double y"));
+        data.extend(encode_start(2, "speed", "double"));
+        data.extend(encode_start(3, "count", "int64"));
+        data.extend(encode_start(4, "name", "string"));
+        data.extend(encode_start(5, "point", "struct:Point"));
+        data.extend(encode_start(6, "points", "struct:Point[]"));
+        data.extend(encode_record(2, 2, &1.0f64.to_le_bytes()));
+        data.extend(encode_record(3, 2, &1i64.to_le_bytes()));
+        data.extend(encode_record(4, 2, b"robot"));
+        data.extend(encode_record(5, 2, &[0; 16]));
+        data.extend(encode_record(6, 2, &[0; 32]));
+
+        let names = |types: &[&str]| -> Vec<String> {
+            let options = ParseOptions {
+                only_types: Some(types.iter().map(|t| t.to_string()).collect()),
+                ..Default::default()
+            };
+            let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+            parsed
+                .dataframe
+                .get_column_names()
+                .iter()
+                .map(|n| n.to_string())
+                .collect()
+        };
+
+        assert_eq!(names(&["double", "int64"]), ["timestamp", "speed", "count"]);
+        assert_eq!(names(&["struct"]), ["timestamp", "point"]);
+        assert_eq!(
+            names(&["struct", "struct[]"]),
+            ["timestamp", "point", "points"]
+        );
+        assert_eq!(names(&["struct:Point[]"]), ["timestamp", "points"]);
+        assert_eq!(names(&[" Double "]), ["timestamp", "speed"]);
+        assert_eq!(names(&["Struct"]), ["timestamp", "point"]);
+    }
+
+    #[test]
+    fn test_struct_schema_type_is_normalized() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "/.schema/struct:Point", " StructSchema"));
+        data.extend(encode_record(1, 1, b"double x; double y"));
+        data.extend(encode_start(2, "point", "struct:Point"));
+        data.extend(encode_record(2, 2, &[0; 16]));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+
+        assert!(parsed.diagnostics.warnings.is_empty());
+        assert_eq!(parsed.dataframe.width(), 2);
+        assert!(matches!(
+            parsed.dataframe.column("point").unwrap().dtype(),
+            DataType::Struct(_)
+        ));
+    }
+
+    #[test]
+    fn test_struct_data_before_schema_definition() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(2, "point", "struct:Point"));
+        let mut point = 1.5f64.to_le_bytes().to_vec();
+        point.extend_from_slice(&2.5f64.to_le_bytes());
+        data.extend(encode_record(2, 2, &point));
+        data.extend(encode_start(1, "/.schema/struct:Point", "structschema"));
+        data.extend(encode_record(1, 3, b"double x; double y"));
+        data.extend(encode_record(2, 4, &point));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+
+        assert!(parsed.diagnostics.warnings.is_empty());
+        let column = parsed.dataframe.column("point").unwrap();
+        assert!(matches!(column.dtype(), DataType::Struct(_)));
+        let x = column.struct_().unwrap().field_by_name("x").unwrap();
+        let x: Vec<Option<f64>> = x.f64().unwrap().into_iter().collect();
+        assert_eq!(x, [Some(1.5), Some(1.5)]);
+    }
+
+    #[test]
+    fn test_struct_schema_never_defined() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "points", "struct:Point[]"));
+        data.extend(encode_start(2, "speed", "double"));
+        data.extend(encode_record(1, 2, &[0; 32]));
+        data.extend(encode_record(2, 3, &1.0f64.to_le_bytes()));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+
+        assert_eq!(parsed.diagnostics.warnings.len(), 1);
+        assert!(parsed.diagnostics.warnings[0].contains("'Point'"));
+        assert_eq!(parsed.dataframe.height(), 2);
+        let points = parsed.dataframe.column("points").unwrap();
+        assert_eq!(points.null_count(), 1);
+        let speed = parsed.dataframe.column("speed").unwrap().f64().unwrap();
+        assert_eq!(speed.get(1), Some(1.0));
+    }
+
+    #[test]
+    fn test_type_override() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "sensor", "string"));
+        data.extend(encode_record(1, 2, &2.5f64.to_le_bytes()));
+
+        let mut options = ParseOptions::default();
+        options
+            .type_overrides
+            .insert("sensor".to_string(), "double".to_string());
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        let values = parsed.dataframe.column("sensor").unwrap().f64().unwrap();
+        assert_eq!(values.get(0), Some(2.5));
+    }
+
+    #[test]
+    fn test_type_override_decode_error_names_column() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "sensor", "string"));
+        data.extend(encode_record(1, 2, b"abc"));
+
+        let mut options = ParseOptions::default();
+        options
+            .type_overrides
+            .insert("sensor".to_string(), "double".to_string());
+
+        match WpilogConverter::from_bytes_with_options(&data, &options) {
+            Err(WpilogError::ParseError(msg)) => {
+                assert!(msg.contains("'sensor'"), "{}", msg);
+                assert!(msg.contains("'double'"), "{}", msg);
+            }
+            other => panic!("Expected ParseError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unknown_type_policy() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "custom", "vendor:Reading"));
+        data.extend(encode_record(1, 2, &[0xff, 0x00, 0x41]));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+        let values = parsed.dataframe.column("custom").unwrap().str().unwrap();
+        assert_eq!(values.get(0), Some("\u{fffd}\u{0}A"));
+        assert_eq!(parsed.diagnostics.warnings.len(), 1);
+        assert!(parsed.diagnostics.warnings[0].contains("'vendor:Reading'"));
+
+        let options = ParseOptions {
+            unknown_type_policy: UnknownTypePolicy::Binary,
+            ..Default::default()
+        };
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+        let values = parsed.dataframe.column("custom").unwrap().binary().unwrap();
+        assert_eq!(values.get(0), Some(&[0xff, 0x00, 0x41][..]));
+        assert!(parsed.diagnostics.warnings.is_empty());
+
+        let options = ParseOptions {
+            unknown_type_policy: UnknownTypePolicy::Error,
+            ..Default::default()
+        };
+        match WpilogConverter::from_bytes_with_options(&data, &options) {
+            Err(WpilogError::SchemaError(msg)) => assert_eq!(
+                msg,
+                "Unknown WPILog type 'vendor:Reading' for entry 'custom'"
+            ),
+            other => panic!("Expected SchemaError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unknown_type_policy_applies_to_overrides() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "sensor", "double"));
+        data.extend(encode_record(1, 2, &2.5f64.to_le_bytes()));
+
+        let mut options = ParseOptions {
+            unknown_type_policy: UnknownTypePolicy::Binary,
+            ..Default::default()
+        };
+        options
+            .type_overrides
+            .insert("sensor".to_string(), "bytes".to_string());
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        let column = parsed.dataframe.column("sensor").unwrap();
+        assert_eq!(column.dtype(), &DataType::Binary);
+        assert_eq!(
+            column.binary().unwrap().get(0),
+            Some(&2.5f64.to_le_bytes()[..])
+        );
+    }
+
+    #[test]
+    fn test_numeric_as_f64() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "count", "int64"));
+        data.extend(encode_start(2, "ratio", "float"));
+        data.extend(encode_start(3, "enabled", "boolean"));
+        data.extend(encode_start(4, "level", "uint8"));
+        data.extend(encode_start(5, "mode", "string"));
+        data.extend(encode_start(6, "speeds", "double[]"));
+        data.extend(encode_record(1, 2, &7i64.to_le_bytes()));
+        data.extend(encode_record(2, 2, &0.5f32.to_le_bytes()));
+        data.extend(encode_record(3, 2, &[1]));
+        data.extend(encode_record(4, 2, &[200]));
+        data.extend(encode_record(5, 2, b"Auto"));
+        data.extend(encode_record(6, 2, &1.5f64.to_le_bytes()));
+        data.extend(encode_record(3, 3, &[0]));
+
+        let options = ParseOptions {
+            numeric_as_f64: true,
+            presence_columns: true,
+            ..Default::default()
+        };
+        let df = WpilogConverter::from_bytes_with_options(&data, &options)
+            .unwrap()
+            .dataframe;
+
+        for name in ["count", "ratio", "enabled", "level"] {
+            assert_eq!(df.column(name).unwrap().dtype(), &DataType::Float64);
+        }
+        assert_eq!(df.column("timestamp").unwrap().dtype(), &DataType::Int64);
+        assert_eq!(df.column("mode").unwrap().dtype(), &DataType::String);
+        assert_eq!(
+            df.column("speeds").unwrap().dtype(),
+            &DataType::List(Box::new(DataType::Float64))
+        );
+        assert_eq!(
+            df.column("enabled__present").unwrap().dtype(),
+            &DataType::Boolean
+        );
+
+        let as_f64 = |name: &str| -> Vec<Option<f64>> {
+            df.column(name)
+                .unwrap()
+                .f64()
+                .unwrap()
+                .into_iter()
+                .collect()
+        };
+        assert_eq!(as_f64("count"), vec![Some(7.0), None]);
+        assert_eq!(as_f64("ratio"), vec![Some(0.5), None]);
+        assert_eq!(as_f64("enabled"), vec![Some(1.0), Some(0.0)]);
+        assert_eq!(as_f64("level"), vec![Some(200.0), None]);
+    }
+
+    #[test]
+    fn test_presence_columns() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_start(2, "enabled", "boolean"));
+        data.extend(encode_record(1, 2, &1.0f64.to_le_bytes()));
+        data.extend(encode_record(2, 3, &[1]));
+        data.extend(encode_record(1, 4, &2.0f64.to_le_bytes()));
+
+        let options = ParseOptions {
+            presence_columns: true,
+            ..Default::default()
+        };
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        let df = &parsed.dataframe;
+        assert_eq!(df.width(), 5);
+        let present = |name: &str| -> Vec<Option<bool>> {
+            df.column(name)
+                .unwrap()
+                .bool()
+                .unwrap()
+                .into_iter()
+                .collect()
+        };
+        assert_eq!(
+            present("speed__present"),
+            [Some(true), Some(false), Some(true)]
+        );
+        assert_eq!(
+            present("enabled__present"),
+            [Some(false), Some(true), Some(false)]
+        );
+    }
+
+    #[test]
+    fn test_presence_column_name_collision() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_start(2, "speed__present", "boolean"));
+        data.extend(encode_record(1, 2, &1.0f64.to_le_bytes()));
+        data.extend(encode_record(2, 2, &[1]));
+
+        assert!(WpilogConverter::from_bytes(&data).is_ok());
+
+        let options = ParseOptions {
+            presence_columns: true,
+            ..Default::default()
+        };
+        match WpilogConverter::from_bytes_with_options(&data, &options) {
+            Err(WpilogError::SchemaError(msg)) => {
+                assert!(msg.contains("'speed__present'"), "{}", msg);
+                assert!(msg.contains("'speed'"), "{}", msg);
+            }
+            other => panic!("Expected SchemaError, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_column_order() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "/b", "double"));
+        data.extend(encode_start(2, "/c", "int64"));
+        data.extend(encode_start(3, "/a", "boolean"));
+        data.extend(encode_record(2, 2, &7i64.to_le_bytes()));
+        data.extend(encode_record(3, 2, &[1]));
+
+        let names = |df: &DataFrame| -> Vec<String> {
+            df.get_column_names()
+                .iter()
+                .map(|s| s.to_string())
+                .collect()
+        };
+
+        // First START order by default
+        let df = WpilogConverter::from_bytes(&data).unwrap();
+        assert_eq!(names(&df), ["timestamp", "/b", "/c", "/a"]);
+
+        let options = ParseOptions {
+            sort_columns: true,
+            ..Default::default()
+        };
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+        assert_eq!(names(&parsed.dataframe), ["timestamp", "/a", "/b", "/c"]);
+
+        // Values stay with their columns after sorting
+        let a = parsed.dataframe.column("/a").unwrap().bool().unwrap();
+        assert_eq!(a.get(0), Some(true));
+        let c = parsed.dataframe.column("/c").unwrap().i64().unwrap();
+        assert_eq!(c.get(0), Some(7));
+    }
+
+    #[test]
+    fn test_utf8_policy() {
+        use crate::options::Utf8Policy;
+
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "name", "string"));
+        data.extend(encode_record(1, 1, b"ab\xffcd"));
+
+        let decode = |utf8_policy| {
+            let options = ParseOptions {
+                utf8_policy,
+                ..Default::default()
+            };
+            WpilogConverter::from_bytes_with_options(&data, &options).map(|parsed| {
+                let column = parsed.dataframe.column("name").unwrap();
+                column.str().unwrap().get(0).unwrap().to_string()
+            })
+        };
+
+        assert_eq!(decode(Utf8Policy::Lossy).unwrap(), "ab\u{fffd}cd");
+        assert_eq!(decode(Utf8Policy::ReplaceWith('?')).unwrap(), "ab?cd");
+        match decode(Utf8Policy::Strict) {
+            Err(WpilogError::ParseError(msg)) => {
+                assert!(msg.contains("'name'"), "{}", msg);
+                assert!(msg.contains("invalid utf-8"), "{}", msg);
+            }
+            other => panic!("Expected ParseError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_skip_corrupt_record() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_record(1, 1, &1.0f64.to_le_bytes()));
+        data.extend(encode_record(1, 2, b"bad"));
+        data.extend(encode_record(1, 3, &3.0f64.to_le_bytes()));
+
+        // Aborts by default
+        assert!(WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).is_err());
+
+        let options = ParseOptions {
+            on_error: ErrorPolicy::SkipRecord,
+            ..Default::default()
+        };
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        let values = parsed.dataframe.column("speed").unwrap().f64().unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values.get(0), Some(1.0));
+        assert_eq!(values.get(1), None);
+        assert_eq!(values.get(2), Some(3.0));
+        assert_eq!(parsed.diagnostics.warnings.len(), 1);
+        assert!(parsed.diagnostics.warnings[0].contains("'speed'"));
+    }
+
+    #[test]
+    fn test_malformed_control_record() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_record(1, 2, &1.0f64.to_le_bytes()));
+        data.extend(encode_record(0, 3, &[0x09, 0x01, 0x00, 0x00, 0x00]));
+        data.extend(encode_record(1, 4, &2.0f64.to_le_bytes()));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+        assert_eq!(parsed.dataframe.height(), 2);
+        assert_eq!(parsed.diagnostics.warnings.len(), 1);
+        assert_eq!(parsed.diagnostics.orphan_records, 0);
+
+        let strict = ParseOptions {
+            strict_control_records: true,
+            ..Default::default()
+        };
+        match WpilogConverter::from_bytes_with_options(&data, &strict) {
+            Err(WpilogError::ParseError(msg)) => assert_eq!(
+                msg,
+                "Malformed control record at timestamp 3: unknown control type 9"
+            ),
+            other => panic!("Expected ParseError, got {:?}", other),
+        }
+
+        let options = ParseOptions {
+            on_error: ErrorPolicy::SkipRecord,
+            ..strict
+        };
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+        assert_eq!(parsed.dataframe.height(), 2);
+        assert_eq!(parsed.diagnostics.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_truncated_start_record() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_record(1, 2, &1.0f64.to_le_bytes()));
+        // A START whose name length runs past the payload
+        data.extend(encode_record(0, 3, &[0x00, 0x02, 0x00, 0x00, 0x00, 0x09]));
+        data.extend(encode_record(1, 4, &2.0f64.to_le_bytes()));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+        assert_eq!(parsed.dataframe.width(), 2);
+        assert_eq!(parsed.dataframe.height(), 2);
+        assert_eq!(parsed.diagnostics.warnings.len(), 1);
+        assert!(parsed.diagnostics.warnings[0].contains("timestamp 3"));
+
+        let strict = ParseOptions {
+            strict_control_records: true,
+            ..Default::default()
+        };
+        assert!(WpilogConverter::from_bytes_with_options(&data, &strict).is_err());
+    }
+
+    #[test]
+    fn test_truncate_struct_arrays() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "/.schema/struct:Point", "structschema"));
+        data.extend(encode_record(1, 1, b"float x; float y"));
+        data.extend(encode_start(2, "points", "struct:Point[]"));
+
+        let mut points = Vec::new();
+        for v in [1.0f32, 2.0, 3.0, 4.0] {
+            points.extend_from_slice(&v.to_le_bytes());
+        }
+        points.push(0xff); // 17 bytes: two 8-byte structs and a stray byte
+        data.extend(encode_record(2, 2, &points));
+
+        match WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()) {
+            Err(WpilogError::StructArraySizeMismatch { actual_len, .. }) => {
+                assert_eq!(actual_len, 17)
+            }
+            other => panic!("Expected StructArraySizeMismatch, got {:?}", other),
+        }
+
+        let options = ParseOptions {
+            truncate_struct_arrays: true,
+            ..Default::default()
+        };
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        let column = parsed.dataframe.column("points").unwrap();
+        assert_eq!(column.list().unwrap().get_as_series(0).unwrap().len(), 2);
+        assert_eq!(parsed.diagnostics.warnings.len(), 1);
+        assert!(parsed.diagnostics.warnings[0].contains("'points'"));
+    }
+
+    #[test]
+    fn test_lenient_booleans() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "enabled", "boolean"));
+        data.extend(encode_record(1, 1, &[1, 0, 0, 0]));
+        data.extend(encode_record(1, 2, &[0, 0, 0, 0]));
+
+        assert!(WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).is_err());
+
+        let options = ParseOptions {
+            lenient_booleans: true,
+            ..Default::default()
+        };
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        let values = parsed.dataframe.column("enabled").unwrap().bool().unwrap();
+        assert_eq!(values.get(0), Some(true));
+        assert_eq!(values.get(1), Some(false));
+        assert_eq!(parsed.diagnostics.warnings.len(), 1);
+        assert!(parsed.diagnostics.warnings[0].contains("'enabled'"));
+    }
+
+    #[test]
+    fn test_skip_record_truncates_partial_numeric_arrays() {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&1.5f64.to_le_bytes());
+        payload.extend_from_slice(&2.5f64.to_le_bytes());
+        payload.extend_from_slice(&[0, 0, 0, 0]);
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "poses", "double[]"));
+        data.extend(encode_record(1, 1, &payload));
+        data.extend(encode_record(1, 2, &payload));
+
+        assert!(WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).is_err());
+
+        let options = ParseOptions {
+            on_error: ErrorPolicy::SkipRecord,
+            ..Default::default()
+        };
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        let values = parsed.dataframe.column("poses").unwrap().list().unwrap();
+        let first = values.get_as_series(0).unwrap();
+        assert_eq!(
+            first.f64().unwrap().into_no_null_iter().collect::<Vec<_>>(),
+            vec![1.5, 2.5]
+        );
+        assert_eq!(parsed.diagnostics.warnings.len(), 1);
+        assert!(parsed.diagnostics.warnings[0].contains("'poses'"));
+    }
+
+    #[test]
+    fn test_flatten_structs() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(
+            1,
+            "/.schema/struct:Translation2d",
+            "structschema",
+        ));
+        data.extend(encode_record(1, 1, b"double x; double y"));
+        data.extend(encode_start(
+            2,
+            "/.schema/struct:Rotation2d",
+            "structschema",
+        ));
+        data.extend(encode_record(2, 1, b"double value"));
+        data.extend(encode_start(3, "/.schema/struct:Pose2d", "structschema"));
+        data.extend(encode_record(
+            3,
+            1,
+            b"Translation2d translation; Rotation2d rotation",
+        ));
+        data.extend(encode_start(4, "pose", "struct:Pose2d"));
+        data.extend(encode_start(5, "speed", "double"));
+
+        let mut pose = Vec::new();
+        for v in [1.0f64, 2.0, 0.5] {
+            pose.extend_from_slice(&v.to_le_bytes());
+        }
+        data.extend(encode_record(4, 2, &pose));
+        data.extend(encode_record(5, 3, &4.0f64.to_le_bytes()));
+
+        let options = ParseOptions {
+            flatten_structs: true,
+            ..Default::default()
+        };
+        let df = WpilogConverter::from_bytes_with_options(&data, &options)
+            .unwrap()
+            .dataframe;
+
+        let names: Vec<&str> = df.get_column_names().iter().map(|n| n.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "timestamp",
+                "pose.translation.x",
+                "pose.translation.y",
+                "pose.rotation.value",
+                "speed"
+            ]
+        );
+
+        let x: Vec<Option<f64>> = df
+            .column("pose.translation.x")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(x, vec![Some(1.0), None]);
+        let rotation = df.column("pose.rotation.value").unwrap().f64().unwrap();
+        assert_eq!(rotation.get(0), Some(0.5));
+    }
+
+    #[test]
+    fn test_from_bytes_with_registry_resolves_external_struct() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "/.schema/struct:Pose", "structschema"));
+        data.extend(encode_record(1, 1, b"Point position; double heading"));
+        data.extend(encode_start(2, "pose", "struct:Pose"));
+
+        let mut pose = Vec::new();
+        for v in [1.0f64, 2.0, 0.5] {
+            pose.extend_from_slice(&v.to_le_bytes());
+        }
+        data.extend(encode_record(2, 2, &pose));
+
+        let mut registry = StructRegistry::new();
+        registry
+            .register("Point".to_string(), "double x; double y")
+            .unwrap();
+
+        let df = WpilogConverter::from_bytes_with_registry(&data, registry).unwrap();
+        let column = df.column("pose").unwrap();
+        assert!(matches!(column.dtype(), DataType::Struct(_)));
+        assert_eq!(column.null_count(), 0);
+    }
+
+    #[test]
+    fn test_generic_struct_name_from_metadata() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "/.schema/struct:Point", "structschema"));
+        data.extend(encode_record(1, 1, b"double x; double y"));
+        data.extend(encode_start_with_metadata(
+            2,
+            "point",
+            "struct",
+            r#"{"source": "vision", "struct": "Point"}"#,
+        ));
+
+        let mut point = 1.5f64.to_le_bytes().to_vec();
+        point.extend_from_slice(&2.5f64.to_le_bytes());
+        data.extend(encode_record(2, 2, &point));
+
+        let parsed =
+            WpilogConverter::from_bytes_with_options(&data, &ParseOptions::default()).unwrap();
+
+        let column = parsed.dataframe.column("point").unwrap();
+        assert!(matches!(column.dtype(), DataType::Struct(_)));
+        assert_eq!(column.null_count(), 0);
+    }
+
+    #[test]
+    fn test_protobuf_kept_as_string_without_decoder() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "pose", "proto:wpi.proto.ProtobufPose2d"));
+        data.extend(encode_record(1, 2, b"abc"));
+
+        let df = WpilogConverter::from_bytes(&data).unwrap();
+        let values = df.column("pose").unwrap().str().unwrap();
+        assert_eq!(values.get(0), Some("abc"));
+    }
+
+    #[test]
+    fn test_protobuf_decoder() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "pose", "proto:wpi.proto.ProtobufPose2d"));
+        data.extend(encode_record(1, 2, &[3, 1, 2, 3]));
+        data.extend(encode_start(2, "other", "proto:Other"));
+        data.extend(encode_record(2, 3, b"xyz"));
+
+        // The first pose payload doesn't decode, and the second decodes to the wrong type
+        data.extend(encode_record(1, 4, &[]));
+        data.extend(encode_record(1, 5, &[9]));
+        data.extend(encode_record(1, 6, &[4]));
+
+        let mut options = ParseOptions {
+            protobuf_decoder: Some(pose_decoder()),
+            ..Default::default()
+        };
+        match WpilogConverter::from_bytes_with_options(&data, &options) {
+            Err(WpilogError::ParseError(msg)) => {
+                assert!(msg.contains("failed to decode message"), "{}", msg);
+            }
+            other => panic!("Expected ParseError, got {:?}", other.map(|_| ())),
+        }
+
+        options.on_error = ErrorPolicy::SkipRecord;
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        let pose = parsed.dataframe.column("pose").unwrap().i64().unwrap();
+        assert_eq!(
+            pose.into_iter().collect::<Vec<_>>(),
+            vec![Some(3), None, None, None, Some(4)]
+        );
+        let other = parsed.dataframe.column("other").unwrap().str().unwrap();
+        assert_eq!(other.get(1), Some("xyz"));
+        assert_eq!(parsed.diagnostics.warnings.len(), 2);
+        assert!(parsed.diagnostics.warnings[1].contains("expected Int64"));
+    }
+
+    fn pose_decoder() -> ProtobufDecoder {
+        ProtobufDecoder::new(
+            |message_name| match message_name {
+                "wpi.proto.ProtobufPose2d" => Some(PolarsDataType::Int64),
+                "Opaque" => Some(PolarsDataType::Protobuf(message_name.to_string())),
+                _ => None,
+            },
+            |message_name, payload| match (message_name, payload) {
+                ("Opaque", _) => panic!("Opaque payloads are not decoded"),
+                (_, []) => None,
+                (_, [9]) => Some(PolarsValue::String("nine".to_string())),
+                _ => Some(PolarsValue::Int64(payload[0] as i64)),
+            },
+        )
+    }
+
+    #[test]
+    fn test_protobuf_decoder_declared_protobuf_is_string() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "opaque", "proto:Opaque"));
+        data.extend(encode_record(1, 2, b"abc"));
+
+        let options = ParseOptions {
+            protobuf_decoder: Some(pose_decoder()),
+            ..Default::default()
+        };
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        let opaque = parsed.dataframe.column("opaque").unwrap().str().unwrap();
+        assert_eq!(opaque.get(0), Some("abc"));
+    }
+
+    #[test]
+    fn test_protobuf_array_not_decoded() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "poses", "proto:Pose2d[]"));
+        data.extend(encode_record(1, 2, b"abc"));
+
+        let options = ParseOptions {
+            protobuf_decoder: Some(ProtobufDecoder::new(
+                |_| Some(PolarsDataType::Int64),
+                |_, _| Some(PolarsValue::Int64(1)),
+            )),
+            ..Default::default()
+        };
+        let parsed = WpilogConverter::from_bytes_with_options(&data, &options).unwrap();
+
+        let poses = parsed.dataframe.column("poses").unwrap().str().unwrap();
+        assert_eq!(poses.get(0), Some("abc"));
+        assert!(parsed.diagnostics.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_struct_dependency_cycle_is_error() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "/.schema/struct:Outer", "structschema"));
+        data.extend(encode_record(1, 1, b"Inner inner"));
+        data.extend(encode_start(2, "/.schema/struct:Inner", "structschema"));
+        data.extend(encode_record(2, 1, b"Outer outer"));
+        data.extend(encode_start(3, "value", "double"));
+        data.extend(encode_record(3, 2, &1.0f64.to_le_bytes()));
+
+        match WpilogConverter::from_bytes(&data) {
+            Err(WpilogError::SchemaError(msg)) => {
+                assert!(msg.contains("Inner -> Outer -> Inner"), "{}", msg);
+            }
+            other => panic!("Expected SchemaError, got {:?}", other),
+        }
+    }
 }
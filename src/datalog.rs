@@ -7,13 +7,28 @@
 //! - Data record type extraction (boolean, int64, float, double, string, arrays)
 
 use crate::error::{Result, WpilogError};
+use crate::options::{ErrorPolicy, ParseOptions, ProgressCallback, StringEncoding, Utf8Policy};
+use crate::struct_support::StructDeserializer;
+use crate::types::{PolarsDataType, PolarsValue};
 use byteorder::{LittleEndian, ReadBytesExt};
 use std::io::Cursor;
+use std::ops::RangeInclusive;
+
+/// The WPILog format versions whose records this reader decodes, packed as
+/// `major << 8 | minor`.
+///
+/// All 1.x versions share the record framing of 1.0. A later major version may
+/// frame records differently, so its records are rejected with
+/// [`WpilogError::UnsupportedVersion`] rather than decoded as garbage.
+pub const SUPPORTED_VERSIONS: RangeInclusive<u16> = 0x0100..=0x01ff;
 
 const CONTROL_START: u8 = 0;
 const CONTROL_FINISH: u8 = 1;
 const CONTROL_SET_METADATA: u8 = 2;
 
+/// Number of bytes between progress reports.
+const PROGRESS_INTERVAL: usize = 1 << 20;
+
 /// Data contained in a start control record.
 #[derive(Debug, Clone)]
 pub struct StartRecordData {
@@ -31,17 +46,48 @@ pub struct MetadataRecordData {
 }
 
 /// A record in the data log.
-#[derive(Debug, Clone)]
-pub struct DataLogRecord {
+///
+/// The payload borrows from the log buffer, so iterating records does not copy data.
+#[derive(Debug, Clone, Copy)]
+pub struct DataLogRecord<'a> {
     pub entry: u32,
     pub timestamp: u64,
-    pub data: Vec<u8>,
+    pub data: &'a [u8],
 }
 
-impl DataLogRecord {
-    /// Returns true if the record is a control record (entry ID 0).
+impl DataLogRecord<'_> {
+    /// Returns the timestamp as the signed microseconds used in the DataFrame.
+    ///
+    /// Fails instead of wrapping if the timestamp exceeds `i64::MAX`.
+    pub fn timestamp_i64(&self) -> Result<i64> {
+        i64::try_from(self.timestamp).map_err(|_| {
+            WpilogError::ParseError(format!(
+                "Timestamp {} for entry {} exceeds the maximum supported value {}",
+                self.timestamp,
+                self.entry,
+                i64::MAX
+            ))
+        })
+    }
+
+    /// Returns true if the record is a control record: entry ID 0 with a known
+    /// control type byte.
+    ///
+    /// A record for entry 0 with an unknown or missing control type is most likely a
+    /// data record whose entry ID was misread, so it is not treated as control; see
+    /// [`is_malformed_control`](Self::is_malformed_control).
     pub fn is_control(&self) -> bool {
         self.entry == 0
+            && matches!(
+                self.get_control_type(),
+                Some(CONTROL_START | CONTROL_FINISH | CONTROL_SET_METADATA)
+            )
+    }
+
+    /// Returns true if the record has the control entry ID 0 but no known control
+    /// type byte.
+    pub fn is_malformed_control(&self) -> bool {
+        self.entry == 0 && !self.is_control()
     }
 
     fn get_control_type(&self) -> Option<u8> {
@@ -49,20 +95,35 @@ impl DataLogRecord {
     }
 
     /// Returns true if the record is a start control record.
+    ///
+    /// Only the control type byte is checked; a truncated payload is reported as an
+    /// error when the record is decoded rather than treated as another record kind.
     pub fn is_start(&self) -> bool {
-        self.entry == 0 && self.data.len() >= 17 && self.get_control_type() == Some(CONTROL_START)
+        self.entry == 0 && self.get_control_type() == Some(CONTROL_START)
     }
 
     /// Returns true if the record is a finish control record.
+    ///
+    /// Bytes after the entry ID are ignored, so padded finish records are accepted.
     pub fn is_finish(&self) -> bool {
-        self.entry == 0 && self.data.len() == 5 && self.get_control_type() == Some(CONTROL_FINISH)
+        self.entry == 0 && self.get_control_type() == Some(CONTROL_FINISH)
     }
 
     /// Returns true if the record is a set metadata control record.
     pub fn is_set_metadata(&self) -> bool {
-        self.entry == 0
-            && self.data.len() >= 9
-            && self.get_control_type() == Some(CONTROL_SET_METADATA)
+        self.entry == 0 && self.get_control_type() == Some(CONTROL_SET_METADATA)
+    }
+
+    /// Reads the entry ID that follows the control type byte.
+    fn read_control_entry(&self, kind: &str) -> Result<u32> {
+        let bytes = self.data.get(1..5).ok_or_else(|| {
+            WpilogError::ParseError(format!(
+                "{} record too short for an entry ID: {} bytes",
+                kind,
+                self.data.len()
+            ))
+        })?;
+        Ok(Cursor::new(bytes).read_u32::<LittleEndian>()?)
     }
 
     /// Decodes a start control record.
@@ -71,13 +132,10 @@ impl DataLogRecord {
             return Err(WpilogError::ParseError("Not a start record".to_string()));
         }
 
-        let mut cursor = Cursor::new(&self.data);
-        cursor.set_position(1); // Skip control type
-
-        let entry = cursor.read_u32::<LittleEndian>()?;
-        let (name, pos) = read_inner_string(&self.data, cursor.position() as usize)?;
-        let (type_name, pos) = read_inner_string(&self.data, pos)?;
-        let (metadata, _) = read_inner_string(&self.data, pos)?;
+        let entry = self.read_control_entry("Start")?;
+        let (name, pos) = read_inner_string(self.data, 5, LOSSY)?;
+        let (type_name, pos) = read_inner_string(self.data, pos, LOSSY)?;
+        let (metadata, _) = read_inner_string(self.data, pos, LOSSY)?;
 
         Ok(StartRecordData {
             entry,
@@ -87,14 +145,23 @@ impl DataLogRecord {
         })
     }
 
+    /// Reads only the entry ID of a start control record, without decoding its
+    /// name, type, and metadata strings.
+    pub fn get_start_entry(&self) -> Result<u32> {
+        if !self.is_start() {
+            return Err(WpilogError::ParseError("Not a start record".to_string()));
+        }
+
+        self.read_control_entry("Start")
+    }
+
     /// Decodes a finish control record.
     pub fn get_finish_entry(&self) -> Result<u32> {
         if !self.is_finish() {
             return Err(WpilogError::ParseError("Not a finish record".to_string()));
         }
 
-        let mut cursor = Cursor::new(&self.data[1..5]);
-        Ok(cursor.read_u32::<LittleEndian>()?)
+        self.read_control_entry("Finish")
     }
 
     /// Decodes a set metadata control record.
@@ -105,9 +172,8 @@ impl DataLogRecord {
             ));
         }
 
-        let mut cursor = Cursor::new(&self.data[1..5]);
-        let entry = cursor.read_u32::<LittleEndian>()?;
-        let (metadata, _) = read_inner_string(&self.data, 5)?;
+        let entry = self.read_control_entry("Set metadata")?;
+        let (metadata, _) = read_inner_string(self.data, 5, LOSSY)?;
 
         Ok(MetadataRecordData { entry, metadata })
     }
@@ -123,6 +189,17 @@ impl DataLogRecord {
         Ok(self.data[0] != 0)
     }
 
+    /// Decodes a boolean data record from its first byte, accepting padded records
+    /// longer than 1 byte.
+    pub fn get_padded_boolean(&self) -> Result<bool> {
+        match self.data.first() {
+            Some(&byte) => Ok(byte != 0),
+            None => Err(WpilogError::ParseError(
+                "Invalid boolean size: expected at least 1 byte, got 0".to_string(),
+            )),
+        }
+    }
+
     /// Decodes an integer (int64) data record.
     pub fn get_integer(&self) -> Result<i64> {
         if self.data.len() != 8 {
@@ -131,10 +208,31 @@ impl DataLogRecord {
                 self.data.len()
             )));
         }
-        let mut cursor = Cursor::new(&self.data);
+        let mut cursor = Cursor::new(self.data);
         Ok(cursor.read_i64::<LittleEndian>()?)
     }
 
+    /// Decodes a `size`-byte little-endian integer data record, sign-extending it
+    /// when `signed`.
+    pub fn get_sized_integer(&self, size: usize, signed: bool) -> Result<i64> {
+        if self.data.len() != size {
+            return Err(WpilogError::ParseError(format!(
+                "Invalid integer size: expected {} bytes, got {}",
+                size,
+                self.data.len()
+            )));
+        }
+        let mut bytes = [0u8; 8];
+        bytes[..size].copy_from_slice(self.data);
+        let value = i64::from_le_bytes(bytes);
+        if signed {
+            let shift = 64 - size as u32 * 8;
+            Ok(value << shift >> shift)
+        } else {
+            Ok(value)
+        }
+    }
+
     /// Decodes a float data record.
     pub fn get_float(&self) -> Result<f32> {
         if self.data.len() != 4 {
@@ -143,7 +241,7 @@ impl DataLogRecord {
                 self.data.len()
             )));
         }
-        let mut cursor = Cursor::new(&self.data);
+        let mut cursor = Cursor::new(self.data);
         Ok(cursor.read_f32::<LittleEndian>()?)
     }
 
@@ -155,86 +253,149 @@ impl DataLogRecord {
                 self.data.len()
             )));
         }
-        let mut cursor = Cursor::new(&self.data);
+        let mut cursor = Cursor::new(self.data);
         Ok(cursor.read_f64::<LittleEndian>()?)
     }
 
     /// Decodes a string data record.
+    /// The whole payload is treated as the string; see `get_string_with_encoding` for
+    /// writers that store a length prefix.
     /// Uses lossy UTF-8 conversion as a fallback for binary data marked as strings.
     pub fn get_string(&self) -> String {
-        match String::from_utf8(self.data.clone()) {
+        match String::from_utf8(self.data.to_vec()) {
             Ok(s) => s,
             Err(_) => {
                 // Fallback: use lossy UTF-8 conversion for binary data marked as strings
                 // This handles cases where the WPILog schema declares a field as string
                 // but it actually contains binary/msgpack data
-                String::from_utf8_lossy(&self.data).to_string()
+                String::from_utf8_lossy(self.data).to_string()
             }
         }
     }
 
+    /// Returns true if the record starts with a 4-byte little-endian length that exactly
+    /// matches the size of the remaining payload.
+    pub fn has_length_prefix(&self) -> bool {
+        self.data.len() >= 4
+            && u32::from_le_bytes([self.data[0], self.data[1], self.data[2], self.data[3]]) as usize
+                == self.data.len() - 4
+    }
+
+    /// Decodes a string data record stored with a 4-byte length prefix.
+    pub fn get_length_prefixed_string(&self) -> Result<String> {
+        self.get_string_with_policy(StringEncoding::LengthPrefixed, LOSSY)
+    }
+
+    /// Decodes a string data record using the given encoding.
+    pub fn get_string_with_encoding(&self, encoding: StringEncoding) -> Result<String> {
+        self.get_string_with_policy(encoding, LOSSY)
+    }
+
+    /// Decodes a string data record using the given encoding, handling invalid UTF-8
+    /// according to `utf8_policy`.
+    pub fn get_string_with_policy(
+        &self,
+        encoding: StringEncoding,
+        utf8_policy: Utf8Policy,
+    ) -> Result<String> {
+        let bytes = match encoding {
+            StringEncoding::Raw => self.data,
+            StringEncoding::LengthPrefixed if !self.has_length_prefix() => {
+                return Err(WpilogError::ParseError(format!(
+                    "Invalid length-prefixed string: prefix does not match payload size {}",
+                    self.data.len()
+                )));
+            }
+            StringEncoding::LengthPrefixed => &self.data[4..],
+            StringEncoding::Auto if self.has_length_prefix() => &self.data[4..],
+            StringEncoding::Auto => self.data,
+        };
+        decode_utf8(bytes, utf8_policy)
+    }
+
     /// Decodes msgpack data.
     pub fn get_msgpack(&self) -> Result<rmpv::Value> {
-        rmpv::decode::read_value(&mut Cursor::new(&self.data))
+        rmpv::decode::read_value(&mut Cursor::new(self.data))
             .map_err(|e| WpilogError::ParseError(format!("MsgPack decode error: {}", e)))
     }
 
     /// Decodes a boolean array data record.
+    ///
+    /// WPILib writes one byte per boolean; any nonzero byte is true.
     pub fn get_boolean_array(&self) -> Vec<bool> {
         self.data.iter().map(|&x| x != 0).collect()
     }
 
+    /// Decodes a boolean array data record written with eight booleans per byte,
+    /// least significant bit first, as done by some non-WPILib writers.
+    ///
+    /// The element count isn't recorded, so the result always has `8 * len` elements
+    /// and any padding bits in the last byte decode as false.
+    pub fn get_bitpacked_boolean_array(&self) -> Vec<bool> {
+        self.data
+            .iter()
+            .flat_map(|&byte| (0..8).map(move |bit| byte & (1 << bit) != 0))
+            .collect()
+    }
+
     /// Decodes an integer array data record.
     pub fn get_integer_array(&self) -> Result<Vec<i64>> {
-        if self.data.len() % 8 != 0 {
+        if !self.data.len().is_multiple_of(8) {
             return Err(WpilogError::ParseError(format!(
                 "Invalid integer array size: {} is not a multiple of 8",
                 self.data.len()
             )));
         }
-        let mut result = Vec::with_capacity(self.data.len() / 8);
-        let mut cursor = Cursor::new(&self.data);
-        while cursor.position() < self.data.len() as u64 {
-            result.push(cursor.read_i64::<LittleEndian>()?);
-        }
-        Ok(result)
+        Ok(self
+            .data
+            .chunks_exact(8)
+            .map(|chunk| i64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect())
     }
 
     /// Decodes a float array data record.
     pub fn get_float_array(&self) -> Result<Vec<f32>> {
-        if self.data.len() % 4 != 0 {
+        if !self.data.len().is_multiple_of(4) {
             return Err(WpilogError::ParseError(format!(
                 "Invalid float array size: {} is not a multiple of 4",
                 self.data.len()
             )));
         }
-        let mut result = Vec::with_capacity(self.data.len() / 4);
-        let mut cursor = Cursor::new(&self.data);
-        while cursor.position() < self.data.len() as u64 {
-            result.push(cursor.read_f32::<LittleEndian>()?);
-        }
-        Ok(result)
+        Ok(self
+            .data
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect())
     }
 
     /// Decodes a double array data record.
     pub fn get_double_array(&self) -> Result<Vec<f64>> {
-        if self.data.len() % 8 != 0 {
+        if !self.data.len().is_multiple_of(8) {
             return Err(WpilogError::ParseError(format!(
                 "Invalid double array size: {} is not a multiple of 8",
                 self.data.len()
             )));
         }
-        let mut result = Vec::with_capacity(self.data.len() / 8);
-        let mut cursor = Cursor::new(&self.data);
-        while cursor.position() < self.data.len() as u64 {
-            result.push(cursor.read_f64::<LittleEndian>()?);
-        }
-        Ok(result)
+        Ok(self
+            .data
+            .chunks_exact(8)
+            .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect())
     }
 
     /// Decodes a string array data record.
     pub fn get_string_array(&self) -> Result<Vec<String>> {
-        let mut cursor = Cursor::new(&self.data);
+        self.get_string_array_with_policy(LOSSY)
+    }
+
+    /// Decodes a string array data record, handling invalid UTF-8 according to
+    /// `utf8_policy`.
+    ///
+    /// The elements must exactly fill the record. A corrupt element length is
+    /// reported with the index of that element rather than decoding the bytes after
+    /// it as further strings.
+    pub fn get_string_array_with_policy(&self, utf8_policy: Utf8Policy) -> Result<Vec<String>> {
+        let mut cursor = Cursor::new(self.data);
         let size = cursor.read_u32::<LittleEndian>()? as usize;
 
         if size > (self.data.len() - 4) / 4 {
@@ -247,18 +408,205 @@ impl DataLogRecord {
         let mut result = Vec::with_capacity(size);
         let mut pos = 4;
 
-        for _ in 0..size {
-            let (s, new_pos) = read_inner_string(&self.data, pos)?;
+        for index in 0..size {
+            let element_error = |msg: String| {
+                WpilogError::ParseError(format!("Invalid string array element {}: {}", index, msg))
+            };
+            let (s, new_pos) =
+                read_inner_string(self.data, pos, utf8_policy).map_err(|e| match e {
+                    WpilogError::ParseError(msg) => element_error(msg),
+                    e => e,
+                })?;
+
+            // Each remaining element needs at least its 4-byte length
+            let remaining = size - index - 1;
+            if remaining > (self.data.len() - new_pos) / 4 {
+                return Err(element_error(format!(
+                    "length leaves {} bytes for {} more elements",
+                    self.data.len() - new_pos,
+                    remaining
+                )));
+            }
+
             result.push(s);
             pos = new_pos;
         }
 
+        if pos != self.data.len() {
+            return Err(WpilogError::ParseError(format!(
+                "String array of {} elements has {} trailing bytes",
+                size,
+                self.data.len() - pos
+            )));
+        }
+
         Ok(result)
     }
+
+    /// Decodes this data record into a `PolarsValue` of the given type.
+    ///
+    /// Struct and struct array types require a deserializer backed by a registry
+    /// containing the struct schema; all other types ignore it.
+    pub fn get_value_as_polars(
+        &self,
+        dtype: &PolarsDataType,
+        deserializer: Option<&StructDeserializer>,
+    ) -> Result<PolarsValue> {
+        match dtype {
+            PolarsDataType::Float64 => Ok(PolarsValue::Float64(self.get_double()?)),
+            PolarsDataType::Float32 => Ok(PolarsValue::Float32(self.get_float()?)),
+            PolarsDataType::Int64 => Ok(PolarsValue::Int64(self.get_integer()?)),
+            PolarsDataType::Int8
+            | PolarsDataType::Int16
+            | PolarsDataType::Int32
+            | PolarsDataType::UInt8
+            | PolarsDataType::UInt16
+            | PolarsDataType::UInt32 => {
+                let (size, signed) = dtype.narrow_integer().unwrap();
+                Ok(PolarsValue::Int64(self.get_sized_integer(size, signed)?))
+            }
+            PolarsDataType::Boolean => Ok(PolarsValue::Boolean(self.get_boolean()?)),
+            PolarsDataType::String
+            | PolarsDataType::Protobuf(_)
+            | PolarsDataType::ProtobufArray(_) => Ok(PolarsValue::String(self.get_string())),
+            PolarsDataType::Binary => Ok(PolarsValue::Binary(self.data.to_vec())),
+            PolarsDataType::BooleanArray => Ok(PolarsValue::BooleanArray(self.get_boolean_array())),
+            PolarsDataType::Int64Array => Ok(PolarsValue::Int64Array(self.get_integer_array()?)),
+            PolarsDataType::Float32Array => Ok(PolarsValue::Float32Array(self.get_float_array()?)),
+            PolarsDataType::Float64Array => Ok(PolarsValue::Float64Array(self.get_double_array()?)),
+            PolarsDataType::StringArray => Ok(PolarsValue::StringArray(self.get_string_array()?)),
+            PolarsDataType::Struct(struct_name) => {
+                let deserializer = Self::require_deserializer(deserializer, struct_name)?;
+                let struct_value = deserializer.deserialize(struct_name, self.data)?;
+                Ok(PolarsValue::Struct(struct_value))
+            }
+            PolarsDataType::StructArray(struct_name) => {
+                let deserializer = Self::require_deserializer(deserializer, struct_name)?;
+                let struct_schema = deserializer.registry().get(struct_name).ok_or_else(|| {
+                    WpilogError::SchemaError(format!(
+                        "Struct '{}' not found in registry for array deserialization",
+                        struct_name
+                    ))
+                })?;
+
+                if struct_schema.is_variable_size() {
+                    return Err(WpilogError::ParseError(format!(
+                        "Arrays of struct '{}' are not supported: it has a variable-length array",
+                        struct_name
+                    )));
+                }
+
+                let struct_size = struct_schema.total_size;
+                if !self.data.len().is_multiple_of(struct_size) {
+                    return Err(WpilogError::StructArraySizeMismatch {
+                        struct_name: struct_name.clone(),
+                        struct_size,
+                        actual_len: self.data.len(),
+                    });
+                }
+
+                // Deserialize each struct in the array
+                let num_structs = self.data.len() / struct_size;
+                let mut struct_values = Vec::with_capacity(num_structs);
+
+                for i in 0..num_structs {
+                    let start = i * struct_size;
+                    let end = start + struct_size;
+                    let struct_value =
+                        deserializer.deserialize(struct_name, &self.data[start..end])?;
+                    struct_values.push(struct_value);
+                }
+
+                Ok(PolarsValue::StructArray(struct_values))
+            }
+        }
+    }
+
+    /// Decodes this data record into a `PolarsValue`, applying the decoding options
+    /// (such as string encoding) from `options`.
+    pub fn get_value_as_polars_with_options(
+        &self,
+        dtype: &PolarsDataType,
+        deserializer: Option<&StructDeserializer>,
+        options: &ParseOptions,
+    ) -> Result<PolarsValue> {
+        match dtype {
+            PolarsDataType::String => Ok(PolarsValue::String(
+                self.get_string_with_policy(options.string_encoding, options.utf8_policy)?,
+            )),
+            PolarsDataType::StringArray => Ok(PolarsValue::StringArray(
+                self.get_string_array_with_policy(options.utf8_policy)?,
+            )),
+            PolarsDataType::Boolean if options.lenient_booleans => {
+                Ok(PolarsValue::Boolean(self.get_padded_boolean()?))
+            }
+            PolarsDataType::Int64Array
+            | PolarsDataType::Float32Array
+            | PolarsDataType::Float64Array
+                if options.on_error == ErrorPolicy::SkipRecord =>
+            {
+                let size = dtype.numeric_array_element_size().unwrap_or(1);
+                let whole = DataLogRecord {
+                    data: &self.data[..self.data.len() - self.data.len() % size],
+                    ..*self
+                };
+                whole.get_value_as_polars(dtype, deserializer)
+            }
+            PolarsDataType::BooleanArray if options.boolean_array_bitpacked => Ok(
+                PolarsValue::BooleanArray(self.get_bitpacked_boolean_array()),
+            ),
+            PolarsDataType::Protobuf(message_name) => {
+                // A message the decoder declares as `Protobuf(_)` is kept as strings,
+                // the same as one it doesn't declare
+                let decoder = options.protobuf_decoder.as_ref().and_then(|decoder| {
+                    decoder
+                        .dtype(message_name)
+                        .filter(|dtype| !matches!(dtype, PolarsDataType::Protobuf(_)))
+                        .map(|dtype| (decoder, dtype))
+                });
+                match decoder {
+                    Some((decoder, dtype)) => match decoder.decode(message_name, self.data) {
+                        Some(PolarsValue::Null) => Ok(PolarsValue::Null),
+                        Some(value) if value.dtype() == dtype => Ok(value),
+                        Some(value) => Err(WpilogError::ParseError(format!(
+                            "Protobuf decoder returned {:?} for message '{}', expected {:?}",
+                            value.dtype(),
+                            message_name,
+                            dtype
+                        ))),
+                        None => Err(WpilogError::ParseError(format!(
+                            "Protobuf decoder failed to decode message '{}'",
+                            message_name
+                        ))),
+                    },
+                    None => Ok(PolarsValue::String(self.get_string_with_policy(
+                        options.string_encoding,
+                        options.utf8_policy,
+                    )?)),
+                }
+            }
+            _ => self.get_value_as_polars(dtype, deserializer),
+        }
+    }
+
+    fn require_deserializer<'d, 'r>(
+        deserializer: Option<&'d StructDeserializer<'r>>,
+        struct_name: &str,
+    ) -> Result<&'d StructDeserializer<'r>> {
+        deserializer.ok_or_else(|| {
+            WpilogError::SchemaError(format!(
+                "Struct registry required to decode struct '{}'",
+                struct_name
+            ))
+        })
+    }
 }
 
+/// Control records and the plain string getters always decode lossily.
+const LOSSY: Utf8Policy = Utf8Policy::Lossy;
+
 /// Reads a length-prefixed string from within a buffer.
-fn read_inner_string(data: &[u8], pos: usize) -> Result<(String, usize)> {
+fn read_inner_string(data: &[u8], pos: usize, utf8_policy: Utf8Policy) -> Result<(String, usize)> {
     if pos + 4 > data.len() {
         return Err(WpilogError::ParseError(
             "Invalid string size position".to_string(),
@@ -276,17 +624,61 @@ fn read_inner_string(data: &[u8], pos: usize) -> Result<(String, usize)> {
         )));
     }
 
-    let s = match String::from_utf8(data[pos + 4..end].to_vec()) {
-        Ok(s) => s,
-        Err(_) => {
-            // Fallback: use lossy UTF-8 conversion for binary data marked as strings
-            String::from_utf8_lossy(&data[pos + 4..end]).to_string()
-        }
-    };
+    let s = decode_utf8(&data[pos + 4..end], utf8_policy)?;
 
     Ok((s, end))
 }
 
+/// Converts string bytes to a `String`, handling invalid UTF-8 according to `policy`.
+fn decode_utf8(bytes: &[u8], policy: Utf8Policy) -> Result<String> {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => Ok(s.to_string()),
+        Err(e) => match policy {
+            // Fallback for binary data marked as strings
+            Utf8Policy::Lossy => Ok(String::from_utf8_lossy(bytes).into_owned()),
+            Utf8Policy::ReplaceWith(replacement) => {
+                let mut s = String::with_capacity(bytes.len());
+                for chunk in bytes.utf8_chunks() {
+                    s.push_str(chunk.valid());
+                    if !chunk.invalid().is_empty() {
+                        s.push(replacement);
+                    }
+                }
+                Ok(s)
+            }
+            Utf8Policy::Strict => Err(WpilogError::ParseError(format!(
+                "Invalid UTF-8 in string: {}",
+                e
+            ))),
+        },
+    }
+}
+
+/// The tool that wrote a log, detected from its extra header string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogFlavor {
+    /// Written by AdvantageKit, whose extra header starts with `AdvantageKit`.
+    AdvantageKit,
+    /// Written by WPILib's `DataLog` (e.g. through `DataLogManager`), which leaves the
+    /// extra header empty.
+    WpiLib,
+    /// Written by another tool; holds the extra header.
+    Other(String),
+}
+
+impl LogFlavor {
+    /// Detects the flavor from an extra header string.
+    pub fn from_extra_header(header: &str) -> Self {
+        if header.starts_with("AdvantageKit") {
+            LogFlavor::AdvantageKit
+        } else if header.is_empty() {
+            LogFlavor::WpiLib
+        } else {
+            LogFlavor::Other(header.to_string())
+        }
+    }
+}
+
 /// WPILog file reader.
 pub struct DataLogReader<'a> {
     pub(crate) data: &'a [u8],
@@ -299,8 +691,24 @@ impl<'a> DataLogReader<'a> {
     }
 
     /// Returns true if the data appears to be a valid WPILog file.
+    ///
+    /// Versions newer than [`SUPPORTED_VERSIONS`] are valid headers, but
+    /// [`records`](Self::records) refuses to read them; see
+    /// [`check_version`](Self::check_version).
     pub fn is_valid(&self) -> bool {
-        self.data.len() >= 12 && &self.data[0..6] == b"WPILOG" && self.get_version() >= 0x0100
+        self.data.len() >= 12
+            && &self.data[0..6] == b"WPILOG"
+            && self.get_version() >= *SUPPORTED_VERSIONS.start()
+    }
+
+    /// Fails with [`WpilogError::UnsupportedVersion`] if the version is outside
+    /// [`SUPPORTED_VERSIONS`].
+    pub fn check_version(&self) -> Result<()> {
+        let version = self.get_version();
+        if !SUPPORTED_VERSIONS.contains(&version) {
+            return Err(WpilogError::UnsupportedVersion(version));
+        }
+        Ok(())
     }
 
     /// Gets the WPILog version number.
@@ -312,7 +720,8 @@ impl<'a> DataLogReader<'a> {
         cursor.read_u16::<LittleEndian>().unwrap_or(0)
     }
 
-    /// Gets the extra header string.
+    /// Gets the extra header string, without a leading UTF-8 byte order mark.
+    /// Invalid UTF-8 sequences are replaced with U+FFFD.
     pub fn get_extra_header(&self) -> String {
         if self.data.len() < 12 {
             return String::new();
@@ -325,25 +734,54 @@ impl<'a> DataLogReader<'a> {
             return String::new();
         }
 
-        String::from_utf8(self.data[12..12 + size].to_vec()).unwrap_or_default()
+        let header = &self.data[12..12 + size];
+        let header = header.strip_prefix(b"\xef\xbb\xbf").unwrap_or(header);
+        String::from_utf8_lossy(header).into_owned()
+    }
+
+    /// Detects the tool that wrote the log from its extra header.
+    pub fn flavor(&self) -> LogFlavor {
+        LogFlavor::from_extra_header(&self.get_extra_header())
     }
 
     /// Returns an iterator over all records in the log.
+    ///
+    /// Fails if the header is invalid, its version is unsupported, or its extra
+    /// header length runs past the end of the data.
     pub fn records(&self) -> Result<DataLogIterator<'a>> {
         if !self.is_valid() {
             return Err(WpilogError::InvalidFormat(
                 "Not a valid WPILOG file".to_string(),
             ));
         }
+        self.check_version()?;
 
         let mut cursor = Cursor::new(&self.data[8..12]);
         let extra_header_size = cursor.read_u32::<LittleEndian>()? as usize;
         let start_pos = 12 + extra_header_size;
+        if start_pos > self.data.len() {
+            return Err(WpilogError::InvalidFormat(format!(
+                "Extra header length {} exceeds the {} bytes after the fixed header",
+                extra_header_size,
+                self.data.len() - 12
+            )));
+        }
 
-        Ok(DataLogIterator {
-            data: self.data,
-            pos: start_pos,
-        })
+        Ok(DataLogIterator::new(self.data, start_pos))
+    }
+
+    /// Returns an iterator over the data records of a single entry.
+    ///
+    /// Control records are skipped, including those that refer to `entry_id`. Errors
+    /// from malformed records are passed through so truncation is still reported.
+    pub fn records_for_entry(
+        &self,
+        entry_id: u32,
+    ) -> Result<impl Iterator<Item = Result<DataLogRecord<'a>>>> {
+        Ok(self.records()?.filter(move |record| match record {
+            Ok(record) => !record.is_control() && record.entry == entry_id,
+            Err(_) => true,
+        }))
     }
 }
 
@@ -351,56 +789,173 @@ impl<'a> DataLogReader<'a> {
 pub struct DataLogIterator<'a> {
     data: &'a [u8],
     pos: usize,
+    progress: Option<(ProgressCallback, usize)>,
+    recover: bool,
+    skipped: Vec<(usize, usize)>,
 }
 
-impl<'a> Iterator for DataLogIterator<'a> {
-    type Item = Result<DataLogRecord>;
+/// The fields of a record header.
+struct RecordHeader {
+    len: usize,
+    entry: u64,
+    size: usize,
+    timestamp: u64,
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.data.len() < self.pos + 4 {
+impl<'a> DataLogIterator<'a> {
+    /// Creates an iterator over records starting at `pos`, skipping header validation.
+    pub(crate) fn new(data: &'a [u8], pos: usize) -> Self {
+        Self {
+            data,
+            pos,
+            progress: None,
+            recover: false,
+            skipped: Vec::new(),
+        }
+    }
+
+    /// Scans forward to the next plausible record header when a record's size runs
+    /// past the end of the data, instead of ending iteration.
+    pub(crate) fn with_recovery(mut self, recover: bool) -> Self {
+        self.recover = recover;
+        self
+    }
+
+    /// Byte ranges skipped while recovering, as `(offset, length)` pairs.
+    pub(crate) fn skipped(&self) -> &[(usize, usize)] {
+        &self.skipped
+    }
+
+    /// Reports progress to `callback` every `PROGRESS_INTERVAL` bytes and once at the end.
+    pub(crate) fn with_progress(mut self, callback: Option<ProgressCallback>) -> Self {
+        self.progress = callback.map(|cb| (cb, self.pos + PROGRESS_INTERVAL));
+        self
+    }
+
+    fn report_progress(&mut self, finished: bool) {
+        if let Some((callback, next_report)) = &mut self.progress {
+            if finished || self.pos >= *next_report {
+                callback(self.pos as u64, self.data.len() as u64);
+                *next_report = self.pos + PROGRESS_INTERVAL;
+            }
+        }
+        if finished {
+            self.progress = None;
+        }
+    }
+
+    /// Decodes the header of the record at `pos`, or returns None if it is incomplete.
+    fn header_at(&self, pos: usize) -> Option<RecordHeader> {
+        if self.data.len() < pos + 4 {
             return None;
         }
 
-        let header_byte = self.data[self.pos];
+        let header_byte = self.data[pos];
         let entry_len = ((header_byte & 0x3) + 1) as usize;
         let size_len = (((header_byte >> 2) & 0x3) + 1) as usize;
         let timestamp_len = (((header_byte >> 4) & 0x7) + 1) as usize;
         let header_len = 1 + entry_len + size_len + timestamp_len;
 
-        if self.data.len() < self.pos + header_len {
+        if self.data.len() < pos + header_len {
             return None;
         }
 
-        let entry = read_varint(&self.data[self.pos + 1..], entry_len);
-        let size = read_varint(&self.data[self.pos + 1 + entry_len..], size_len) as usize;
-        let timestamp = read_varint(
-            &self.data[self.pos + 1 + entry_len + size_len..],
-            timestamp_len,
-        );
+        Some(RecordHeader {
+            len: header_len,
+            entry: read_varint(&self.data[pos + 1..], entry_len),
+            size: read_varint(&self.data[pos + 1 + entry_len..], size_len) as usize,
+            timestamp: read_varint(&self.data[pos + 1 + entry_len + size_len..], timestamp_len),
+        })
+    }
 
-        if self.data.len() < self.pos + header_len + size {
+    /// Returns the end of the record at `pos` if it could be a real record: the
+    /// unused top bit of the header byte is clear, the payload fits in the data, and a
+    /// control record starts with a known control type.
+    fn plausible_record_end(&self, pos: usize) -> Option<usize> {
+        if self.data.get(pos)? & 0x80 != 0 {
+            return None;
+        }
+        let header = self.header_at(pos)?;
+        let start = pos + header.len;
+        let end = start + header.size;
+        let payload = self.data.get(start..end)?;
+        if header.entry == 0
+            && !matches!(
+                payload.first(),
+                Some(&(CONTROL_START | CONTROL_FINISH | CONTROL_SET_METADATA))
+            )
+        {
             return None;
         }
+        Some(end)
+    }
 
-        let data = self.data[self.pos + header_len..self.pos + header_len + size].to_vec();
+    /// Finds the first position at or after `from` holding a plausible record that is
+    /// followed by another plausible record or the end of the data.
+    fn resync_point(&self, from: usize) -> Option<usize> {
+        (from..self.data.len()).find(|&pos| {
+            self.plausible_record_end(pos).is_some_and(|end| {
+                end == self.data.len() || self.plausible_record_end(end).is_some()
+            })
+        })
+    }
+
+    /// Decodes the record at the current position, or returns None if it is incomplete.
+    fn read_record(&mut self) -> Option<Result<DataLogRecord<'a>>> {
+        let header = self.header_at(self.pos)?;
+
+        if self.data.len() < self.pos + header.len + header.size {
+            if !self.recover {
+                return None;
+            }
+
+            // The size is impossible; skip to the next plausible record
+            let resume = self.resync_point(self.pos + 1);
+            let end = resume.unwrap_or(self.data.len());
+            self.skipped.push((self.pos, end - self.pos));
+            self.pos = end;
+            return resume.and_then(|_| self.read_record());
+        }
+
+        let buffer: &'a [u8] = self.data;
+        let data = &buffer[self.pos + header.len..self.pos + header.len + header.size];
 
         let record = DataLogRecord {
-            entry: entry as u32,
-            timestamp,
+            entry: header.entry as u32,
+            timestamp: header.timestamp,
             data,
         };
 
-        self.pos += header_len + size;
+        self.pos += header.len + header.size;
 
         Some(Ok(record))
     }
+
+    /// Returns the byte offset of the next record to be read, from the start of the
+    /// log data.
+    ///
+    /// Reading the offset before and after `next()` gives the location and full
+    /// length (header and payload) of the returned record.
+    pub fn offset(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<'a> Iterator for DataLogIterator<'a> {
+    type Item = Result<DataLogRecord<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = self.read_record();
+        self.report_progress(record.is_none());
+        record
+    }
 }
 
 /// Reads a variable-length integer from a byte slice.
 fn read_varint(data: &[u8], len: usize) -> u64 {
     let mut val = 0u64;
-    for i in 0..len {
-        val |= (data[i] as u64) << (i * 8);
+    for (i, &byte) in data.iter().take(len).enumerate() {
+        val |= (byte as u64) << (i * 8);
     }
     val
 }
@@ -408,6 +963,8 @@ fn read_varint(data: &[u8], len: usize) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::struct_support::StructRegistry;
+    use crate::test_fixtures::{encode_start, start_payload, wpilog_header};
 
     #[test]
     fn test_read_varint() {
@@ -433,4 +990,612 @@ mod tests {
         let reader = DataLogReader::new(invalid_magic);
         assert!(!reader.is_valid());
     }
+
+    #[test]
+    fn test_unsupported_version() {
+        let minor_bump = b"WPILOG\x07\x01\x00\x00\x00\x00";
+        assert!(DataLogReader::new(minor_bump).records().is_ok());
+
+        let next_major = b"WPILOG\x00\x02\x00\x00\x00\x00";
+        let reader = DataLogReader::new(next_major);
+        assert_eq!(reader.get_version(), 0x0200);
+        assert!(matches!(
+            reader.records(),
+            Err(WpilogError::UnsupportedVersion(0x0200))
+        ));
+        assert!(matches!(
+            crate::converter::WpilogConverter::from_bytes(next_major),
+            Err(WpilogError::UnsupportedVersion(0x0200))
+        ));
+    }
+
+    #[test]
+    fn test_sized_integers() {
+        let decode = |dtype: PolarsDataType, data: &[u8]| {
+            let record = DataLogRecord {
+                entry: 1,
+                timestamp: 0,
+                data,
+            };
+            match record.get_value_as_polars(&dtype, None) {
+                Ok(PolarsValue::Int64(v)) => Ok(v),
+                Ok(other) => panic!("Expected Int64, got {:?}", other),
+                Err(e) => Err(e),
+            }
+        };
+
+        assert_eq!(decode(PolarsDataType::UInt8, &[0xff]).unwrap(), 255);
+        assert_eq!(decode(PolarsDataType::Int8, &[0xff]).unwrap(), -1);
+        assert_eq!(
+            decode(PolarsDataType::Int16, &(-300i16).to_le_bytes()).unwrap(),
+            -300
+        );
+        assert_eq!(
+            decode(PolarsDataType::UInt32, &u32::MAX.to_le_bytes()).unwrap(),
+            u32::MAX as i64
+        );
+        assert!(decode(PolarsDataType::Int16, &[0x01]).is_err());
+    }
+
+    #[test]
+    fn test_impossible_extra_header_length() {
+        let mut data = b"WPILOG\x00\x01".to_vec();
+        data.extend_from_slice(&0xFFFF_FFF0u32.to_le_bytes());
+        data.extend_from_slice(&[0x00, 0x01, 0x01, 0x01, 0xaa]);
+
+        let reader = DataLogReader::new(&data);
+        assert!(reader.is_valid());
+        match reader.records() {
+            Err(WpilogError::InvalidFormat(msg)) => assert!(msg.contains("Extra header length")),
+            other => panic!("Expected InvalidFormat, got {:?}", other.map(|_| ())),
+        }
+
+        // An extra header that exactly fills the data is valid and has no records
+        let mut data = b"WPILOG\x00\x01".to_vec();
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(b"abc");
+        assert_eq!(DataLogReader::new(&data).records().unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_extra_header() {
+        let header_with = |extra: &[u8]| {
+            let mut data = b"WPILOG\x00\x01".to_vec();
+            data.extend_from_slice(&(extra.len() as u32).to_le_bytes());
+            data.extend_from_slice(extra);
+            data
+        };
+
+        let data = header_with(b"\xef\xbb\xbfteam 254");
+        assert_eq!(DataLogReader::new(&data).get_extra_header(), "team 254");
+
+        // Invalid UTF-8 is replaced rather than discarding the whole header
+        let data = header_with(b"caf\xe9");
+        assert_eq!(DataLogReader::new(&data).get_extra_header(), "caf\u{fffd}");
+    }
+
+    #[test]
+    fn test_log_flavor() {
+        assert_eq!(
+            LogFlavor::from_extra_header("AdvantageKit"),
+            LogFlavor::AdvantageKit
+        );
+        assert_eq!(LogFlavor::from_extra_header(""), LogFlavor::WpiLib);
+        assert_eq!(
+            LogFlavor::from_extra_header("team 254"),
+            LogFlavor::Other("team 254".to_string())
+        );
+
+        let mut data = b"WPILOG\x00\x01".to_vec();
+        data.extend_from_slice(&12u32.to_le_bytes());
+        data.extend_from_slice(b"AdvantageKit");
+        assert_eq!(DataLogReader::new(&data).flavor(), LogFlavor::AdvantageKit);
+
+        let data = b"WPILOG\x00\x01\x00\x00\x00\x00";
+        assert_eq!(DataLogReader::new(data).flavor(), LogFlavor::WpiLib);
+    }
+
+    #[test]
+    fn test_records_for_entry() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "a", "double"));
+        // Data records: entry 1 at t=2, entry 2 at t=3, entry 1 at t=4
+        for (entry, timestamp) in [(1u8, 2u8), (2, 3), (1, 4)] {
+            data.extend_from_slice(&[0x00, entry, 0x01, timestamp, 0xaa]);
+        }
+
+        let reader = DataLogReader::new(&data);
+        let timestamps: Vec<u64> = reader
+            .records_for_entry(1)
+            .unwrap()
+            .map(|r| r.unwrap().timestamp)
+            .collect();
+        assert_eq!(timestamps, vec![2, 4]);
+
+        assert_eq!(reader.records_for_entry(0).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_get_value_as_polars() {
+        let record = DataLogRecord {
+            entry: 1,
+            timestamp: 0,
+            data: &42i64.to_le_bytes(),
+        };
+        match record
+            .get_value_as_polars(&PolarsDataType::Int64, None)
+            .unwrap()
+        {
+            PolarsValue::Int64(v) => assert_eq!(v, 42),
+            other => panic!("Expected Int64, got {:?}", other),
+        }
+
+        // Struct types need a deserializer
+        let result =
+            record.get_value_as_polars(&PolarsDataType::Struct("Pose2d".to_string()), None);
+        assert!(matches!(result, Err(WpilogError::SchemaError(_))));
+    }
+
+    #[test]
+    fn test_struct_array_size_mismatch() {
+        let mut registry = StructRegistry::new();
+        registry
+            .register("Point".to_string(), "float x; float y")
+            .unwrap();
+        let deserializer = StructDeserializer::new(&registry);
+
+        let record = DataLogRecord {
+            entry: 1,
+            timestamp: 0,
+            data: &[0u8; 17],
+        };
+        let result = record.get_value_as_polars(
+            &PolarsDataType::StructArray("Point".to_string()),
+            Some(&deserializer),
+        );
+        match result {
+            Err(WpilogError::StructArraySizeMismatch {
+                struct_name,
+                struct_size,
+                actual_len,
+            }) => {
+                assert_eq!(struct_name, "Point");
+                assert_eq!(struct_size, 8);
+                assert_eq!(actual_len, 17);
+            }
+            other => panic!("Expected StructArraySizeMismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_get_start_entry() {
+        let payload = start_payload(7, "name", "double", "");
+        let record = DataLogRecord {
+            entry: 0,
+            timestamp: 0,
+            data: &payload,
+        };
+
+        assert_eq!(record.get_start_entry().unwrap(), 7);
+        assert_eq!(record.get_start_data().unwrap().entry, 7);
+
+        let data_record = DataLogRecord {
+            entry: 7,
+            timestamp: 0,
+            data: &payload,
+        };
+        assert!(data_record.get_start_entry().is_err());
+    }
+
+    #[test]
+    fn test_minimal_start_record() {
+        // Control type, entry ID, and three empty strings
+        let mut payload = vec![0x00];
+        payload.extend_from_slice(&3u32.to_le_bytes());
+        payload.extend_from_slice(&[0; 12]);
+        let record = DataLogRecord {
+            entry: 0,
+            timestamp: 0,
+            data: &payload,
+        };
+
+        assert!(record.is_start());
+        let start = record.get_start_data().unwrap();
+        assert_eq!(start.entry, 3);
+        assert_eq!(start.name, "");
+        assert_eq!(start.type_name, "");
+        assert_eq!(start.metadata, "");
+
+        // A truncated start is still a start record, but fails to decode
+        let truncated = DataLogRecord {
+            data: &payload[..3],
+            ..record
+        };
+        assert!(truncated.is_start());
+        assert!(truncated.get_start_entry().is_err());
+        assert!(truncated.get_start_data().is_err());
+    }
+
+    #[test]
+    fn test_recover_from_impossible_size() {
+        let mut data = b"WPILOG\x00\x01\x00\x00\x00\x00".to_vec();
+        data.extend_from_slice(&[0x00, 0x01, 0x01, 0x01, 0xaa]);
+        // 4-byte size field claiming far more bytes than remain
+        let corrupt_offset = data.len();
+        data.extend_from_slice(&[0x0c, 0x01, 0xff, 0xff, 0xff, 0xff, 0x02]);
+        data.extend_from_slice(&[0x00, 0x01, 0x01, 0x03, 0xbb]);
+        data.extend_from_slice(&[0x00, 0x01, 0x01, 0x04, 0xcc]);
+
+        let reader = DataLogReader::new(&data);
+        assert_eq!(reader.records().unwrap().count(), 1);
+
+        let mut records = reader.records().unwrap().with_recovery(true);
+        let timestamps: Vec<u64> = records.by_ref().map(|r| r.unwrap().timestamp).collect();
+        assert_eq!(timestamps, vec![1, 3, 4]);
+        assert_eq!(records.skipped(), &[(corrupt_offset, 7)]);
+    }
+
+    #[test]
+    fn test_iterator_offset() {
+        let mut data = b"WPILOG\x00\x01\x03\x00\x00\x00abc".to_vec();
+        // 1-byte fields: 4-byte header + 1-byte payload
+        data.extend_from_slice(&[0x00, 0x01, 0x01, 0x01, 0xaa]);
+        // 2-byte entry, 8-byte timestamp: 12-byte header + 8-byte payload
+        data.extend_from_slice(&[0x71, 0x02, 0x00, 0x08]);
+        data.extend_from_slice(&2u64.to_le_bytes());
+        data.extend_from_slice(&1.5f64.to_le_bytes());
+
+        let reader = DataLogReader::new(&data);
+        let mut records = reader.records().unwrap();
+        assert_eq!(records.offset(), 15);
+        records.next().unwrap().unwrap();
+        assert_eq!(records.offset(), 15 + 4 + 1);
+        records.next().unwrap().unwrap();
+        assert_eq!(records.offset(), 20 + 12 + 8);
+        assert_eq!(records.offset(), data.len());
+        assert!(records.next().is_none());
+        assert_eq!(records.offset(), data.len());
+    }
+
+    #[test]
+    fn test_unknown_control_type_is_not_control() {
+        let payload = [0x07, 0x01, 0x00, 0x00, 0x00];
+        let record = DataLogRecord {
+            entry: 0,
+            timestamp: 0,
+            data: &payload,
+        };
+        assert!(!record.is_control());
+        assert!(record.is_malformed_control());
+        assert!(!record.is_start() && !record.is_finish() && !record.is_set_metadata());
+
+        let empty = DataLogRecord {
+            data: &[],
+            ..record
+        };
+        assert!(empty.is_malformed_control());
+
+        let finish = DataLogRecord {
+            data: &[0x01, 0x01, 0x00, 0x00, 0x00],
+            ..record
+        };
+        assert!(finish.is_control());
+        assert!(!finish.is_malformed_control());
+    }
+
+    #[test]
+    fn test_padded_finish_record() {
+        let mut payload = vec![0x01]; // Control type = Finish
+        payload.extend_from_slice(&9u32.to_le_bytes());
+        payload.extend_from_slice(&[0; 3]);
+        let record = DataLogRecord {
+            entry: 0,
+            timestamp: 0,
+            data: &payload,
+        };
+
+        assert!(record.is_finish());
+        assert!(!record.is_start());
+        assert_eq!(record.get_finish_entry().unwrap(), 9);
+    }
+
+    #[test]
+    fn test_numeric_arrays() {
+        fn record(data: &[u8]) -> DataLogRecord<'_> {
+            DataLogRecord {
+                entry: 1,
+                timestamp: 0,
+                data,
+            }
+        }
+
+        let ints = [1i64, -2, i64::MAX];
+        let data: Vec<u8> = ints.iter().flat_map(|v| v.to_le_bytes()).collect();
+        assert_eq!(record(&data).get_integer_array().unwrap(), ints);
+
+        let floats = [0.5f32, -1.25, f32::MIN_POSITIVE];
+        let data: Vec<u8> = floats.iter().flat_map(|v| v.to_le_bytes()).collect();
+        assert_eq!(record(&data).get_float_array().unwrap(), floats);
+
+        let doubles = [3.5f64, -0.0, 1e300];
+        let data: Vec<u8> = doubles.iter().flat_map(|v| v.to_le_bytes()).collect();
+        assert_eq!(record(&data).get_double_array().unwrap(), doubles);
+
+        assert!(record(&[]).get_double_array().unwrap().is_empty());
+        assert!(record(&[0; 12]).get_double_array().is_err());
+        assert!(record(&[0; 6]).get_float_array().is_err());
+    }
+
+    #[test]
+    fn test_timestamp_i64() {
+        let mut record = DataLogRecord {
+            entry: 1,
+            timestamp: i64::MAX as u64,
+            data: &[],
+        };
+        assert_eq!(record.timestamp_i64().unwrap(), i64::MAX);
+
+        record.timestamp = u64::MAX - 1;
+        assert!(matches!(
+            record.timestamp_i64(),
+            Err(WpilogError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_skip_record_truncates_partial_numeric_arrays() {
+        let skip = ParseOptions {
+            on_error: ErrorPolicy::SkipRecord,
+            ..Default::default()
+        };
+        let mut data = Vec::new();
+        data.extend_from_slice(&1.5f64.to_le_bytes());
+        data.extend_from_slice(&2.5f64.to_le_bytes());
+        data.extend_from_slice(&[0xAA; 4]);
+        let record = DataLogRecord {
+            entry: 1,
+            timestamp: 0,
+            data: &data,
+        };
+
+        assert!(record
+            .get_value_as_polars_with_options(
+                &PolarsDataType::Float64Array,
+                None,
+                &ParseOptions::default()
+            )
+            .is_err());
+        assert!(matches!(
+            record.get_value_as_polars_with_options(&PolarsDataType::Float64Array, None, &skip),
+            Ok(PolarsValue::Float64Array(ref values)) if values == &[1.5, 2.5]
+        ));
+        assert!(matches!(
+            record.get_value_as_polars_with_options(&PolarsDataType::Int64Array, None, &skip),
+            Ok(PolarsValue::Int64Array(ref values)) if values.len() == 2
+        ));
+        assert!(matches!(
+            record.get_value_as_polars_with_options(&PolarsDataType::Float32Array, None, &skip),
+            Ok(PolarsValue::Float32Array(ref values)) if values.len() == 5
+        ));
+    }
+
+    #[test]
+    fn test_boolean_sizes() {
+        let lenient = ParseOptions {
+            lenient_booleans: true,
+            ..Default::default()
+        };
+        let decode = |data: &[u8], options: &ParseOptions| {
+            let record = DataLogRecord {
+                entry: 1,
+                timestamp: 0,
+                data,
+            };
+            record.get_value_as_polars_with_options(&PolarsDataType::Boolean, None, options)
+        };
+
+        // 1 byte is accepted in both modes
+        assert!(matches!(
+            decode(&[1], &ParseOptions::default()),
+            Ok(PolarsValue::Boolean(true))
+        ));
+        assert!(matches!(
+            decode(&[0], &lenient),
+            Ok(PolarsValue::Boolean(false))
+        ));
+
+        // Padded records are only accepted leniently, using the first byte
+        assert!(decode(&[1, 0, 0, 0], &ParseOptions::default()).is_err());
+        assert!(matches!(
+            decode(&[1, 0, 0, 0], &lenient),
+            Ok(PolarsValue::Boolean(true))
+        ));
+
+        // Empty records are always an error
+        assert!(decode(&[], &ParseOptions::default()).is_err());
+        assert!(decode(&[], &lenient).is_err());
+    }
+
+    #[test]
+    fn test_bitpacked_boolean_array() {
+        let record = DataLogRecord {
+            entry: 1,
+            timestamp: 0,
+            data: &[0b0000_0101, 0b1000_0000],
+        };
+
+        let unpacked = record.get_bitpacked_boolean_array();
+        assert_eq!(unpacked.len(), 16);
+        assert_eq!(
+            &unpacked[..8],
+            &[true, false, true, false, false, false, false, false]
+        );
+        assert!(unpacked[15]);
+
+        // Byte-per-bool decoding sees two (true) elements
+        assert_eq!(record.get_boolean_array(), vec![true, true]);
+
+        let options = ParseOptions {
+            boolean_array_bitpacked: true,
+            ..Default::default()
+        };
+        match record
+            .get_value_as_polars_with_options(&PolarsDataType::BooleanArray, None, &options)
+            .unwrap()
+        {
+            PolarsValue::BooleanArray(values) => assert_eq!(values, unpacked),
+            other => panic!("Expected BooleanArray, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_utf8_policies() {
+        let mut array = 2u32.to_le_bytes().to_vec();
+        for s in [&b"ok"[..], &b"\xc3("[..]] {
+            array.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            array.extend_from_slice(s);
+        }
+        let array = DataLogRecord {
+            entry: 1,
+            timestamp: 0,
+            data: &array,
+        };
+        let string = DataLogRecord {
+            entry: 1,
+            timestamp: 0,
+            data: b"\xffhi\xfe",
+        };
+
+        assert_eq!(
+            string
+                .get_string_with_policy(StringEncoding::Raw, Utf8Policy::Lossy)
+                .unwrap(),
+            "\u{fffd}hi\u{fffd}"
+        );
+        assert_eq!(
+            string
+                .get_string_with_policy(StringEncoding::Raw, Utf8Policy::ReplaceWith('?'))
+                .unwrap(),
+            "?hi?"
+        );
+        assert!(string
+            .get_string_with_policy(StringEncoding::Raw, Utf8Policy::Strict)
+            .is_err());
+
+        assert_eq!(
+            array
+                .get_string_array_with_policy(Utf8Policy::ReplaceWith('_'))
+                .unwrap(),
+            vec!["ok", "_("]
+        );
+        assert!(array
+            .get_string_array_with_policy(Utf8Policy::Strict)
+            .is_err());
+        // The default getters stay lossy
+        assert_eq!(array.get_string_array().unwrap(), vec!["ok", "\u{fffd}("]);
+    }
+
+    #[test]
+    fn test_string_array_corrupt_element_length() {
+        let encode = |lengths: [u32; 3]| {
+            let mut data = 3u32.to_le_bytes().to_vec();
+            for (len, s) in lengths.iter().zip(["ab", "cd", "ef"]) {
+                data.extend_from_slice(&len.to_le_bytes());
+                data.extend_from_slice(s.as_bytes());
+            }
+            data
+        };
+        let decode = |data: &[u8]| {
+            DataLogRecord {
+                entry: 1,
+                timestamp: 0,
+                data,
+            }
+            .get_string_array()
+        };
+
+        assert_eq!(decode(&encode([2, 2, 2])).unwrap(), vec!["ab", "cd", "ef"]);
+
+        // The middle length swallows the next element, leaving too few bytes
+        match decode(&encode([2, 8, 2])) {
+            Err(WpilogError::ParseError(msg)) => assert_eq!(
+                msg,
+                "Invalid string array element 1: length leaves 0 bytes for 1 more elements"
+            ),
+            other => panic!("Expected ParseError, got {:?}", other),
+        }
+
+        // The middle length runs past the end of the record
+        match decode(&encode([2, 20, 2])) {
+            Err(WpilogError::ParseError(msg)) => {
+                assert!(
+                    msg.starts_with("Invalid string array element 1:"),
+                    "{}",
+                    msg
+                )
+            }
+            other => panic!("Expected ParseError, got {:?}", other),
+        }
+
+        // A short middle length leaves the rest misaligned
+        assert!(decode(&encode([2, 1, 2])).is_err());
+
+        let mut trailing = encode([2, 2, 2]);
+        trailing.push(0);
+        assert!(decode(&trailing).is_err());
+    }
+
+    #[test]
+    fn test_string_encodings() {
+        let raw = DataLogRecord {
+            entry: 1,
+            timestamp: 0,
+            data: b"Enabled",
+        };
+        let mut prefixed_data = 7u32.to_le_bytes().to_vec();
+        prefixed_data.extend_from_slice(b"Enabled");
+        let prefixed = DataLogRecord {
+            entry: 1,
+            timestamp: 0,
+            data: &prefixed_data,
+        };
+
+        assert!(!raw.has_length_prefix());
+        assert!(prefixed.has_length_prefix());
+
+        // Raw encoding keeps the whole payload
+        assert_eq!(
+            raw.get_string_with_encoding(StringEncoding::Raw).unwrap(),
+            "Enabled"
+        );
+        assert_eq!(
+            prefixed
+                .get_string_with_encoding(StringEncoding::Raw)
+                .unwrap(),
+            "\u{7}\0\0\0Enabled"
+        );
+
+        // Length-prefixed encoding strips the prefix and rejects raw payloads
+        assert_eq!(
+            prefixed
+                .get_string_with_encoding(StringEncoding::LengthPrefixed)
+                .unwrap(),
+            "Enabled"
+        );
+        assert!(raw
+            .get_string_with_encoding(StringEncoding::LengthPrefixed)
+            .is_err());
+
+        // Auto detection handles both
+        assert_eq!(
+            raw.get_string_with_encoding(StringEncoding::Auto).unwrap(),
+            "Enabled"
+        );
+        assert_eq!(
+            prefixed
+                .get_string_with_encoding(StringEncoding::Auto)
+                .unwrap(),
+            "Enabled"
+        );
+    }
 }
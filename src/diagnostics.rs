@@ -0,0 +1,53 @@
+//! Non-fatal issues reported while converting WPILog data.
+
+/// Non-fatal issues encountered while converting a WPILog file.
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    /// Number of data records read before any START record for their entry.
+    pub orphan_records: usize,
+    /// Warnings about recoverable problems, in the order they were encountered.
+    pub warnings: Vec<String>,
+    /// Structs defined in the log whose schemas could not be registered, sorted by name.
+    pub unresolved_structs: Vec<UnresolvedStruct>,
+    /// Number of corrupt bytes skipped with [`ParseOptions::recover`](crate::ParseOptions::recover).
+    pub skipped_bytes: usize,
+}
+
+/// A struct whose schema could not be registered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedStruct {
+    pub name: String,
+    /// Referenced structs that are not registered. For a struct that has no schema at
+    /// all, this is the struct itself.
+    pub missing_dependencies: Vec<String>,
+}
+
+impl Diagnostics {
+    /// Creates an empty set of diagnostics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a warning and prints it to stderr.
+    pub fn warn(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        eprintln!("Warning: {}", message);
+        self.warnings.push(message);
+    }
+
+    /// Appends the issues recorded in `other` without printing them again.
+    pub fn extend(&mut self, other: Diagnostics) {
+        self.orphan_records += other.orphan_records;
+        self.warnings.extend(other.warnings);
+        self.unresolved_structs.extend(other.unresolved_structs);
+        self.skipped_bytes += other.skipped_bytes;
+    }
+
+    /// Returns true if no issues were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.orphan_records == 0
+            && self.warnings.is_empty()
+            && self.unresolved_structs.is_empty()
+            && self.skipped_bytes == 0
+    }
+}
@@ -0,0 +1,142 @@
+//! Hierarchical view of entry names.
+//!
+//! AdvantageKit and NetworkTables entries are slash-delimited paths such as
+//! `/RealOutputs/Drive/Module0/Angle` or `NT:/SmartDashboard/Auto`. This module
+//! arranges them into a tree for navigation without decoding any data.
+
+use crate::schema::WpilogSchema;
+use std::collections::BTreeMap;
+
+/// The WPILog entry found at a node's path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryInfo {
+    /// Full entry name as recorded in the START record.
+    pub entry_name: String,
+    pub entry_id: u32,
+    /// WPILog type string (e.g. `"double"` or `"struct:Pose2d"`).
+    pub type_name: String,
+}
+
+/// A node in the tree of entry names split on `/`.
+///
+/// Empty segments are ignored, so `/Drive/Speed` and `Drive/Speed` share a path, and
+/// an `NT:` prefix becomes its own top-level node. A node may hold an entry and have
+/// children at the same time when one entry's name is a prefix of another's.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EntryNode {
+    /// Path segment of this node; empty for the root.
+    pub name: String,
+    /// The entry with exactly this path, if any.
+    pub entry: Option<EntryInfo>,
+    /// Child nodes keyed by path segment.
+    pub children: BTreeMap<String, EntryNode>,
+}
+
+impl EntryNode {
+    /// Builds a tree from the columns of an inferred schema.
+    ///
+    /// If several entries map to the same path, the first one started is kept.
+    pub fn from_schema(schema: &WpilogSchema) -> Self {
+        let mut root = Self::default();
+        for column in schema.columns() {
+            root.insert(EntryInfo {
+                entry_name: column.name.clone(),
+                entry_id: column.entry_id,
+                type_name: column.type_name.clone(),
+            });
+        }
+        root
+    }
+
+    /// Looks up the node at a slash-delimited path relative to this node.
+    pub fn get(&self, path: &str) -> Option<&EntryNode> {
+        path_segments(path).try_fold(self, |node, segment| node.children.get(segment))
+    }
+
+    /// Returns true if this node has no children.
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    fn insert(&mut self, info: EntryInfo) {
+        let mut node = self;
+        for segment in path_segments(&info.entry_name) {
+            node = node
+                .children
+                .entry(segment.to_string())
+                .or_insert_with(|| EntryNode {
+                    name: segment.to_string(),
+                    ..Default::default()
+                });
+        }
+        if node.entry.is_none() {
+            node.entry = Some(info);
+        }
+    }
+}
+
+fn path_segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::ColumnInfo;
+    use crate::types::PolarsDataType;
+
+    fn schema_with(entries: &[(u32, &str, &str)]) -> WpilogSchema {
+        let mut schema = WpilogSchema::new();
+        for (entry_id, name, type_name) in entries {
+            schema.add_column(ColumnInfo {
+                entry_id: *entry_id,
+                name: name.to_string(),
+                dtype: PolarsDataType::from_wpilog_type(type_name).unwrap(),
+                type_name: type_name.to_string(),
+                nullable: true,
+                metadata: String::new(),
+            });
+        }
+        schema
+    }
+
+    #[test]
+    fn test_tree_from_paths() {
+        let schema = schema_with(&[
+            (1, "/RealOutputs/Drive/Module0/Angle", "double"),
+            (2, "/RealOutputs/Drive/Module1/Angle", "double"),
+            (3, "NT:/SmartDashboard/Auto", "string"),
+            (4, "plain", "int64"),
+        ]);
+        let tree = EntryNode::from_schema(&schema);
+
+        let names: Vec<&str> = tree.children.keys().map(|k| k.as_str()).collect();
+        assert_eq!(names, ["NT:", "RealOutputs", "plain"]);
+
+        let drive = tree.get("/RealOutputs/Drive").unwrap();
+        assert_eq!(drive.children.len(), 2);
+        assert!(drive.entry.is_none());
+
+        let angle = tree.get("RealOutputs/Drive/Module1/Angle").unwrap();
+        assert!(angle.is_leaf());
+        let info = angle.entry.as_ref().unwrap();
+        assert_eq!(info.entry_id, 2);
+        assert_eq!(info.type_name, "double");
+        assert_eq!(info.entry_name, "/RealOutputs/Drive/Module1/Angle");
+
+        let auto = tree.get("NT:/SmartDashboard/Auto").unwrap();
+        assert_eq!(auto.entry.as_ref().unwrap().entry_id, 3);
+        assert!(tree.get("/RealOutputs/Missing").is_none());
+    }
+
+    #[test]
+    fn test_entry_with_children() {
+        let schema = schema_with(&[(1, "/Drive", "double"), (2, "/Drive/Speed", "double")]);
+        let tree = EntryNode::from_schema(&schema);
+
+        let drive = tree.get("/Drive").unwrap();
+        assert_eq!(drive.entry.as_ref().unwrap().entry_id, 1);
+        assert!(!drive.is_leaf());
+        assert_eq!(drive.children["Speed"].entry.as_ref().unwrap().entry_id, 2);
+    }
+}
@@ -8,10 +8,15 @@ pub type Result<T> = std::result::Result<T, WpilogError>;
 /// Errors that can occur when parsing WPILog files.
 #[derive(Error, Debug)]
 pub enum WpilogError {
-    /// Invalid WPILog file format (e.g., wrong magic bytes, unsupported version)
+    /// Invalid WPILog file format (e.g., wrong magic bytes, truncated header)
     #[error("Invalid WPILOG format: {0}")]
     InvalidFormat(String),
 
+    /// WPILog version outside
+    /// [`SUPPORTED_VERSIONS`](crate::datalog::SUPPORTED_VERSIONS)
+    #[error("Unsupported WPILog version {}.{}", .0 >> 8, .0 & 0xff)]
+    UnsupportedVersion(u16),
+
     /// I/O error occurred while reading or writing
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
@@ -28,6 +33,16 @@ pub enum WpilogError {
     #[error("Schema error: {0}")]
     SchemaError(String),
 
+    /// Struct array record whose length is not a whole number of structs
+    #[error(
+        "Struct array size mismatch for '{struct_name}': {actual_len} bytes is not a multiple of struct size {struct_size}"
+    )]
+    StructArraySizeMismatch {
+        struct_name: String,
+        struct_size: usize,
+        actual_len: usize,
+    },
+
     /// Polars error during DataFrame construction
     #[error("Polars error: {0}")]
     PolarsError(#[from] polars::error::PolarsError),
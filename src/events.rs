@@ -0,0 +1,175 @@
+//! Decoding a log into a stream of events instead of a DataFrame.
+//!
+//! Each data record becomes one `(name, timestamp, value)` event, in file order.
+//! Records are decoded one at a time as the iterator advances, so a log can be
+//! processed without holding its values in memory.
+
+use crate::converter::WpilogConverter;
+use crate::datalog::{DataLogIterator, DataLogReader};
+use crate::diagnostics::Diagnostics;
+use crate::error::{Result, WpilogError};
+use crate::options::ParseOptions;
+use crate::schema::WpilogSchema;
+use crate::struct_support::{StructDeserializer, StructRegistry};
+use crate::types::PolarsValue;
+use std::collections::HashSet;
+use std::ops::Deref;
+
+/// Returns an iterator decoding each data record of WPILog data into an
+/// `(entry name, timestamp, value)` event, in file order.
+///
+/// The schema and struct registry are inferred first; values are then decoded as
+/// the iterator advances, the same way as for a DataFrame column. Control records,
+/// struct schema records, and records after an entry's FINISH are skipped. A record
+/// that fails to decode yields an error, and iteration continues after it.
+pub fn decoded_events(
+    data: &[u8],
+) -> Result<impl Iterator<Item = Result<(String, i64, PolarsValue)>> + '_> {
+    DecodedEvents::new(data)
+}
+
+/// Iterator over the decoded events of log data held in `D`.
+pub(crate) struct DecodedEvents<D> {
+    data: D,
+    pos: usize,
+    schema: WpilogSchema,
+    registry: StructRegistry,
+    finished_entries: HashSet<u32>,
+}
+
+impl<D: Deref<Target = [u8]>> DecodedEvents<D> {
+    /// Infers the schema and registry of `data` and positions the iterator at its
+    /// first record.
+    pub(crate) fn new(data: D) -> Result<Self> {
+        let reader = DataLogReader::new(&data);
+        if !reader.is_valid() {
+            return Err(WpilogError::InvalidFormat(
+                "Invalid WPILog file header".to_string(),
+            ));
+        }
+
+        let mut diagnostics = Diagnostics::new();
+        let (registry, mut schema) = WpilogConverter::build_registry_and_schema(
+            &reader,
+            StructRegistry::new(),
+            &ParseOptions::default(),
+            &mut diagnostics,
+        )?;
+        WpilogConverter::downgrade_unresolved_structs(&mut schema, &registry, &mut diagnostics);
+        let pos = reader.records()?.offset();
+
+        Ok(Self {
+            data,
+            pos,
+            schema,
+            registry,
+            finished_entries: HashSet::new(),
+        })
+    }
+}
+
+impl<D: Deref<Target = [u8]>> Iterator for DecodedEvents<D> {
+    type Item = Result<(String, i64, PolarsValue)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut records = DataLogIterator::new(&self.data, self.pos);
+        let deserializer = StructDeserializer::new(&self.registry);
+
+        loop {
+            let record = match records.next()? {
+                Ok(record) => record,
+                Err(e) => {
+                    self.pos = records.offset();
+                    return Some(Err(e));
+                }
+            };
+
+            if record.is_control() {
+                if record.is_finish() {
+                    match record.get_finish_entry() {
+                        Ok(entry) => {
+                            self.finished_entries.insert(entry);
+                        }
+                        Err(e) => {
+                            self.pos = records.offset();
+                            return Some(Err(e));
+                        }
+                    }
+                }
+                continue;
+            }
+            if self.finished_entries.contains(&record.entry) {
+                continue;
+            }
+            let Some(column) = self.schema.get_column_by_entry(record.entry) else {
+                continue;
+            };
+
+            self.pos = records.offset();
+            let event = record.timestamp_i64().and_then(|timestamp| {
+                let value = record.get_value_as_polars(&column.dtype, Some(&deserializer))?;
+                Ok((column.name.clone(), timestamp, value))
+            });
+            return Some(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{encode_finish, encode_record, encode_start, wpilog_header};
+
+    #[test]
+    fn test_decoded_events() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_start(2, "mode", "string"));
+        data.extend(encode_record(1, 2, &1.5f64.to_le_bytes()));
+        data.extend(encode_record(2, 2, b"Auto"));
+        data.extend(encode_record(1, 3, &2.5f64.to_le_bytes()));
+        // FINISH for entry 1 drops its later records
+        data.extend(encode_finish(1, 4));
+        data.extend(encode_record(1, 5, &3.5f64.to_le_bytes()));
+        data.extend(encode_record(2, 5, b"Teleop"));
+
+        let events: Vec<(String, i64, String)> = decoded_events(&data)
+            .unwrap()
+            .map(|event| {
+                let (name, timestamp, value) = event.unwrap();
+                (name, timestamp, format!("{:?}", value))
+            })
+            .collect();
+        let expected = [
+            ("speed", 2, "Float64(1.5)"),
+            ("mode", 2, "String(\"Auto\")"),
+            ("speed", 3, "Float64(2.5)"),
+            ("mode", 5, "String(\"Teleop\")"),
+        ];
+        assert_eq!(events.len(), expected.len());
+        for (event, (name, timestamp, value)) in events.iter().zip(expected) {
+            assert_eq!(
+                (event.0.as_str(), event.1, event.2.as_str()),
+                (name, timestamp, value)
+            );
+        }
+
+        assert!(decoded_events(b"NOTLOG").is_err());
+    }
+
+    #[test]
+    fn test_decode_error_does_not_end_iteration() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_record(1, 2, &[0x00, 0x01]));
+        data.extend(encode_record(1, 3, &2.5f64.to_le_bytes()));
+
+        let events: Vec<_> = decoded_events(&data).unwrap().collect();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], Err(WpilogError::ParseError(_))));
+        assert!(matches!(
+            &events[1],
+            Ok((name, 3, PolarsValue::Float64(v))) if name == "speed" && *v == 2.5
+        ));
+    }
+}
@@ -0,0 +1,115 @@
+//! Loading WPILog files from disk.
+
+use crate::diagnostics::Diagnostics;
+use crate::error::{Result, WpilogError};
+use memmap2::Mmap;
+use std::fs::File;
+use std::ops::Deref;
+use std::path::Path;
+
+/// Size of the fixed WPILog header (magic, version, extra header length).
+const MIN_FILE_SIZE: usize = 12;
+
+/// Contents of a WPILog file, either memory-mapped or read into memory.
+pub(crate) enum FileData {
+    Mapped(Mmap),
+    Read(Vec<u8>),
+}
+
+impl FileData {
+    /// Opens a file using memory mapping, falling back to a normal read if mapping fails.
+    ///
+    /// Some filesystems (network mounts, /proc) don't support mmap; the fallback is
+    /// recorded as a warning in `diagnostics`. Files reporting zero length are read
+    /// rather than mapped, since memmap2 errors on them and /proc-style files report
+    /// zero length while still having contents. Files too small to hold a WPILog
+    /// header are rejected once loaded.
+    pub(crate) fn open(path: &Path, diagnostics: &mut Diagnostics) -> Result<Self> {
+        let file = File::open(path)?;
+        let data = if file.metadata()?.len() == 0 {
+            Self::Read(std::fs::read(path)?)
+        } else {
+            match unsafe { Mmap::map(&file) } {
+                Ok(mmap) => Self::Mapped(mmap),
+                Err(e) => {
+                    diagnostics.warn(format!(
+                        "Memory mapping {} failed ({}), reading file instead",
+                        path.display(),
+                        e
+                    ));
+                    Self::Read(std::fs::read(path)?)
+                }
+            }
+        };
+
+        if data.len() < MIN_FILE_SIZE {
+            return Err(WpilogError::InvalidFormat(format!(
+                "file too small: {} bytes",
+                data.len()
+            )));
+        }
+        Ok(data)
+    }
+}
+
+impl Deref for FileData {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Mapped(mmap) => mmap,
+            Self::Read(data) => data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_open_maps_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"WPILOG\x00\x01\x00\x00\x00\x00").unwrap();
+
+        let mut diagnostics = Diagnostics::new();
+        let data = FileData::open(file.path(), &mut diagnostics).unwrap();
+
+        assert!(matches!(data, FileData::Mapped(_)));
+        assert_eq!(&data[..6], b"WPILOG");
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_open_rejects_tiny_files() {
+        for contents in [&b""[..], &b"WPILO"[..]] {
+            let mut file = tempfile::NamedTempFile::new().unwrap();
+            file.write_all(contents).unwrap();
+
+            match FileData::open(file.path(), &mut Diagnostics::new()) {
+                Err(WpilogError::InvalidFormat(msg)) => {
+                    assert_eq!(msg, format!("file too small: {} bytes", contents.len()));
+                }
+                _ => panic!("Expected InvalidFormat for {} byte file", contents.len()),
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_open_reads_zero_length_proc_file() {
+        let path = Path::new("/proc/self/status");
+        assert_eq!(std::fs::metadata(path).unwrap().len(), 0);
+
+        let data = FileData::open(path, &mut Diagnostics::new()).unwrap();
+        assert!(matches!(data, FileData::Read(_)));
+        assert!(data.starts_with(b"Name:"));
+    }
+
+    #[test]
+    fn test_read_fallback_derefs_to_bytes() {
+        let data = FileData::Read(b"WPILOG".to_vec());
+        assert_eq!(&*data, b"WPILOG");
+    }
+}
@@ -0,0 +1,293 @@
+//! Indexed point-in-time access to WPILog values.
+//!
+//! This module provides:
+//! - `WpilogIndex`: per-entry sorted timestamp/value pairs
+//! - "Value as of time T" lookups via binary search
+//! - `RawValueIndex`: undecoded record payloads by entry ID and timestamp
+
+use crate::converter::WpilogConverter;
+use crate::datalog::DataLogReader;
+use crate::diagnostics::Diagnostics;
+use crate::error::{Result, WpilogError};
+use crate::file_data::FileData;
+use crate::options::ParseOptions;
+use crate::schema::WpilogSchema;
+use crate::struct_support::{StructDeserializer, StructRegistry};
+use crate::types::PolarsValue;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Per-entry index of decoded values, sorted by timestamp.
+///
+/// Useful when the same log is queried many times for the value of an entry at a
+/// given time, e.g. when scrubbing through a recording.
+pub struct WpilogIndex {
+    schema: WpilogSchema,
+    values: HashMap<String, Vec<(i64, PolarsValue)>>,
+}
+
+impl WpilogIndex {
+    /// Builds an index from WPILog data in a byte slice.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let reader = DataLogReader::new(data);
+
+        if !reader.is_valid() {
+            return Err(WpilogError::InvalidFormat(
+                "Invalid WPILog file header".to_string(),
+            ));
+        }
+
+        let mut diagnostics = Diagnostics::new();
+        let (registry, mut schema) = WpilogConverter::build_registry_and_schema(
+            &reader,
+            StructRegistry::new(),
+            &ParseOptions::default(),
+            &mut diagnostics,
+        )?;
+        WpilogConverter::downgrade_unresolved_structs(&mut schema, &registry, &mut diagnostics);
+        let deserializer = StructDeserializer::new(&registry);
+
+        let mut values: HashMap<String, Vec<(i64, PolarsValue)>> = schema
+            .columns()
+            .iter()
+            .map(|c| (c.name.clone(), Vec::new()))
+            .collect();
+        let mut finished_entries = HashSet::new();
+
+        for record_result in reader.records()? {
+            let record = record_result?;
+
+            if record.is_control() {
+                if record.is_finish() {
+                    finished_entries.insert(record.get_finish_entry()?);
+                }
+                continue;
+            }
+
+            if finished_entries.contains(&record.entry) {
+                continue;
+            }
+
+            // Skip entries that aren't in the schema (e.g., structschema entries)
+            let column_info = match schema.get_column_by_entry(record.entry) {
+                Some(info) => info,
+                None => continue,
+            };
+
+            let value = record.get_value_as_polars(&column_info.dtype, Some(&deserializer))?;
+            if let Some(entry_values) = values.get_mut(&column_info.name) {
+                entry_values.push((record.timestamp_i64()?, value));
+            }
+        }
+
+        // Records are normally in timestamp order; a stable sort keeps ties in log order
+        for entry_values in values.values_mut() {
+            entry_values.sort_by_key(|(ts, _)| *ts);
+        }
+
+        Ok(Self { schema, values })
+    }
+
+    /// Builds an index from a WPILog file on disk using memory mapping.
+    ///
+    /// Falls back to reading the file into memory if it cannot be mapped.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let data = FileData::open(path.as_ref(), &mut Diagnostics::new())?;
+        Self::from_bytes(&data)
+    }
+
+    /// Returns the last value of the named entry at or before `timestamp`.
+    ///
+    /// Returns `None` if the entry is unknown or has no value at or before `timestamp`.
+    pub fn value_at(&self, name: &str, timestamp: i64) -> Option<&PolarsValue> {
+        let entry_values = self.values.get(name)?;
+        let idx = entry_values.partition_point(|(ts, _)| *ts <= timestamp);
+        if idx == 0 {
+            None
+        } else {
+            Some(&entry_values[idx - 1].1)
+        }
+    }
+
+    /// Returns the first value of the named entry at or after `timestamp`, with the
+    /// timestamp it was logged at.
+    ///
+    /// Returns `None` if the entry is unknown or has no value at or after `timestamp`.
+    pub fn value_at_or_after(&self, name: &str, timestamp: i64) -> Option<(i64, &PolarsValue)> {
+        let entry_values = self.values.get(name)?;
+        let idx = entry_values.partition_point(|(ts, _)| *ts < timestamp);
+        entry_values.get(idx).map(|(ts, value)| (*ts, value))
+    }
+
+    /// Returns the value of the named entry logged closest to `timestamp`, with the
+    /// timestamp it was logged at.
+    ///
+    /// When the values before and after are equally far away, the earlier one is
+    /// returned. Returns `None` if the entry is unknown or has no values.
+    pub fn nearest(&self, name: &str, timestamp: i64) -> Option<(i64, &PolarsValue)> {
+        let entry_values = self.values.get(name)?;
+        let idx = entry_values.partition_point(|(ts, _)| *ts < timestamp);
+        let after = entry_values.get(idx);
+        let before = idx.checked_sub(1).map(|i| &entry_values[i]);
+
+        let (ts, value) = match (before, after) {
+            (Some(before), Some(after)) => {
+                if timestamp.abs_diff(before.0) <= after.0.abs_diff(timestamp) {
+                    before
+                } else {
+                    after
+                }
+            }
+            (Some(only), None) | (None, Some(only)) => only,
+            (None, None) => return None,
+        };
+        Some((*ts, value))
+    }
+
+    /// Gets all timestamp/value pairs for the named entry, sorted by timestamp.
+    pub fn values(&self, name: &str) -> Option<&[(i64, PolarsValue)]> {
+        self.values.get(name).map(|v| v.as_slice())
+    }
+
+    /// Gets the schema of the indexed log.
+    pub fn schema(&self) -> &WpilogSchema {
+        &self.schema
+    }
+}
+
+/// Index of undecoded data record payloads by entry ID and timestamp.
+///
+/// Payloads borrow from the log buffer, so looking one up copies nothing. Useful
+/// for inspecting the original bytes when a decoder produces unexpected output.
+pub struct RawValueIndex<'a> {
+    values: HashMap<(u32, u64), &'a [u8]>,
+}
+
+impl<'a> RawValueIndex<'a> {
+    /// Builds an index of every data record in WPILog data.
+    ///
+    /// If an entry has several records at the same timestamp, the last one is kept,
+    /// matching the value that ends up in the DataFrame.
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self> {
+        let mut values = HashMap::new();
+
+        for record_result in DataLogReader::new(data).records()? {
+            let record = record_result?;
+            if !record.is_control() {
+                values.insert((record.entry, record.timestamp), record.data);
+            }
+        }
+
+        Ok(Self { values })
+    }
+
+    /// Gets the payload of the data record for `entry` at `timestamp`.
+    pub fn raw_value(&self, entry: u32, timestamp: u64) -> Option<&'a [u8]> {
+        self.values.get(&(entry, timestamp)).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{encode_record, encode_start, wpilog_header};
+
+    /// Helper to create a WPILog file with a "speed" double entry logged at t=2 and t=5
+    fn create_test_wpilog() -> Vec<u8> {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_record(1, 2, &1.5f64.to_le_bytes()));
+        data.extend(encode_record(1, 5, &2.5f64.to_le_bytes()));
+
+        data
+    }
+
+    fn as_f64(value: Option<&PolarsValue>) -> Option<f64> {
+        match value {
+            Some(PolarsValue::Float64(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_value_at() {
+        let index = WpilogIndex::from_bytes(&create_test_wpilog()).unwrap();
+
+        assert_eq!(as_f64(index.value_at("speed", 1)), None);
+        assert_eq!(as_f64(index.value_at("speed", 2)), Some(1.5));
+        assert_eq!(as_f64(index.value_at("speed", 4)), Some(1.5));
+        assert_eq!(as_f64(index.value_at("speed", 5)), Some(2.5));
+        assert_eq!(as_f64(index.value_at("speed", 100)), Some(2.5));
+        assert!(index.value_at("missing", 100).is_none());
+    }
+
+    #[test]
+    fn test_value_at_or_after() {
+        let index = WpilogIndex::from_bytes(&create_test_wpilog()).unwrap();
+
+        let found = |ts| {
+            index
+                .value_at_or_after("speed", ts)
+                .map(|(t, v)| (t, as_f64(Some(v))))
+        };
+        assert_eq!(found(0), Some((2, Some(1.5))));
+        assert_eq!(found(2), Some((2, Some(1.5))));
+        assert_eq!(found(3), Some((5, Some(2.5))));
+        assert_eq!(found(6), None);
+        assert!(index.value_at_or_after("missing", 0).is_none());
+    }
+
+    #[test]
+    fn test_nearest() {
+        let index = WpilogIndex::from_bytes(&create_test_wpilog()).unwrap();
+
+        let nearest = |ts| index.nearest("speed", ts).map(|(t, _)| t);
+        // Before the first and after the last value
+        assert_eq!(nearest(-10), Some(2));
+        assert_eq!(nearest(100), Some(5));
+        // Exact matches
+        assert_eq!(nearest(2), Some(2));
+        assert_eq!(nearest(5), Some(5));
+        // Between values, the closer one wins
+        assert_eq!(nearest(3), Some(2));
+        assert_eq!(nearest(4), Some(5));
+        assert_eq!(as_f64(index.nearest("speed", 4).map(|(_, v)| v)), Some(2.5));
+        assert!(index.nearest("missing", 0).is_none());
+    }
+
+    #[test]
+    fn test_nearest_tie_prefers_earlier() {
+        let index = WpilogIndex {
+            schema: WpilogSchema::new(),
+            values: HashMap::from([(
+                "speed".to_string(),
+                vec![
+                    (2, PolarsValue::Float64(1.0)),
+                    (6, PolarsValue::Float64(2.0)),
+                ],
+            )]),
+        };
+
+        assert_eq!(index.nearest("speed", 4).map(|(t, _)| t), Some(2));
+    }
+
+    #[test]
+    fn test_values() {
+        let index = WpilogIndex::from_bytes(&create_test_wpilog()).unwrap();
+
+        let values = index.values("speed").unwrap();
+        let timestamps: Vec<i64> = values.iter().map(|(ts, _)| *ts).collect();
+        assert_eq!(timestamps, vec![2, 5]);
+    }
+
+    #[test]
+    fn test_raw_value() {
+        let data = create_test_wpilog();
+        let index = RawValueIndex::from_bytes(&data).unwrap();
+
+        assert_eq!(index.raw_value(1, 5), Some(&2.5f64.to_le_bytes()[..]));
+        assert_eq!(index.raw_value(1, 3), None);
+        // Control records are not indexed
+        assert_eq!(index.raw_value(0, 1), None);
+    }
+}
@@ -0,0 +1,227 @@
+//! Merging columns that differ only by a trailing index.
+//!
+//! AdvantageKit logs an array of structured values, such as swerve module states, as
+//! one entry per element: `/Drive/ModuleStates/0`, `/Drive/ModuleStates/1`, and so
+//! on. These helpers find such groups and merge each into a single List column.
+
+use crate::error::{Result, WpilogError};
+use polars::chunked_array::builder::get_list_builder;
+use polars::prelude::*;
+use std::collections::BTreeMap;
+
+/// Groups the columns of `df` whose names end in `/<index>` by the prefix before
+/// it, e.g. `/Drive/ModuleStates/0` and `/Drive/ModuleStates/1` under
+/// `/Drive/ModuleStates`.
+///
+/// Each group lists its column names in index order. Only prefixes with at least
+/// two indexed columns, all of the same data type, whose indices are exactly
+/// `0..n` form a group. Zero-padded indices such as `/01` don't count as indices.
+pub fn indexed_column_groups(df: &DataFrame) -> BTreeMap<String, Vec<String>> {
+    let mut candidates: BTreeMap<&str, Vec<(usize, &Column)>> = BTreeMap::new();
+    for column in df.get_columns() {
+        let Some((prefix, suffix)) = column.name().rsplit_once('/') else {
+            continue;
+        };
+        if suffix.is_empty()
+            || !suffix.bytes().all(|b| b.is_ascii_digit())
+            || (suffix.len() > 1 && suffix.starts_with('0'))
+        {
+            continue;
+        }
+        let Ok(index) = suffix.parse::<usize>() else {
+            continue;
+        };
+        candidates.entry(prefix).or_default().push((index, column));
+    }
+
+    candidates
+        .into_iter()
+        .filter_map(|(prefix, mut members)| {
+            members.sort_by_key(|(index, _)| *index);
+            let is_group = members.len() >= 2
+                && members
+                    .iter()
+                    .enumerate()
+                    .all(|(k, (index, _))| *index == k)
+                && members
+                    .iter()
+                    .all(|(_, c)| c.dtype() == members[0].1.dtype());
+            if !is_group {
+                return None;
+            }
+            let names = members.iter().map(|(_, c)| c.name().to_string()).collect();
+            Some((prefix.to_string(), names))
+        })
+        .collect()
+}
+
+/// Replaces each group of [`indexed_column_groups`] with one List column named
+/// after the group's prefix, at the position of the group's leftmost column.
+///
+/// Row `i` of the merged column lists row `i` of every member in index order,
+/// keeping nulls for members not logged at that timestamp. A row where no member
+/// was logged is null.
+///
+/// # Errors
+///
+/// Returns [`WpilogError::InvalidEntry`] if a group's prefix is already the name
+/// of a column.
+///
+/// # Example
+///
+/// ```no_run
+/// use wpilog_polars::{merge_indexed_columns, WpilogParser};
+///
+/// let df = merge_indexed_columns(WpilogParser::from_file("robot.wpilog")?)?;
+/// println!("{:?}", df.column("/RealOutputs/Drive/ModuleStates")?);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn merge_indexed_columns(mut df: DataFrame) -> Result<DataFrame> {
+    for (prefix, names) in indexed_column_groups(&df) {
+        if df.get_column_index(&prefix).is_some() {
+            return Err(WpilogError::InvalidEntry(format!(
+                "Cannot merge indexed columns into '{}', which is already a column",
+                prefix
+            )));
+        }
+
+        let position = names
+            .iter()
+            .filter_map(|name| df.get_column_index(name))
+            .min()
+            .unwrap_or(df.width());
+        let merged = merge_group(&df, &prefix, &names)?;
+        for name in &names {
+            df.drop_in_place(name)?;
+        }
+        df.insert_column(position, merged)?;
+    }
+    Ok(df)
+}
+
+/// Builds the List column for one group of same-typed columns.
+fn merge_group(df: &DataFrame, prefix: &str, names: &[String]) -> Result<Series> {
+    let height = df.height();
+    let members = names
+        .iter()
+        .map(|name| Ok(df.column(name)?.as_materialized_series().clone()))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Stack the members, then interleave them so each row's values are contiguous
+    let mut stacked = members[0].clone();
+    for member in &members[1..] {
+        stacked.append(member)?;
+    }
+    let indices: Vec<IdxSize> = (0..height)
+        .flat_map(|row| (0..members.len()).map(move |k| (k * height + row) as IdxSize))
+        .collect();
+    let interleaved = stacked.take_slice(&indices)?;
+
+    let mut logged = members[0].is_not_null();
+    for member in &members[1..] {
+        logged = &logged | &member.is_not_null();
+    }
+    let mut builder =
+        get_list_builder(members[0].dtype(), interleaved.len(), height, prefix.into());
+    for (row, logged) in logged.into_no_null_iter().enumerate() {
+        if logged {
+            builder
+                .append_series(&interleaved.slice((row * members.len()) as i64, members.len()))?;
+        } else {
+            builder.append_null();
+        }
+    }
+
+    Ok(builder.finish().into_series())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> DataFrame {
+        df!(
+            "timestamp" => [1i64, 2, 3],
+            "/Drive/ModuleStates/1" => [Some(1.5f64), None, Some(1.6)],
+            "/Drive/ModuleStates/0" => [Some(0.5f64), None, None],
+            "/Drive/ModuleStates/2" => [Some(2.5f64), None, Some(2.6)],
+            "/Drive/Speed" => [Some(3.0f64), Some(3.1), None],
+            "/Vision/Tags/0" => [Some(7i64), None, None],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_indexed_column_groups() {
+        let groups = indexed_column_groups(&sample());
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups["/Drive/ModuleStates"],
+            vec![
+                "/Drive/ModuleStates/0",
+                "/Drive/ModuleStates/1",
+                "/Drive/ModuleStates/2"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_indexed_column_groups_require_contiguous_indices() {
+        let df = df!(
+            "/Arm/Joints/0" => [0.1f64],
+            "/Arm/Joints/2" => [0.3f64],
+            "/Climb/Motors/0" => [1.0f64],
+            "/Climb/Motors/1" => [1.1f64],
+            "/Climb/Motors/01" => [1.2f64],
+        )
+        .unwrap();
+
+        let groups = indexed_column_groups(&df);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups["/Climb/Motors"],
+            vec!["/Climb/Motors/0", "/Climb/Motors/1"]
+        );
+    }
+
+    #[test]
+    fn test_merge_indexed_columns() {
+        let df = merge_indexed_columns(sample()).unwrap();
+
+        let names: Vec<&str> = df.get_column_names().iter().map(|n| n.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "timestamp",
+                "/Drive/ModuleStates",
+                "/Drive/Speed",
+                "/Vision/Tags/0"
+            ]
+        );
+
+        let merged = df.column("/Drive/ModuleStates").unwrap().list().unwrap();
+        let rows: Vec<Option<Vec<Option<f64>>>> = merged
+            .into_iter()
+            .map(|row| row.map(|s| s.f64().unwrap().into_iter().collect()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                Some(vec![Some(0.5), Some(1.5), Some(2.5)]),
+                None,
+                Some(vec![None, Some(1.6), Some(2.6)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_indexed_columns_name_collision() {
+        let mut df = sample();
+        df.with_column(Series::new("/Drive/ModuleStates".into(), [0i64, 1, 2]))
+            .unwrap();
+        assert!(matches!(
+            merge_indexed_columns(df),
+            Err(WpilogError::InvalidEntry(_))
+        ));
+    }
+}
@@ -11,6 +11,9 @@
 //! - Automatic sparse data handling (null-filling for missing values)
 //! - Memory-mapped file I/O for maximum performance
 //! - UTF-8 fallback for binary data marked as strings
+//! - Incremental parsing of growing log files via [`WpilogStreamParser`]
+//! - Point-in-time value lookups via [`WpilogIndex`]
+//! - Writing DataFrames back to WPILog via [`WpilogWriter`]
 //!
 //! # Example
 //!
@@ -36,6 +39,8 @@
 //! | `double` | `Float64` |
 //! | `float` | `Float32` |
 //! | `int64` | `Int64` |
+//! | `int8`, `int16`, `int32` | `Int8`, `Int16`, `Int32` |
+//! | `uint8`, `uint16`, `uint32` | `UInt8`, `UInt16`, `UInt32` |
 //! | `boolean` | `Boolean` |
 //! | `string` | `String` |
 //! | `double[]` | `List(Float64)` |
@@ -59,25 +64,89 @@
 //! - Zero-copy where possible
 //! - Direct columnar format (no row-to-column conversion)
 
+pub mod align;
 pub mod builders;
 pub mod converter;
 pub mod datalog;
+pub mod diagnostics;
+pub mod entry_tree;
 pub mod error;
+pub mod events;
+mod file_data;
+pub mod index;
+pub mod indexed;
+pub mod options;
+pub mod parser_builder;
+pub mod preview;
+pub mod rates;
+pub mod resample;
 pub mod schema;
+pub mod stream;
+pub mod struct_field;
 pub mod struct_support;
+#[cfg(test)]
+mod test_fixtures;
 pub mod types;
+pub mod validation;
+pub mod writer;
 
+pub use align::align_to_entry;
+pub use converter::ParsedLog;
+pub use datalog::{LogFlavor, SUPPORTED_VERSIONS};
+pub use diagnostics::{Diagnostics, UnresolvedStruct};
+pub use entry_tree::{EntryInfo, EntryNode};
 pub use error::{Result, WpilogError};
+pub use events::decoded_events;
+pub use index::{RawValueIndex, WpilogIndex};
+pub use indexed::{indexed_column_groups, merge_indexed_columns};
+pub use options::{
+    EnumOutput, ErrorPolicy, ParseOptions, ProgressCallback, ProtobufDecoder, StringEncoding,
+    TimestampKind, UnknownTypePolicy, Utf8Policy,
+};
+pub use parser_builder::WpilogParserBuilder;
 pub use polars::prelude::DataFrame;
+pub use preview::preview;
+pub use rates::entry_rates;
+pub use resample::ResampleStrategy;
+pub use schema::{
+    ColumnDocument, ColumnInfo, SchemaDiff, SchemaDocument, StructFieldDocument, TypeChange,
+    WpilogSchema,
+};
+pub use stream::WpilogStreamParser;
+pub use struct_field::struct_field;
+pub use struct_support::StructRegistry;
+pub use validation::{SizeMismatch, ValidationReport};
+pub use writer::WpilogWriter;
 
 use converter::WpilogConverter;
-use std::fs::File;
+use file_data::FileData;
+use polars::prelude::{IpcWriter, ParquetCompression, SerWriter};
+use std::collections::HashMap;
+use std::io::Cursor;
 use std::path::Path;
+use types::normalize_type_name;
 
 /// Main entry point for parsing WPILog files.
 pub struct WpilogParser;
 
 impl WpilogParser {
+    /// Returns a builder for configuring parse options with chainable setters.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// let parsed = WpilogParser::builder()
+    ///     .drop_orphan_records(true)
+    ///     .type_override("/Robot/Mode", "string")
+    ///     .parse_file("robot.wpilog")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn builder() -> WpilogParserBuilder {
+        WpilogParserBuilder::new()
+    }
+
     /// Parses a WPILog file from a byte vector and returns a Polars DataFrame.
     ///
     /// # Arguments
@@ -107,7 +176,8 @@ impl WpilogParser {
     /// Parses a WPILog file from disk using memory mapping for maximum performance.
     ///
     /// This method uses memory-mapped I/O which is significantly faster than reading
-    /// the entire file into memory, especially for large files.
+    /// the entire file into memory, especially for large files. If the file cannot be
+    /// mapped (e.g. on some network filesystems), it is read into memory instead.
     ///
     /// # Arguments
     ///
@@ -129,9 +199,444 @@ impl WpilogParser {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<DataFrame> {
-        let file = File::open(path)?;
-        let mmap = unsafe { memmap2::Mmap::map(&file)? };
-        WpilogConverter::from_bytes(&mmap)
+        let data = FileData::open(path.as_ref(), &mut Diagnostics::new())?;
+        WpilogConverter::from_bytes(&data)
+    }
+
+    /// Parses a WPILog file from a byte vector, seeding struct definitions from `registry`.
+    ///
+    /// Struct schemas found in the file are merged with the registry, which allows
+    /// parsing log fragments whose `structschema` entries were recorded elsewhere.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::{StructRegistry, WpilogParser};
+    ///
+    /// let mut registry = StructRegistry::new();
+    /// registry.register("Translation2d".to_string(), "double x; double y")?;
+    ///
+    /// let data = std::fs::read("fragment.wpilog")?;
+    /// let df = WpilogParser::from_bytes_with_registry(data, registry)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_bytes_with_registry(data: Vec<u8>, registry: StructRegistry) -> Result<DataFrame> {
+        WpilogConverter::from_bytes_with_registry(&data, registry)
+    }
+
+    /// Parses a WPILog file from disk, reporting progress while data is accumulated.
+    ///
+    /// `progress` receives `(bytes_processed, total_bytes)` about every megabyte and
+    /// once when all records have been read.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// let df = WpilogParser::from_file_with_progress("robot.wpilog", |done, total| {
+    ///     eprint!("\r{:.0}%", done as f64 / total as f64 * 100.0);
+    /// })?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_file_with_progress<P, F>(path: P, progress: F) -> Result<DataFrame>
+    where
+        P: AsRef<Path>,
+        F: Fn(u64, u64) + Send + Sync + 'static,
+    {
+        let options = ParseOptions {
+            progress: Some(std::sync::Arc::new(progress)),
+            ..Default::default()
+        };
+        Ok(Self::from_file_with_options(path, &options)?.dataframe)
+    }
+
+    /// Parses a WPILog file from a byte vector using the given options.
+    ///
+    /// Returns the DataFrame together with diagnostics such as orphan record counts
+    /// and warnings about recoverable problems.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::{ParseOptions, WpilogParser};
+    ///
+    /// let data = std::fs::read("robot.wpilog")?;
+    /// let options = ParseOptions {
+    ///     drop_orphan_records: true,
+    ///     ..Default::default()
+    /// };
+    /// let parsed = WpilogParser::from_bytes_with_options(data, &options)?;
+    /// println!("{} orphan records", parsed.diagnostics.orphan_records);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_bytes_with_options(data: Vec<u8>, options: &ParseOptions) -> Result<ParsedLog> {
+        WpilogConverter::from_bytes_with_options(&data, options)
+    }
+
+    /// Parses a WPILog file from disk, also returning the schema built while parsing.
+    ///
+    /// The schema keeps the WPILog type string, entry ID, and metadata of each column,
+    /// which the DataFrame does not carry. This avoids reading the file a second time
+    /// with [`infer_schema`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// let (df, schema) = WpilogParser::from_file_with_schema("robot.wpilog")?;
+    /// for column in schema.columns() {
+    ///     println!("{}: {}", column.name, column.type_name);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_file_with_schema<P: AsRef<Path>>(path: P) -> Result<(DataFrame, WpilogSchema)> {
+        let data = FileData::open(path.as_ref(), &mut Diagnostics::new())?;
+        WpilogConverter::from_bytes_with_schema(&data)
+    }
+
+    /// Parses only the first `n_rows` rows of a WPILog file.
+    ///
+    /// Rows are distinct timestamps. Data accumulation stops once `n_rows` rows are
+    /// complete, so only the beginning of the file is decoded. This is cheaper than
+    /// parsing everything and taking the head of the DataFrame. Columns are sparse, so
+    /// entries that aren't logged within the first rows are entirely null.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// let df = WpilogParser::from_file_head("robot.wpilog", 1_000)?;
+    /// assert!(df.height() <= 1_000);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_file_head<P: AsRef<Path>>(path: P, n_rows: usize) -> Result<DataFrame> {
+        let data = FileData::open(path.as_ref(), &mut Diagnostics::new())?;
+        WpilogConverter::from_bytes_head(&data, n_rows)
+    }
+
+    /// Parses only the last `window_us` microseconds of a WPILog file.
+    ///
+    /// Unlike trimming a fully parsed DataFrame, records before the window are never
+    /// decoded, so the full frame is not materialized. The first row is at the window
+    /// start and holds the last value each entry had before the window.
+    ///
+    /// The file is scanned three times: the schema is read as in
+    /// [`from_file`](Self::from_file), a header-only pass finds the latest
+    /// timestamp, and a last pass decodes the window. The extra header scan is cheap next to decoding,
+    /// but still touches every record in the file.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// // The last 30 seconds of the log
+    /// let df = WpilogParser::from_file_tail("robot.wpilog", 30_000_000)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_file_tail<P: AsRef<Path>>(path: P, window_us: u64) -> Result<DataFrame> {
+        let data = FileData::open(path.as_ref(), &mut Diagnostics::new())?;
+        WpilogConverter::from_bytes_tail(&data, window_us)
+    }
+
+    /// Parses a WPILog file from disk with timestamps relative to a reference entry.
+    ///
+    /// The `timestamp` column is shifted so that t=0 is the first value of
+    /// `reference_entry`, e.g. `/DriverStation/MatchTime` for time since match start.
+    /// See [`align_to_entry`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// let df = WpilogParser::from_file_aligned("robot.wpilog", "/DriverStation/MatchTime")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_file_aligned<P: AsRef<Path>>(path: P, reference_entry: &str) -> Result<DataFrame> {
+        align_to_entry(Self::from_file(path)?, reference_entry)
+    }
+
+    /// Resamples a parsed log onto a regular grid of times `every_us` microseconds
+    /// apart, so every column has a value at each row.
+    ///
+    /// `on` names the time column, normally `timestamp`. See
+    /// [`resample::resample`] for how gaps are filled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::{ResampleStrategy, WpilogParser};
+    ///
+    /// let df = WpilogParser::from_file("robot.wpilog")?;
+    /// let dense = WpilogParser::resample(&df, "timestamp", 20_000, ResampleStrategy::Linear)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn resample(
+        df: &DataFrame,
+        on: &str,
+        every_us: u64,
+        strategy: ResampleStrategy,
+    ) -> Result<DataFrame> {
+        resample::resample(df, on, every_us, strategy)
+    }
+
+    /// Parses a WPILog file from disk using the given options.
+    ///
+    /// Uses memory mapping like [`from_file`](Self::from_file). A fallback to reading
+    /// the file into memory is reported in the returned diagnostics.
+    pub fn from_file_with_options<P: AsRef<Path>>(
+        path: P,
+        options: &ParseOptions,
+    ) -> Result<ParsedLog> {
+        let mut diagnostics = Diagnostics::new();
+        let data = FileData::open(path.as_ref(), &mut diagnostics)?;
+        let mut parsed = WpilogConverter::from_bytes_with_options(&data, options)?;
+        diagnostics.extend(parsed.diagnostics);
+        parsed.diagnostics = diagnostics;
+        Ok(parsed)
+    }
+
+    /// Checks that a WPILog file's struct columns have resolvable schemas and that
+    /// fixed-size records have the size of their type, without decoding any values.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// let report = WpilogParser::validate_file("robot.wpilog")?;
+    /// for unresolved in &report.unresolved_structs {
+    ///     println!("{} needs {:?}", unresolved.name, unresolved.missing_dependencies);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn validate_file<P: AsRef<Path>>(path: P) -> Result<ValidationReport> {
+        let data = FileData::open(path.as_ref(), &mut Diagnostics::new())?;
+        validation::validate(&data)
+    }
+
+    /// Parses a WPILog file and serializes the DataFrame to an in-memory Arrow IPC
+    /// file, without writing anything to disk.
+    ///
+    /// Useful for handing a log across an FFI boundary, e.g. to `pyarrow` or Python
+    /// Polars, which can read the buffer directly.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// let ipc = WpilogParser::to_ipc_bytes("robot.wpilog")?;
+    /// println!("{} bytes of Arrow IPC", ipc.len());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_ipc_bytes<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+        let mut df = Self::from_file(path)?;
+        let mut buffer = Cursor::new(Vec::new());
+        IpcWriter::new(&mut buffer).finish(&mut df)?;
+        Ok(buffer.into_inner())
+    }
+
+    /// Converts a WPILog file to Parquet without holding the whole DataFrame in
+    /// memory.
+    ///
+    /// Rows are parsed and written `chunk_rows` at a time, so memory use is bounded by
+    /// the chunk size rather than the log size. The file is written with the default
+    /// Parquet compression; use
+    /// [`WpilogParserBuilder::write_parquet_streaming`] to choose another or to set
+    /// parse options.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// WpilogParser::convert_to_parquet_streaming("robot.wpilog", "robot.parquet", 100_000)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn convert_to_parquet_streaming<P: AsRef<Path>, Q: AsRef<Path>>(
+        input: P,
+        output: Q,
+        chunk_rows: usize,
+    ) -> Result<()> {
+        Self::builder().write_parquet_streaming(
+            input,
+            output,
+            chunk_rows,
+            ParquetCompression::default(),
+        )?;
+        Ok(())
+    }
+
+    /// Computes the average logging rate of each entry in a WPILog file, in records
+    /// per second, from a single scan of record headers.
+    ///
+    /// Entries with fewer than two records have no rate and map to `None`. See
+    /// [`entry_rates`](rates::entry_rates).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// for (name, rate) in WpilogParser::entry_rates("robot.wpilog")? {
+    ///     match rate {
+    ///         Some(hz) => println!("{}: {:.1} Hz", name, hz),
+    ///         None => println!("{}: too few samples", name),
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn entry_rates<P: AsRef<Path>>(path: P) -> Result<HashMap<String, Option<f64>>> {
+        let data = FileData::open(path.as_ref(), &mut Diagnostics::new())?;
+        rates::entry_rates(&data)
+    }
+
+    /// Returns the sorted, distinct timestamps of the data records in a WPILog file
+    /// from a single scan of record headers. See [`timestamps`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// let timestamps = WpilogParser::timestamps("robot.wpilog")?;
+    /// println!("{} distinct timestamps", timestamps.len());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn timestamps<P: AsRef<Path>>(path: P) -> Result<Vec<i64>> {
+        let data = FileData::open(path.as_ref(), &mut Diagnostics::new())?;
+        timestamps(&data)
+    }
+
+    /// Returns an iterator decoding each data record of a WPILog file into an
+    /// `(entry name, timestamp, value)` event. See [`decoded_events`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// for event in WpilogParser::decoded_events("robot.wpilog")? {
+    ///     let (name, timestamp, value) = event?;
+    ///     println!("{} {} {:?}", timestamp, name, value);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn decoded_events<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<impl Iterator<Item = Result<(String, i64, types::PolarsValue)>>> {
+        let data = FileData::open(path.as_ref(), &mut Diagnostics::new())?;
+        events::DecodedEvents::new(data)
+    }
+
+    /// Returns the on-disk location of every data record in a WPILog file, grouped by
+    /// entry ID. See [`entry_byte_ranges`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// let ranges = WpilogParser::entry_byte_ranges("robot.wpilog")?;
+    /// for (timestamp, offset, len) in ranges.get(&1).into_iter().flatten() {
+    ///     println!("{}: {} bytes at {}", timestamp, len, offset);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn entry_byte_ranges<P: AsRef<Path>>(path: P) -> Result<EntryByteRanges> {
+        let data = FileData::open(path.as_ref(), &mut Diagnostics::new())?;
+        entry_byte_ranges(&data)
+    }
+
+    /// Builds a sampled preview of up to `rows` rows spread evenly across a WPILog
+    /// file, decoding only the records the rows need. See [`preview`].
+    ///
+    /// The rows show the log's state at the sampled timestamps, not exact rows of
+    /// the full DataFrame.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// let df = WpilogParser::preview("robot.wpilog", 10)?;
+    /// println!("{}", df);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn preview<P: AsRef<Path>>(path: P, rows: usize) -> Result<DataFrame> {
+        let data = FileData::open(path.as_ref(), &mut Diagnostics::new())?;
+        preview::preview(&data, rows)
+    }
+
+    /// Returns each column's START metadata parsed as JSON, keyed by column name.
+    /// See [`column_metadata_json`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// let metadata = WpilogParser::column_metadata_json("robot.wpilog")?;
+    /// if let Some(unit) = metadata["/Drive/Speed"].get("unit") {
+    ///     println!("Speed is in {}", unit);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn column_metadata_json<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<HashMap<String, serde_json::Value>> {
+        let data = FileData::open(path.as_ref(), &mut Diagnostics::new())?;
+        column_metadata_json(&data)
+    }
+
+    /// Returns the struct schemas defined in a WPILog file, keyed by struct name,
+    /// without parsing any other data. See [`struct_schemas`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::{StructRegistry, WpilogParser};
+    ///
+    /// let mut registry = StructRegistry::new();
+    /// for (name, schema) in WpilogParser::struct_schemas("robot.wpilog")? {
+    ///     println!("{}: {}", name, schema);
+    ///     let _ = registry.register(name, &schema);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn struct_schemas<P: AsRef<Path>>(path: P) -> Result<HashMap<String, String>> {
+        let data = FileData::open(path.as_ref(), &mut Diagnostics::new())?;
+        struct_schemas(&data)
+    }
+
+    /// Builds a tree of entry names split on `/` from a WPILog file's START records,
+    /// without parsing any data.
+    ///
+    /// Leaf nodes carry the entry's WPILog type and ID, which makes this suitable for
+    /// populating a signal browser before loading the data.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wpilog_polars::WpilogParser;
+    ///
+    /// let tree = WpilogParser::entry_tree("robot.wpilog")?;
+    /// if let Some(drive) = tree.get("/RealOutputs/Drive") {
+    ///     for name in drive.children.keys() {
+    ///         println!("{}", name);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn entry_tree<P: AsRef<Path>>(path: P) -> Result<EntryNode> {
+        let data = FileData::open(path.as_ref(), &mut Diagnostics::new())?;
+        let schema = infer_wpilog_schema(&data)?;
+        Ok(EntryNode::from_schema(&schema))
     }
 }
 
@@ -161,8 +666,226 @@ impl WpilogParser {
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 pub fn infer_schema(data: &[u8]) -> Result<polars::prelude::Schema> {
+    Ok(infer_wpilog_schema(data)?.to_polars_schema())
+}
+
+/// Returns the format version from a WPILog header, or `None` if `data` does not
+/// start with one.
+///
+/// The version is packed as `major << 8 | minor`, so `0x0100` is version 1.0; see
+/// [`format_wpilog_version`] for a readable form.
+///
+/// # Example
+///
+/// ```no_run
+/// use wpilog_polars::{format_wpilog_version, wpilog_version};
+///
+/// let data = std::fs::read("robot.wpilog")?;
+/// if let Some(version) = wpilog_version(&data) {
+///     println!("WPILog {}", format_wpilog_version(version));
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn wpilog_version(data: &[u8]) -> Option<u16> {
+    if data.len() < 12 || &data[0..6] != b"WPILOG" {
+        return None;
+    }
+    Some(datalog::DataLogReader::new(data).get_version())
+}
+
+/// Detects the tool that wrote a log from its extra header, or returns `None` if
+/// `data` does not start with a WPILog header.
+///
+/// # Example
+///
+/// ```no_run
+/// use wpilog_polars::{log_flavor, LogFlavor};
+///
+/// let data = std::fs::read("robot.wpilog")?;
+/// if log_flavor(&data) == Some(LogFlavor::AdvantageKit) {
+///     println!("AdvantageKit log");
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn log_flavor(data: &[u8]) -> Option<LogFlavor> {
+    wpilog_version(data)?;
+    Some(datalog::DataLogReader::new(data).flavor())
+}
+
+/// Returns the sorted, distinct timestamps of the data records in WPILog data.
+///
+/// Only record headers are read and no values are decoded, which is much cheaper
+/// than parsing the DataFrame for its `timestamp` column. Control records are
+/// excluded.
+pub fn timestamps(data: &[u8]) -> Result<Vec<i64>> {
+    let reader = datalog::DataLogReader::new(data);
+    if !reader.is_valid() {
+        return Err(WpilogError::InvalidFormat(
+            "Invalid WPILog file header".to_string(),
+        ));
+    }
+
+    let mut timestamps = Vec::new();
+    for record_result in reader.records()? {
+        let record = record_result?;
+        if record.entry != 0 {
+            timestamps.push(record.timestamp_i64()?);
+        }
+    }
+    timestamps.sort_unstable();
+    timestamps.dedup();
+    Ok(timestamps)
+}
+
+/// Data record locations grouped by entry ID, as `(timestamp, offset, len)` tuples
+/// in file order. Returned by [`entry_byte_ranges`].
+pub type EntryByteRanges = HashMap<u32, Vec<(i64, usize, usize)>>;
+
+/// Returns the location of every data record in WPILog data, grouped by entry ID,
+/// as `(timestamp, offset, len)` tuples in file order.
+///
+/// `offset` is the byte offset of the record header from the start of the data and
+/// `len` covers the header and payload, so each range holds exactly one complete
+/// record. Only record headers are read. Control records are excluded.
+pub fn entry_byte_ranges(data: &[u8]) -> Result<EntryByteRanges> {
+    let reader = datalog::DataLogReader::new(data);
+    if !reader.is_valid() {
+        return Err(WpilogError::InvalidFormat(
+            "Invalid WPILog file header".to_string(),
+        ));
+    }
+
+    let mut ranges = EntryByteRanges::new();
+    let mut records = reader.records()?;
+    let mut offset = records.offset();
+    while let Some(record_result) = records.next() {
+        let record = record_result?;
+        let end = records.offset();
+        if record.entry != 0 {
+            ranges.entry(record.entry).or_default().push((
+                record.timestamp_i64()?,
+                offset,
+                end - offset,
+            ));
+        }
+        offset = end;
+    }
+    Ok(ranges)
+}
+
+/// Returns the offset of the first record starting at or after byte `from` of WPILog
+/// data, or the end of the last complete record if no record starts there.
+///
+/// Records are not self-delimiting, so record headers are read from the start of
+/// the log up to `from`. The offsets returned for increasing `from` split the log
+/// into ranges for [`WpilogConverter::from_bytes_range_with_schema`]; the end of
+/// the records is the boundary closing the last range.
+///
+/// # Example
+///
+/// ```no_run
+/// use wpilog_polars::converter::WpilogConverter;
+/// use wpilog_polars::next_record_boundary;
+///
+/// let data = std::fs::read("robot.wpilog")?;
+/// let (schema, registry) = WpilogConverter::infer_schema_and_registry(&data)?;
+/// let start = next_record_boundary(&data, 0)?;
+/// let middle = next_record_boundary(&data, data.len() / 2)?;
+/// let end = next_record_boundary(&data, data.len())?;
+/// for range in [start..middle, middle..end] {
+///     let df = WpilogConverter::from_bytes_range_with_schema(&data, range, &schema, &registry)?;
+///     println!("{}", df);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn next_record_boundary(data: &[u8], from: usize) -> Result<usize> {
+    let reader = datalog::DataLogReader::new(data);
+    if !reader.is_valid() {
+        return Err(WpilogError::InvalidFormat(
+            "Invalid WPILog file header".to_string(),
+        ));
+    }
+
+    let mut records = reader.records()?;
+    while records.offset() < from {
+        if records.next().transpose()?.is_none() {
+            break;
+        }
+    }
+    Ok(records.offset())
+}
+
+/// Returns each column's START metadata parsed as JSON, keyed by column name, from
+/// a scan of START records only.
+///
+/// Metadata that is not valid JSON, including empty metadata, is returned unchanged
+/// as a JSON string value.
+///
+/// # Errors
+///
+/// Returns [`WpilogError::SchemaError`] if two entries share a column name, as
+/// their metadata can't both be keyed by it.
+pub fn column_metadata_json(data: &[u8]) -> Result<HashMap<String, serde_json::Value>> {
+    let schema = infer_wpilog_schema(data)?;
+    let mut metadata = HashMap::with_capacity(schema.num_columns());
+    for column in schema.columns() {
+        let value = serde_json::from_str(&column.metadata)
+            .unwrap_or_else(|_| serde_json::Value::String(column.metadata.clone()));
+        if metadata.insert(column.name.clone(), value).is_some() {
+            return Err(WpilogError::SchemaError(format!(
+                "Column name '{}' is used by more than one entry",
+                column.name
+            )));
+        }
+    }
+    Ok(metadata)
+}
+
+/// Returns the raw text of each struct schema defined in WPILog data, keyed by
+/// struct name (e.g. `Translation2d` for `/.schema/struct:Translation2d`).
+///
+/// Only `structschema` START records and their data records are read; other
+/// records are skipped without being decoded. If a schema is written more than
+/// once, the last text wins. Schemas are returned unparsed, so invalid ones are
+/// included; registering them in a [`StructRegistry`] validates them.
+pub fn struct_schemas(data: &[u8]) -> Result<HashMap<String, String>> {
+    let reader = datalog::DataLogReader::new(data);
+    if !reader.is_valid() {
+        return Err(WpilogError::InvalidFormat(
+            "Invalid WPILog file header".to_string(),
+        ));
+    }
+
+    let mut schema_entries = HashMap::new();
+    let mut schemas = HashMap::new();
+    for record_result in reader.records()? {
+        let record = record_result?;
+        if record.is_start() {
+            let start_data = record.get_start_data()?;
+            if normalize_type_name(&start_data.type_name) == "structschema" {
+                let name = match start_data.name.strip_prefix("/.schema/struct:") {
+                    Some(name) => name.to_string(),
+                    None => start_data.name,
+                };
+                schema_entries.insert(start_data.entry, name);
+            }
+        } else if !record.is_control() {
+            if let Some(name) = schema_entries.get(&record.entry) {
+                schemas.insert(name.clone(), record.get_string());
+            }
+        }
+    }
+    Ok(schemas)
+}
+
+/// Formats a packed WPILog version (e.g. `0x0100`) as `major.minor` (e.g. `"1.0"`).
+pub fn format_wpilog_version(version: u16) -> String {
+    format!("{}.{}", version >> 8, version & 0xff)
+}
+
+/// Reads the START records of a WPILog file into a `WpilogSchema`.
+fn infer_wpilog_schema(data: &[u8]) -> Result<WpilogSchema> {
     use datalog::DataLogReader;
-    use schema::WpilogSchema;
 
     let reader = DataLogReader::new(data);
     if !reader.is_valid() {
@@ -171,13 +894,13 @@ pub fn infer_schema(data: &[u8]) -> Result<polars::prelude::Schema> {
         ));
     }
 
-    let schema = WpilogSchema::infer_from_records(reader.records()?)?;
-    Ok(schema.to_polars_schema())
+    WpilogSchema::infer_from_records(reader.records()?)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_fixtures::{encode_record, encode_start, encode_start_with_metadata};
 
     /// Helper to create a minimal WPILog file for testing
     fn create_test_wpilog() -> Vec<u8> {
@@ -227,6 +950,244 @@ mod tests {
         assert!(col_names.iter().any(|s| s.as_str() == "test"));
     }
 
+    #[test]
+    fn test_entry_tree() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, &create_test_wpilog()).unwrap();
+
+        let tree = WpilogParser::entry_tree(file.path()).unwrap();
+        let node = tree.get("test").unwrap();
+        let info = node.entry.as_ref().unwrap();
+        assert_eq!(info.entry_id, 1);
+        assert_eq!(info.type_name, "double");
+    }
+
+    #[test]
+    fn test_from_file_aligned() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, &create_test_wpilog()).unwrap();
+
+        let df = WpilogParser::from_file_aligned(file.path(), "test").unwrap();
+        let timestamp = df.column("timestamp").unwrap().i64().unwrap();
+        assert_eq!(timestamp.get(0), Some(0));
+    }
+
+    #[test]
+    fn test_to_ipc_bytes() {
+        use polars::prelude::{IpcReader, SerReader};
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, &create_test_wpilog()).unwrap();
+
+        let ipc = WpilogParser::to_ipc_bytes(file.path()).unwrap();
+        let df = IpcReader::new(Cursor::new(ipc)).finish().unwrap();
+
+        assert!(df.equals_missing(&WpilogParser::from_file(file.path()).unwrap()));
+    }
+
+    #[test]
+    fn test_convert_to_parquet_streaming() {
+        use polars::prelude::{ParquetReader, SerReader};
+
+        let mut data = create_test_wpilog();
+        for timestamp in 3..8u8 {
+            data.extend_from_slice(&[0x00, 0x01, 0x08, timestamp]);
+            data.extend_from_slice(&(timestamp as f64).to_le_bytes());
+        }
+        let mut input = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut input, &data).unwrap();
+        let output = tempfile::NamedTempFile::new().unwrap();
+
+        WpilogParser::convert_to_parquet_streaming(input.path(), output.path(), 2).unwrap();
+
+        let file = std::fs::File::open(output.path()).unwrap();
+        let df = ParquetReader::new(file).finish().unwrap();
+        assert_eq!(df.height(), 6);
+        assert!(df.equals_missing(&WpilogParser::from_file(input.path()).unwrap()));
+    }
+
+    #[test]
+    fn test_timestamps() {
+        let data = create_test_wpilog();
+        let df = WpilogParser::from_bytes(data.clone()).unwrap();
+        let expected: Vec<i64> = df
+            .column("timestamp")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(timestamps(&data).unwrap(), expected);
+
+        // Out-of-order and repeated timestamps are sorted and deduplicated
+        let mut data = data;
+        for timestamp in [9u8, 5, 9, 2] {
+            data.extend_from_slice(&[0x00, 0x01, 0x08, timestamp]);
+            data.extend_from_slice(&0f64.to_le_bytes());
+        }
+        assert_eq!(timestamps(&data).unwrap(), [2, 5, 9]);
+
+        assert!(timestamps(b"NOTLOG").is_err());
+    }
+
+    #[test]
+    fn test_entry_byte_ranges() {
+        let mut data = create_test_wpilog();
+        let appended = data.len();
+        for (timestamp, value) in [(7u8, 1.5f64), (9, 2.5)] {
+            data.extend_from_slice(&[0x00, 0x01, 0x08, timestamp]);
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let ranges = entry_byte_ranges(&data).unwrap();
+        assert_eq!(ranges.len(), 1);
+        let records = &ranges[&1];
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].0, 2);
+        assert_eq!(records[1], (7, appended, 12));
+        assert_eq!(records[2], (9, appended + 12, 12));
+
+        // Each range holds one complete record
+        let (_, offset, len) = records[2];
+        assert_eq!(&data[offset + len - 8..offset + len], &2.5f64.to_le_bytes());
+        assert_eq!(records[0].1 + records[0].2, appended);
+
+        assert!(entry_byte_ranges(b"NOTLOG").is_err());
+    }
+
+    #[test]
+    fn test_next_record_boundary() {
+        let mut data = create_test_wpilog();
+        let appended = data.len();
+        for timestamp in [7u8, 9] {
+            data.extend_from_slice(&[0x00, 0x01, 0x08, timestamp]);
+            data.extend_from_slice(&1.5f64.to_le_bytes());
+        }
+
+        assert_eq!(next_record_boundary(&data, appended).unwrap(), appended);
+        assert_eq!(
+            next_record_boundary(&data, appended + 1).unwrap(),
+            appended + 12
+        );
+        assert_eq!(next_record_boundary(&data, data.len()).unwrap(), data.len());
+        assert_eq!(next_record_boundary(&data, usize::MAX).unwrap(), data.len());
+        // Offsets inside the header move to the first record
+        assert_eq!(next_record_boundary(&data, 0).unwrap(), 12);
+
+        // A truncated trailing record is not a boundary
+        data.extend_from_slice(&[0x00, 0x01, 0x08, 11, 0x00]);
+        assert_eq!(
+            next_record_boundary(&data, data.len()).unwrap(),
+            data.len() - 5
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_range_with_schema() {
+        let mut data = create_test_wpilog();
+        for timestamp in 3..=10u8 {
+            data.extend_from_slice(&[0x00, 0x01, 0x08, timestamp]);
+            data.extend_from_slice(&(timestamp as f64).to_le_bytes());
+        }
+        let full = WpilogConverter::from_bytes(&data).unwrap();
+
+        let (schema, registry) = WpilogConverter::infer_schema_and_registry(&data).unwrap();
+        let start = next_record_boundary(&data, 0).unwrap();
+        let middle = next_record_boundary(&data, data.len() / 2).unwrap();
+        let end = next_record_boundary(&data, data.len()).unwrap();
+        let first =
+            WpilogConverter::from_bytes_range_with_schema(&data, start..middle, &schema, &registry)
+                .unwrap();
+        let second =
+            WpilogConverter::from_bytes_range_with_schema(&data, middle..end, &schema, &registry)
+                .unwrap();
+
+        assert!(first.height() > 0 && second.height() > 0);
+        assert_eq!(first.schema(), second.schema());
+        assert!(first.vstack(&second).unwrap().equals_missing(&full));
+
+        // Ranges must lie within the records
+        for range in [0..end, middle..start, start..data.len() + 1] {
+            assert!(WpilogConverter::from_bytes_range_with_schema(
+                &data, range, &schema, &registry
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_column_metadata_json() {
+        let mut data = create_test_wpilog();
+        for (entry, name, metadata) in [(2u32, "speed", r#"{"unit":"m/s"}"#), (3, "mode", "auto")] {
+            data.extend(encode_start_with_metadata(entry, name, "double", metadata));
+        }
+
+        let metadata = column_metadata_json(&data).unwrap();
+        assert_eq!(metadata.len(), 3);
+        assert_eq!(metadata["speed"]["unit"], "m/s");
+        // Non-JSON and empty metadata fall back to strings
+        assert_eq!(
+            metadata["mode"],
+            serde_json::Value::String("auto".to_string())
+        );
+        assert_eq!(metadata["test"], serde_json::Value::String(String::new()));
+
+        assert!(column_metadata_json(b"NOTLOG").is_err());
+
+        data.extend(encode_start_with_metadata(
+            4,
+            "speed",
+            "double",
+            r#"{"unit":"ft/s"}"#,
+        ));
+        assert!(matches!(
+            column_metadata_json(&data),
+            Err(WpilogError::SchemaError(_))
+        ));
+    }
+
+    #[test]
+    fn test_struct_schemas() {
+        let mut data = create_test_wpilog();
+        data.extend(encode_start(
+            2,
+            "/.schema/struct:Translation2d",
+            "structschema",
+        ));
+        data.extend(encode_record(2, 1, b"double x;double y"));
+
+        let schemas = struct_schemas(&data).unwrap();
+        assert_eq!(schemas.len(), 1);
+        assert_eq!(schemas["Translation2d"], "double x;double y");
+
+        let mut registry = StructRegistry::new();
+        registry
+            .register("Translation2d".to_string(), &schemas["Translation2d"])
+            .unwrap();
+        assert!(registry.get("Translation2d").is_some());
+
+        assert!(struct_schemas(&create_test_wpilog()).unwrap().is_empty());
+        assert!(struct_schemas(b"NOTLOG").is_err());
+    }
+
+    #[test]
+    fn test_wpilog_version() {
+        let data = create_test_wpilog();
+        assert_eq!(wpilog_version(&data), Some(0x0100));
+        assert_eq!(format_wpilog_version(0x0100), "1.0");
+        assert_eq!(format_wpilog_version(0x020a), "2.10");
+
+        assert_eq!(wpilog_version(b"WPILOG"), None);
+        assert_eq!(wpilog_version(b"NOTLOG\x00\x01\x00\x00\x00\x00"), None);
+    }
+
+    #[test]
+    fn test_log_flavor() {
+        let data = create_test_wpilog();
+        assert_eq!(log_flavor(&data), Some(LogFlavor::WpiLib));
+        assert_eq!(log_flavor(b"NOTLOG\x00\x01\x00\x00\x00\x00"), None);
+    }
+
     #[test]
     fn test_infer_schema() {
         let data = create_test_wpilog();
@@ -1,9 +1,13 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand, ValueEnum};
 use polars::prelude::*;
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
-use wpilog_polars::WpilogParser;
+use wpilog_polars::{
+    format_wpilog_version, log_flavor, wpilog_version, LogFlavor, ParseOptions, TimestampKind,
+    WpilogParser,
+};
 
 /// High-performance WPILog to Polars DataFrame converter
 #[derive(Parser)]
@@ -34,6 +38,10 @@ enum Commands {
         #[arg(short, long, value_delimiter = ',')]
         columns: Option<Vec<String>>,
 
+        /// Columns to select by shell-style pattern, e.g. "/Drive/Module?/*" (comma-separated)
+        #[arg(long, value_delimiter = ',', conflicts_with = "columns")]
+        columns_glob: Option<Vec<String>>,
+
         /// Display only first N rows (default: all)
         #[arg(short = 'n', long)]
         head: Option<usize>,
@@ -45,6 +53,22 @@ enum Commands {
         /// Filter rows where column matches value (format: column=value)
         #[arg(long, value_name = "FILTER")]
         filter: Option<Vec<String>>,
+
+        /// How to write NaN/Inf float values in CSV output (default: Polars' formatting)
+        #[arg(long, value_enum)]
+        nan_repr: Option<NanRepr>,
+
+        /// Omit columns for entries that were declared but never written
+        #[arg(long)]
+        drop_empty_columns: bool,
+
+        /// Add a <column>__present column marking the rows where each entry was logged
+        #[arg(long)]
+        presence_columns: bool,
+
+        /// Keep only columns of these WPILog types, e.g. "double,int64,struct" (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        only_types: Option<Vec<String>>,
     },
 
     /// Show schema information for a WPILog file
@@ -56,6 +80,32 @@ enum Commands {
         /// Show detailed type information
         #[arg(short, long)]
         verbose: bool,
+
+        /// Write the schema and struct layouts as JSON to this file instead of printing them
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Check that struct columns have resolvable schemas without parsing data
+    Validate {
+        /// Input WPILog file path
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+    },
+
+    /// Show entries added, removed, or retyped between two WPILog files
+    Diff {
+        /// Older WPILog file path
+        #[arg(value_name = "OLD")]
+        old: PathBuf,
+
+        /// Newer WPILog file path
+        #[arg(value_name = "NEW")]
+        new: PathBuf,
+
+        /// Print the differences as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Show general information about a WPILog file
@@ -65,13 +115,24 @@ enum Commands {
         input: PathBuf,
     },
 
+    /// Show per-column summary statistics for a WPILog file
+    Stats {
+        /// Input WPILog file path
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+
+        /// Columns to summarize (comma-separated, default: all)
+        #[arg(short, long, value_delimiter = ',')]
+        columns: Option<Vec<String>>,
+    },
+
     /// Convert WPILog to another format
     Convert {
         /// Input WPILog file path
         #[arg(value_name = "INPUT")]
         input: PathBuf,
 
-        /// Output file path
+        /// Output file path (or directory when using --split)
         #[arg(value_name = "OUTPUT")]
         output: PathBuf,
 
@@ -82,6 +143,34 @@ enum Commands {
         /// Compression for Parquet files
         #[arg(long, value_enum, default_value = "snappy")]
         compression: Compression,
+
+        /// Write one file per column (timestamp + column, nulls dropped) into OUTPUT directory
+        #[arg(long)]
+        split: bool,
+
+        /// Columns to convert by shell-style pattern, e.g. "/Drive/Module?/*" (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        columns_glob: Option<Vec<String>>,
+
+        /// How to write NaN/Inf float values in CSV output (default: Polars' formatting)
+        #[arg(long, value_enum)]
+        nan_repr: Option<NanRepr>,
+
+        /// Omit columns for entries that were declared but never written
+        #[arg(long)]
+        drop_empty_columns: bool,
+
+        /// Add a <column>__present column marking the rows where each entry was logged
+        #[arg(long, conflicts_with = "split")]
+        presence_columns: bool,
+
+        /// Write Parquet N rows at a time instead of holding the whole log in memory
+        #[arg(long, value_name = "N", conflicts_with_all = ["split", "columns_glob"])]
+        chunk_rows: Option<usize>,
+
+        /// Keep only columns of these WPILog types, e.g. "double,int64,struct" (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        only_types: Option<Vec<String>>,
     },
 }
 
@@ -101,6 +190,16 @@ enum ConvertFormat {
     Parquet,
 }
 
+#[derive(Debug, Clone, ValueEnum)]
+enum NanRepr {
+    /// Replace non-finite values with null
+    Null,
+    /// Write non-finite values as the strings "NaN", "inf", and "-inf"
+    String,
+    /// Write non-finite values as empty strings
+    Empty,
+}
+
 #[derive(Debug, Clone, ValueEnum)]
 enum Compression {
     Uncompressed,
@@ -129,40 +228,100 @@ fn main() -> Result<()> {
             format,
             output,
             columns,
+            columns_glob,
+            head,
+            tail,
+            filter,
+            nan_repr,
+            drop_empty_columns,
+            presence_columns,
+            only_types,
+        } => parse_command(
+            input,
+            format,
+            output,
+            columns,
+            columns_glob,
             head,
             tail,
             filter,
-        } => parse_command(input, format, output, columns, head, tail, filter)?,
+            nan_repr,
+            drop_empty_columns,
+            presence_columns,
+            only_types,
+        )?,
+
+        Commands::Schema {
+            input,
+            verbose,
+            output,
+        } => schema_command(input, verbose, output)?,
 
-        Commands::Schema { input, verbose } => schema_command(input, verbose)?,
+        Commands::Validate { input } => validate_command(input)?,
+
+        Commands::Diff { old, new, json } => diff_command(old, new, json)?,
 
         Commands::Info { input } => info_command(input)?,
 
+        Commands::Stats { input, columns } => stats_command(input, columns)?,
+
         Commands::Convert {
             input,
             output,
             format,
             compression,
-        } => convert_command(input, output, format, compression)?,
+            split,
+            columns_glob,
+            nan_repr,
+            drop_empty_columns,
+            presence_columns,
+            chunk_rows,
+            only_types,
+        } => convert_command(
+            input,
+            output,
+            format,
+            compression,
+            split,
+            columns_glob,
+            nan_repr,
+            drop_empty_columns,
+            presence_columns,
+            chunk_rows,
+            only_types,
+        )?,
     }
 
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn parse_command(
     input: PathBuf,
     format: OutputFormat,
     output: Option<PathBuf>,
     columns: Option<Vec<String>>,
+    columns_glob: Option<Vec<String>>,
     head: Option<usize>,
     tail: Option<usize>,
     filter: Option<Vec<String>>,
+    nan_repr: Option<NanRepr>,
+    drop_empty_columns: bool,
+    presence_columns: bool,
+    only_types: Option<Vec<String>>,
 ) -> Result<()> {
     // Parse the WPILog file
     println!("Parsing {}...", input.display());
     let parse_start = Instant::now();
-    let mut df = WpilogParser::from_file(&input)
-        .with_context(|| format!("Failed to parse WPILog file: {}", input.display()))?;
+    let options = ParseOptions {
+        drop_empty_columns,
+        presence_columns,
+        only_types,
+        ..Default::default()
+    };
+    let mut df = WpilogParser::from_file_with_options(&input, &options)
+        .with_context(|| format!("Failed to parse WPILog file: {}", input.display()))?
+        .dataframe;
     let parse_duration = parse_start.elapsed();
 
     println!("Loaded {} rows and {} columns ({:.3}s)", df.height(), df.width(), parse_duration.as_secs_f64());
@@ -178,6 +337,11 @@ fn parse_command(
         println!("Selected {} columns", df.width());
     }
 
+    if let Some(patterns) = columns_glob {
+        df = select_columns_glob(df, &patterns)?;
+        println!("Selected {} columns", df.width());
+    }
+
     // Apply filters
     if let Some(filters) = filter {
         for filter_expr in filters {
@@ -213,6 +377,9 @@ fn parse_command(
         }
         OutputFormat::Csv => {
             let output_path = output.context("Output path required for CSV format")?;
+            if let Some(repr) = &nan_repr {
+                replace_non_finite(&mut df, repr)?;
+            }
             let mut file = std::fs::File::create(&output_path)?;
             CsvWriter::new(&mut file).finish(&mut df)?;
             println!("Exported to CSV: {}", output_path.display());
@@ -250,37 +417,45 @@ fn apply_filter(df: DataFrame, col_name: &str, value: &str) -> Result<DataFrame>
     Ok(result)
 }
 
-fn schema_command(input: PathBuf, verbose: bool) -> Result<()> {
-    use wpilog_polars::datalog::DataLogReader;
-    use wpilog_polars::schema::WpilogSchema;
+fn schema_command(input: PathBuf, verbose: bool, output: Option<PathBuf>) -> Result<()> {
+    use wpilog_polars::converter::WpilogConverter;
+    use wpilog_polars::types::PolarsDataType;
 
     println!("Reading schema from {}...", input.display());
 
     let data = std::fs::read(&input)?;
-    let reader = DataLogReader::new(&data);
-    let records = reader.records()?;
 
-    // Infer WpilogSchema (not Polars schema) to get original type info
-    let wpilog_schema = WpilogSchema::infer_from_records(records)
+    // Infer WpilogSchema (not Polars schema) to get original type info, along with
+    // the struct definitions needed to show struct layouts
+    let (wpilog_schema, registry) = WpilogConverter::infer_schema_and_registry(&data)
         .with_context(|| format!("Failed to infer schema from: {}", input.display()))?;
 
+    if let Some(output) = output {
+        let json = serde_json::to_string_pretty(&wpilog_schema.to_document(&registry))?;
+        std::fs::write(&output, json)
+            .with_context(|| format!("Failed to write schema to: {}", output.display()))?;
+        println!("Wrote schema to {}", output.display());
+        return Ok(());
+    }
+
     println!("\nSchema for {}:", input.display());
     println!("{} columns found\n", wpilog_schema.num_columns() + 1); // +1 for timestamp
 
     // Always show timestamp first
-    println!("  {:30} {}", "timestamp", "Int64");
+    println!("  {:30} Int64", "timestamp");
 
     if verbose {
         for col in wpilog_schema.columns() {
-            println!("  {:30} {:?}", col.name, col.dtype);
+            println!("  {:30} {:?} (entry {})", col.name, col.dtype, col.entry_id);
+            if let PolarsDataType::Struct(name) | PolarsDataType::StructArray(name) = &col.dtype {
+                print_struct_layout(&registry, name);
+            }
         }
     } else {
         for col in wpilog_schema.columns() {
             // Format the type nicely, showing struct names
             let type_str = match &col.dtype {
-                wpilog_polars::types::PolarsDataType::Struct(name) => {
-                    format!("Struct({})", name)
-                }
+                PolarsDataType::Struct(name) => format!("Struct({})", name),
                 other => format!("{:?}", other.to_polars_dtype()),
             };
             println!("  {:30} {}", col.name, type_str);
@@ -290,6 +465,143 @@ fn schema_command(input: PathBuf, verbose: bool) -> Result<()> {
     Ok(())
 }
 
+/// Prints the fields of a registered struct with their byte offsets and sizes.
+fn print_struct_layout(registry: &wpilog_polars::StructRegistry, name: &str) {
+    use wpilog_polars::struct_support::StructField;
+
+    let Some(schema) = registry.get(name) else {
+        println!("      (struct '{}' is not registered)", name);
+        return;
+    };
+
+    for field in &schema.fields {
+        match field {
+            StructField::Standard(f) => {
+                let type_str = f.field_type.to_string();
+                println!(
+                    "      {:26} {:16} offset {:>4}  size {}",
+                    f.name, type_str, f.offset, f.size
+                );
+            }
+            StructField::BitField(b) => {
+                let type_str = format!("{}:{}", b.int_type, b.bit_width);
+                println!(
+                    "      {:26} {:16} offset {:>4}  size {}  bits {}..{}",
+                    b.name,
+                    type_str,
+                    b.storage_offset,
+                    b.int_type.size(),
+                    b.bit_offset,
+                    b.bit_offset + b.bit_width
+                );
+            }
+        }
+    }
+    if schema.is_variable_size() {
+        println!("      ({} bytes with empty arrays)", schema.total_size);
+    } else {
+        println!("      ({} bytes)", schema.total_size);
+    }
+}
+
+fn validate_command(input: PathBuf) -> Result<()> {
+    println!("Validating {}...\n", input.display());
+
+    let report = WpilogParser::validate_file(&input)
+        .with_context(|| format!("Failed to validate WPILog file: {}", input.display()))?;
+
+    println!("  Columns:             {}", report.columns);
+    println!("  Struct columns:      {}", report.struct_columns);
+    println!(
+        "  Registered structs:  {} {:?}",
+        report.registered_structs.len(),
+        report.registered_structs
+    );
+    println!("  Unresolved structs:  {}", report.unresolved_structs.len());
+    for unresolved in &report.unresolved_structs {
+        println!(
+            "    {} (missing: {})",
+            unresolved.name,
+            unresolved.missing_dependencies.join(", ")
+        );
+    }
+    println!("  Duplicate names:     {}", report.duplicate_names.len());
+    for name in &report.duplicate_names {
+        println!("    {}", name);
+    }
+    println!("  Size mismatches:     {}", report.size_mismatches.len());
+    for mismatch in report.size_mismatches.iter().take(10) {
+        println!(
+            "    {} ({}) at {}: {} bytes, expected {}",
+            mismatch.name,
+            mismatch.type_name,
+            mismatch.timestamp,
+            mismatch.actual,
+            mismatch.expected
+        );
+    }
+    if report.size_mismatches.len() > 10 {
+        println!("    ... and {} more", report.size_mismatches.len() - 10);
+    }
+
+    if !report.is_valid() {
+        anyhow::bail!(
+            "{} unresolved struct(s) and {} size mismatch(es) in {}",
+            report.unresolved_structs.len(),
+            report.size_mismatches.len(),
+            input.display()
+        );
+    }
+
+    println!("\nAll struct schemas resolved and record sizes match");
+    Ok(())
+}
+
+fn diff_command(old: PathBuf, new: PathBuf, json: bool) -> Result<()> {
+    use wpilog_polars::converter::WpilogConverter;
+
+    let read_schema = |path: &Path| {
+        let data = std::fs::read(path)?;
+        let (schema, _) = WpilogConverter::infer_schema_and_registry(&data)
+            .with_context(|| format!("Failed to infer schema from: {}", path.display()))?;
+        Ok::<_, anyhow::Error>(schema)
+    };
+    let diff = read_schema(&old)?.diff(&read_schema(&new)?);
+
+    if json {
+        println!("{}", diff_to_json(&diff)?);
+        return Ok(());
+    }
+
+    println!(
+        "Schema changes from {} to {}:\n",
+        old.display(),
+        new.display()
+    );
+    for name in &diff.added {
+        println!("  + {}", name);
+    }
+    for name in &diff.removed {
+        println!("  - {}", name);
+    }
+    for change in &diff.retyped {
+        println!(
+            "  ~ {} ({} -> {})",
+            change.name, change.old_type, change.new_type
+        );
+    }
+    if diff.is_empty() {
+        println!("  No changes");
+    }
+
+    Ok(())
+}
+
+/// Formats a schema diff as a single-line JSON object.
+fn diff_to_json(diff: &wpilog_polars::SchemaDiff) -> Result<String> {
+    Ok(serde_json::to_string(diff)?)
+}
+
 fn info_command(input: PathBuf) -> Result<()> {
     println!("Analyzing {}...\n", input.display());
 
@@ -297,9 +609,14 @@ fn info_command(input: PathBuf) -> Result<()> {
     let metadata = std::fs::metadata(&input)?;
     let file_size = metadata.len();
 
-    // Parse the file
-    let df = WpilogParser::from_file(&input)
-        .with_context(|| format!("Failed to parse WPILog file: {}", input.display()))?;
+    // Parse the file, keeping timestamps unsigned so every valid log can be summarized
+    let options = ParseOptions {
+        timestamp_kind: TimestampKind::UInt64,
+        ..Default::default()
+    };
+    let df = WpilogParser::from_file_with_options(&input, &options)
+        .with_context(|| format!("Failed to parse WPILog file: {}", input.display()))?
+        .dataframe;
 
     println!("File Information:");
     println!("  Path:           {}", input.display());
@@ -308,13 +625,23 @@ fn info_command(input: PathBuf) -> Result<()> {
         file_size,
         file_size as f64 / 1_048_576.0
     );
+    match read_wpilog_version(&input) {
+        Some(version) => println!("  Version:        {}", format_wpilog_version(version)),
+        None => println!("  Version:        unknown"),
+    }
+    match read_log_flavor(&input) {
+        Some(LogFlavor::AdvantageKit) => println!("  Written by:     AdvantageKit"),
+        Some(LogFlavor::WpiLib) => println!("  Written by:     WPILib DataLog"),
+        Some(LogFlavor::Other(header)) => println!("  Extra header:   {}", header),
+        None => {}
+    }
     println!("\nData Information:");
     println!("  Rows:           {}", df.height());
     println!("  Columns:        {}", df.width());
 
     // Get timestamp range
     if let Ok(ts_col) = df.column("timestamp") {
-        if let Ok(ts) = ts_col.i64() {
+        if let Ok(ts) = ts_col.u64() {
             if let (Some(min), Some(max)) = (ts.min(), ts.max()) {
                 let duration_us = max - min;
                 let duration_s = duration_us as f64 / 1_000_000.0;
@@ -339,7 +666,11 @@ fn info_command(input: PathBuf) -> Result<()> {
     for name in df.get_column_names() {
         if let Ok(col) = df.column(name.as_str()) {
             let null_count = col.is_null().sum().unwrap_or(0);
-            let null_pct = (null_count as f64 / df.height() as f64) * 100.0;
+            let null_pct = if df.height() > 0 {
+                (null_count as f64 / df.height() as f64) * 100.0
+            } else {
+                0.0
+            };
             println!(
                 "  {:30} {:15?} (nulls: {:.1}%)",
                 name,
@@ -352,11 +683,109 @@ fn info_command(input: PathBuf) -> Result<()> {
     Ok(())
 }
 
+/// Reads the format version from the file header without loading the whole file.
+fn read_wpilog_version(path: &Path) -> Option<u16> {
+    let mut header = [0u8; 12];
+    std::fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .ok()?;
+    wpilog_version(&header)
+}
+
+/// Reads the header and extra header of a WPILog file to detect its flavor.
+fn read_log_flavor(path: &Path) -> Option<LogFlavor> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut header = vec![0u8; 12];
+    file.read_exact(&mut header).ok()?;
+    let extra_len = u32::from_le_bytes(header[8..12].try_into().ok()?) as usize;
+    if 12 + extra_len as u64 > file.metadata().ok()?.len() {
+        return None;
+    }
+    header.resize(12 + extra_len, 0);
+    file.read_exact(&mut header[12..]).ok()?;
+    log_flavor(&header)
+}
+
+fn stats_command(input: PathBuf, columns: Option<Vec<String>>) -> Result<()> {
+    println!("Analyzing {}...\n", input.display());
+
+    let mut df = WpilogParser::from_file(&input)
+        .with_context(|| format!("Failed to parse WPILog file: {}", input.display()))?;
+
+    if let Some(cols) = columns {
+        df = df.select(&cols)?;
+    }
+
+    let height = df.height();
+    println!("Statistics ({} rows):", height);
+
+    for column in df.get_columns() {
+        let series = column.as_materialized_series();
+        let name = series.name().as_str();
+        let null_pct = if height > 0 {
+            (series.null_count() as f64 / height as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        match series.dtype() {
+            DataType::Float64
+            | DataType::Float32
+            | DataType::Int64
+            | DataType::Int32
+            | DataType::Int16
+            | DataType::Int8
+            | DataType::UInt64
+            | DataType::UInt32
+            | DataType::UInt16
+            | DataType::UInt8 => {
+                let values = series.cast(&DataType::Float64)?;
+                let values = values.f64()?;
+                println!(
+                    "  {:30} min={} max={} mean={} std={} (nulls: {:.1}%)",
+                    name,
+                    format_stat(values.min()),
+                    format_stat(values.max()),
+                    format_stat(values.mean()),
+                    format_stat(values.std(1)),
+                    null_pct
+                );
+            }
+            DataType::String | DataType::Boolean => {
+                let distinct = series.drop_nulls().n_unique()?;
+                println!(
+                    "  {:30} distinct={} (nulls: {:.1}%)",
+                    name, distinct, null_pct
+                );
+            }
+            other => {
+                println!("  {:30} {:15?} (nulls: {:.1}%)", name, other, null_pct);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn format_stat(value: Option<f64>) -> String {
+    value
+        .map(|v| format!("{:.4}", v))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+#[allow(clippy::too_many_arguments)]
 fn convert_command(
     input: PathBuf,
     output: PathBuf,
     format: Option<ConvertFormat>,
     compression: Compression,
+    split: bool,
+    columns_glob: Option<Vec<String>>,
+    nan_repr: Option<NanRepr>,
+    drop_empty_columns: bool,
+    presence_columns: bool,
+    chunk_rows: Option<usize>,
+    only_types: Option<Vec<String>>,
 ) -> Result<()> {
     println!("Converting {} to {}...", input.display(), output.display());
 
@@ -374,31 +803,431 @@ fn convert_command(
         }
     };
 
+    if let Some(chunk_rows) = chunk_rows {
+        if !matches!(out_format, ConvertFormat::Parquet) {
+            anyhow::bail!("--chunk-rows is only supported for Parquet output");
+        }
+        let convert_start = Instant::now();
+        let mut builder = WpilogParser::builder()
+            .drop_empty_columns(drop_empty_columns)
+            .presence_columns(presence_columns);
+        if let Some(types) = only_types {
+            builder = builder.only_types(types);
+        }
+        builder
+            .write_parquet_streaming(
+                &input,
+                &output,
+                chunk_rows,
+                to_parquet_compression(compression),
+            )
+            .with_context(|| format!("Failed to convert WPILog file: {}", input.display()))?;
+        println!(
+            "Successfully converted to Parquet: {} ({:.3}s)",
+            output.display(),
+            convert_start.elapsed().as_secs_f64()
+        );
+        return Ok(());
+    }
+
     // Parse the WPILog file
     let parse_start = Instant::now();
-    let mut df = WpilogParser::from_file(&input)
-        .with_context(|| format!("Failed to parse WPILog file: {}", input.display()))?;
+    let options = ParseOptions {
+        drop_empty_columns,
+        presence_columns,
+        only_types,
+        ..Default::default()
+    };
+    let mut df = WpilogParser::from_file_with_options(&input, &options)
+        .with_context(|| format!("Failed to parse WPILog file: {}", input.display()))?
+        .dataframe;
     let parse_duration = parse_start.elapsed();
 
     println!("Loaded {} rows and {} columns ({:.3}s)", df.height(), df.width(), parse_duration.as_secs_f64());
 
+    if let Some(patterns) = columns_glob {
+        df = select_columns_glob(df, &patterns)?;
+        println!("Selected {} columns", df.width());
+    }
+
+    if let (ConvertFormat::Csv, Some(repr)) = (&out_format, &nan_repr) {
+        replace_non_finite(&mut df, repr)?;
+    }
+
+    if split {
+        return split_columns(&df, &output, &out_format, &compression);
+    }
+
     // Write to output format
+    let save_start = Instant::now();
+    write_output(&mut df, &output, &out_format, &compression)?;
+    let save_duration = save_start.elapsed();
     match out_format {
+        ConvertFormat::Csv => println!("Successfully converted to CSV: {}", output.display()),
+        ConvertFormat::Parquet => println!(
+            "Successfully converted to Parquet: {} ({:.3}s)",
+            output.display(),
+            save_duration.as_secs_f64()
+        ),
+    }
+
+    Ok(())
+}
+
+/// Writes one file per column into `output_dir`, each containing the timestamp and the
+/// column's non-null values.
+///
+/// Entries whose names sanitize to the same file name get a numeric suffix, so no
+/// file overwrites another.
+fn split_columns(
+    df: &DataFrame,
+    output_dir: &Path,
+    format: &ConvertFormat,
+    compression: &Compression,
+) -> Result<()> {
+    std::fs::create_dir_all(output_dir)?;
+
+    let extension = match format {
+        ConvertFormat::Csv => "csv",
+        ConvertFormat::Parquet => "parquet",
+    };
+
+    let mut used_names = std::collections::HashSet::new();
+    let mut written = 0;
+    for column in df.get_columns() {
+        let name = column.name().as_str();
+        if name == "timestamp" || column.null_count() == column.len() {
+            continue;
+        }
+
+        // CSV cannot represent nested values
+        if matches!(format, ConvertFormat::Csv)
+            && matches!(column.dtype(), DataType::List(_) | DataType::Struct(_))
+        {
+            eprintln!(
+                "Warning: Skipping column '{}' ({:?} is not supported in CSV)",
+                name,
+                column.dtype()
+            );
+            continue;
+        }
+
+        let mut column_df = df
+            .clone()
+            .lazy()
+            .select([col("timestamp"), col(name)])
+            .filter(col(name).is_not_null())
+            .collect()?;
+
+        let base_name = sanitize_file_name(name);
+        let mut file_name = base_name.clone();
+        let mut suffix = 2;
+        while !used_names.insert(file_name.to_ascii_lowercase()) {
+            file_name = format!("{}_{}", base_name, suffix);
+            suffix += 1;
+        }
+
+        let path = output_dir.join(format!("{}.{}", file_name, extension));
+        write_output(&mut column_df, &path, format, compression)?;
+        written += 1;
+    }
+
+    println!(
+        "Successfully wrote {} files to {}",
+        written,
+        output_dir.display()
+    );
+
+    Ok(())
+}
+
+/// Writes a DataFrame to a single CSV or Parquet file.
+fn write_output(
+    df: &mut DataFrame,
+    path: &Path,
+    format: &ConvertFormat,
+    compression: &Compression,
+) -> Result<()> {
+    match format {
         ConvertFormat::Csv => {
-            let mut file = std::fs::File::create(&output)?;
-            CsvWriter::new(&mut file).finish(&mut df)?;
-            println!("Successfully converted to CSV: {}", output.display());
+            let mut file = std::fs::File::create(path)?;
+            CsvWriter::new(&mut file).finish(df)?;
         }
         ConvertFormat::Parquet => {
-            let file = std::fs::File::create(&output)?;
-            let save_start = Instant::now();
+            let file = std::fs::File::create(path)?;
             ParquetWriter::new(file)
-                .with_compression(to_parquet_compression(compression))
-                .finish(&mut df)?;
-            let save_duration = save_start.elapsed();
-            println!("Successfully converted to Parquet: {} ({:.3}s)", output.display(), save_duration.as_secs_f64());
+                .with_compression(to_parquet_compression(compression.clone()))
+                .finish(df)?;
         }
     }
 
     Ok(())
 }
+
+/// Selects the timestamp plus every column whose name matches one of `patterns`.
+///
+/// Columns keep their original order. Fails if any pattern matches no columns, which
+/// usually means a typo.
+fn select_columns_glob(df: DataFrame, patterns: &[String]) -> Result<DataFrame> {
+    let names: Vec<String> = df
+        .get_column_names()
+        .iter()
+        .map(|n| n.to_string())
+        .filter(|n| n != "timestamp")
+        .collect();
+
+    for pattern in patterns {
+        if !names.iter().any(|n| glob_match(pattern, n)) {
+            anyhow::bail!("Column pattern '{}' matched no columns", pattern);
+        }
+    }
+
+    let mut selected = vec!["timestamp".to_string()];
+    selected.extend(
+        names
+            .into_iter()
+            .filter(|n| patterns.iter().any(|p| glob_match(p, n))),
+    );
+    Ok(df.select(selected)?)
+}
+
+/// Matches `name` against a shell-style pattern where `*` matches any run of
+/// characters (including `/`) and `?` matches exactly one character.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and the name position it is currently matched up to
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            // Let the last `*` absorb one more character and retry
+            star = Some((star_p, star_n + 1));
+            p = star_p + 1;
+            n = star_n + 1;
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Rewrites NaN and infinite values in float columns according to `repr`.
+///
+/// `Null` keeps the column's float type; `String` and `Empty` turn the column into
+/// strings so the replacement text is written verbatim. Existing nulls stay null.
+fn replace_non_finite(df: &mut DataFrame, repr: &NanRepr) -> Result<()> {
+    let float_columns: Vec<String> = df
+        .get_columns()
+        .iter()
+        .filter(|c| matches!(c.dtype(), DataType::Float32 | DataType::Float64))
+        .map(|c| c.name().to_string())
+        .collect();
+
+    for name in float_columns {
+        let column = df.column(&name)?;
+        let dtype = column.dtype().clone();
+        let values = column.cast(&DataType::Float64)?;
+        let values = values.f64()?;
+
+        let replaced = match repr {
+            NanRepr::Null => {
+                let finite: Vec<Option<f64>> = values
+                    .into_iter()
+                    .map(|v| v.filter(|x| x.is_finite()))
+                    .collect();
+                Series::new(name.as_str().into(), finite).cast(&dtype)?
+            }
+            NanRepr::String | NanRepr::Empty => {
+                // Format f32 values as f32 so they print without widening artifacts
+                let text: Vec<Option<String>> = match dtype {
+                    DataType::Float32 => column
+                        .f32()?
+                        .into_iter()
+                        .map(|v| v.map(|x| format_float(x, repr)))
+                        .collect(),
+                    _ => values
+                        .into_iter()
+                        .map(|v| v.map(|x| format_float(x, repr)))
+                        .collect(),
+                };
+                Series::new(name.as_str().into(), text)
+            }
+        };
+
+        df.replace(&name, replaced)?;
+    }
+
+    Ok(())
+}
+
+/// Renders one float for CSV output. Finite values use their `Display` form; NaN and
+/// infinities follow `repr`.
+fn format_float<T: Into<f64> + std::fmt::Display + Copy>(value: T, repr: &NanRepr) -> String {
+    let wide: f64 = value.into();
+    if wide.is_finite() {
+        value.to_string()
+    } else if matches!(repr, NanRepr::Empty) {
+        String::new()
+    } else if wide.is_nan() {
+        "NaN".to_string()
+    } else if wide > 0.0 {
+        "inf".to_string()
+    } else {
+        "-inf".to_string()
+    }
+}
+
+/// Converts an entry name like "/Robot/Drive/Speed" into a file name like "Robot_Drive_Speed".
+fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let trimmed = sanitized.trim_matches('_');
+
+    if trimmed.is_empty() {
+        "column".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_to_json() {
+        let diff = wpilog_polars::SchemaDiff {
+            added: vec!["/Drive/\"Speed\"".to_string()],
+            removed: vec![],
+            retyped: vec![wpilog_polars::TypeChange {
+                name: "mode".to_string(),
+                old_type: "int64".to_string(),
+                new_type: "string".to_string(),
+            }],
+        };
+
+        assert_eq!(
+            diff_to_json(&diff).unwrap(),
+            r#"{"added":["/Drive/\"Speed\""],"removed":[],"retyped":[{"name":"mode","old_type":"int64","new_type":"string"}]}"#
+        );
+    }
+
+    #[test]
+    fn test_split_columns_disambiguates_file_names() {
+        let df = df!(
+            "timestamp" => [1i64, 2],
+            "/Drive/Speed" => [Some(1.0), None],
+            "Drive_Speed" => [Some(2.0), Some(3.0)],
+            "/Arm/Angle" => [None::<f64>, None],
+        )
+        .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        split_columns(&df, dir.path(), &ConvertFormat::Csv, &Compression::Snappy).unwrap();
+
+        let mut files: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        assert_eq!(files, vec!["Drive_Speed.csv", "Drive_Speed_2.csv"]);
+
+        let first = std::fs::read_to_string(dir.path().join("Drive_Speed.csv")).unwrap();
+        assert_eq!(first.lines().count(), 2);
+        let second = std::fs::read_to_string(dir.path().join("Drive_Speed_2.csv")).unwrap();
+        assert_eq!(second.lines().count(), 3);
+    }
+
+    #[test]
+    fn test_replace_non_finite_as_string() {
+        let mut df = df!(
+            "timestamp" => [1i64, 2, 3],
+            "f32" => [Some(0.1f32), Some(f32::NAN), None],
+            "f64" => [Some(2.5f64), Some(f64::NEG_INFINITY), Some(3.0)],
+        )
+        .unwrap();
+
+        replace_non_finite(&mut df, &NanRepr::String).unwrap();
+
+        let f32_text: Vec<Option<&str>> = df
+            .column("f32")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(f32_text, vec![Some("0.1"), Some("NaN"), None]);
+        let f64_text: Vec<Option<&str>> = df
+            .column("f64")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(f64_text, vec![Some("2.5"), Some("-inf"), Some("3")]);
+    }
+
+    #[test]
+    fn test_glob_match_star() {
+        assert!(glob_match("/Drive/*", "/Drive/Speed"));
+        assert!(glob_match("/Drive/*", "/Drive/Module0/Angle"));
+        assert!(glob_match("*Angle", "/Drive/Module0/Angle"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("/Drive/*", "/Arm/Speed"));
+    }
+
+    #[test]
+    fn test_glob_match_question_mark() {
+        assert!(glob_match("/Drive/Module?/Angle", "/Drive/Module3/Angle"));
+        assert!(!glob_match("/Drive/Module?/Angle", "/Drive/Module10/Angle"));
+        assert!(!glob_match("/Drive/Module?", "/Drive/Module"));
+    }
+
+    #[test]
+    fn test_glob_match_prefix_and_exact() {
+        assert!(glob_match("/Drive/Module0*", "/Drive/Module0/Speed"));
+        assert!(glob_match("/Drive/Speed", "/Drive/Speed"));
+        assert!(!glob_match("/Drive/Speed", "/Drive/SpeedSetpoint"));
+    }
+
+    #[test]
+    fn test_select_columns_glob() {
+        let df = df!(
+            "timestamp" => [1i64, 2],
+            "/Drive/Module0/Speed" => [1.0, 2.0],
+            "/Drive/Module1/Speed" => [3.0, 4.0],
+            "/Arm/Angle" => [5.0, 6.0],
+        )
+        .unwrap();
+
+        let selected = select_columns_glob(df.clone(), &["/Drive/Module?/*".to_string()]).unwrap();
+        let names: Vec<&str> = selected
+            .get_column_names()
+            .iter()
+            .map(|n| n.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["timestamp", "/Drive/Module0/Speed", "/Drive/Module1/Speed"]
+        );
+
+        assert!(select_columns_glob(df, &["/Drvie/*".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,301 @@
+//! Options controlling how WPILog data is converted to a DataFrame.
+
+use crate::types::{PolarsDataType, PolarsValue};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Decodes protobuf record payloads for [`ParseOptions::protobuf_decoder`].
+///
+/// Each message type name (e.g. the `wpi.proto.ProtobufPose2d` in
+/// `proto:wpi.proto.ProtobufPose2d`) is first mapped to the type of its column, so the
+/// column type never depends on which values happen to be logged. Entries whose
+/// message maps to `None` are kept as strings. Payloads the decoder returns `None`
+/// for, and decoded values of another type, are null.
+#[derive(Clone)]
+pub struct ProtobufDecoder {
+    dtype: Arc<MessageTypeFn>,
+    decode: Arc<DecodeFn>,
+}
+
+type MessageTypeFn = dyn Fn(&str) -> Option<PolarsDataType> + Send + Sync;
+type DecodeFn = dyn Fn(&str, &[u8]) -> Option<PolarsValue> + Send + Sync;
+
+impl ProtobufDecoder {
+    /// Creates a decoder from a function giving each message's column type and a
+    /// function decoding one payload of that message.
+    pub fn new<T, D>(dtype: T, decode: D) -> Self
+    where
+        T: Fn(&str) -> Option<PolarsDataType> + Send + Sync + 'static,
+        D: Fn(&str, &[u8]) -> Option<PolarsValue> + Send + Sync + 'static,
+    {
+        Self {
+            dtype: Arc::new(dtype),
+            decode: Arc::new(decode),
+        }
+    }
+
+    /// Returns the column type for `message_name`, or `None` (or a `Protobuf` type)
+    /// to keep it as strings.
+    pub fn dtype(&self, message_name: &str) -> Option<PolarsDataType> {
+        (self.dtype)(message_name)
+    }
+
+    /// Decodes one payload of `message_name`. A `None` result, or a value that
+    /// isn't of the declared column type, is a decode failure handled by
+    /// [`ParseOptions::on_error`].
+    pub fn decode(&self, message_name: &str, payload: &[u8]) -> Option<PolarsValue> {
+        (self.decode)(message_name, payload)
+    }
+}
+
+/// Receives `(bytes_processed, total_bytes)` as records are read.
+pub type ProgressCallback = Arc<dyn Fn(u64, u64) + Send + Sync>;
+
+/// Options for converting a WPILog file to a DataFrame.
+///
+/// The defaults match the behavior of [`WpilogParser::from_bytes`](crate::WpilogParser::from_bytes).
+#[derive(Clone, Default)]
+pub struct ParseOptions {
+    /// Drop data records that are read before their entry's START record.
+    ///
+    /// The schema is inferred from the whole file before data is accumulated, so by
+    /// default such orphan records are still decoded into their column. Orphans are
+    /// counted in [`Diagnostics::orphan_records`](crate::diagnostics::Diagnostics)
+    /// either way.
+    pub drop_orphan_records: bool,
+
+    /// How `string` entry values are encoded within data records.
+    pub string_encoding: StringEncoding,
+
+    /// Maps entry names to a WPILog type string (e.g. `"double"`) used instead of the
+    /// type declared in the entry's START record.
+    pub type_overrides: HashMap<String, String>,
+
+    /// Build `string` columns as `Categorical` instead of `String`.
+    ///
+    /// Useful for low-cardinality entries such as robot state or mode names.
+    pub categorical_strings: bool,
+
+    /// Decode `boolean[]` payloads as bit-packed (eight booleans per byte, least
+    /// significant bit first) instead of WPILib's one byte per boolean.
+    ///
+    /// Bit-packed arrays don't record their length, so every array has a multiple of
+    /// eight elements and padding bits decode as false.
+    pub boolean_array_bitpacked: bool,
+
+    /// Omit columns for entries that never received a data record.
+    ///
+    /// Such columns are removed from the schema before data is accumulated. They are
+    /// not listed in [`ParsedLog::unwritten_entries`](crate::ParsedLog::unwritten_entries).
+    pub drop_empty_columns: bool,
+
+    /// Custom decoder for `proto:*` entries, which are kept as strings when unset.
+    ///
+    /// The column type of each entry comes from [`ProtobufDecoder::dtype`].
+    pub protobuf_decoder: Option<ProtobufDecoder>,
+
+    /// Called periodically (about every megabyte) while data records are accumulated,
+    /// and once when all records have been read.
+    pub progress: Option<ProgressCallback>,
+
+    /// What to do when a data record's value fails to decode.
+    pub on_error: ErrorPolicy,
+
+    /// Apply `on_error` to control records that are truncated or have an unknown
+    /// control type. When false, such records are skipped with a warning.
+    pub strict_control_records: bool,
+
+    /// Order entry columns alphabetically by name instead of by first START record.
+    ///
+    /// The `timestamp` column always comes first. Sorted columns make it easier to
+    /// diff the schemas of two logs.
+    pub sort_columns: bool,
+
+    /// Decode the largest whole-struct prefix of struct-array records whose length
+    /// isn't a multiple of the struct size, with a warning, instead of failing.
+    ///
+    /// Such records usually mean the log was written with a different version of the
+    /// struct schema.
+    pub truncate_struct_arrays: bool,
+
+    /// Decode `boolean` records longer than 1 byte from their first byte instead of
+    /// failing, as some writers pad booleans.
+    ///
+    /// Each padded entry is reported once in the diagnostics. Empty records are
+    /// still an error.
+    pub lenient_booleans: bool,
+
+    /// How invalid UTF-8 in `string` and `string[]` values is handled.
+    pub utf8_policy: Utf8Policy,
+
+    /// Skip past records whose declared size runs past the end of the file by
+    /// scanning forward for the next plausible record header, instead of ending the
+    /// parse there.
+    ///
+    /// Each skipped range is reported as a warning and counted in
+    /// [`Diagnostics::skipped_bytes`](crate::diagnostics::Diagnostics). An incomplete
+    /// record at the very end of the file is also counted as skipped.
+    pub recover: bool,
+
+    /// Build each `struct` column as one column per leaf field, named with the field
+    /// path joined by dots (e.g. `pose.translation.x`), instead of a `Struct` column.
+    ///
+    /// Struct arrays can't be flattened and stay `List(Struct)` columns. Flattened
+    /// columns have no [`ParsedLog::field_metadata`](crate::ParsedLog::field_metadata).
+    pub flatten_structs: bool,
+
+    /// How entries whose WPILog type is not recognized are decoded, e.g. custom
+    /// types from vendor loggers. Type overrides naming an unknown type are
+    /// treated the same way.
+    pub unknown_type_policy: UnknownTypePolicy,
+
+    /// Follow each entry column with a Boolean `<name>__present` column that is true
+    /// in rows where the entry was logged, so "not updated" can be told apart from a
+    /// null value after export.
+    ///
+    /// This adds one Boolean column per entry. In Parquet these compress to a few
+    /// bytes per row group for entries updated at a steady rate, but can add
+    /// noticeably to CSV output and in-memory size.
+    ///
+    /// A log with an entry named like another entry's presence column (`x__present`
+    /// next to `x`) is a [`WpilogError::SchemaError`](crate::WpilogError::SchemaError).
+    pub presence_columns: bool,
+
+    /// Data type of the `timestamp` column.
+    pub timestamp_kind: TimestampKind,
+
+    /// Keep only the columns whose declared WPILog type matches one of these
+    /// selectors (see [`ColumnInfo::matches_type`](crate::schema::ColumnInfo::matches_type)).
+    /// Other entries are dropped from the schema, so their records are never decoded.
+    /// The `timestamp` column is always kept. `None` keeps every column.
+    pub only_types: Option<Vec<String>>,
+
+    /// Maps entry names to the column names they are built under, e.g.
+    /// `"/RealOutputs/Drive/LeftVelocity"` to `"left_velocity"`.
+    ///
+    /// Other options keyed by entry name, such as `type_overrides`, still use the
+    /// original name. Field metadata follows the new name. Renaming a column onto
+    /// another column's name (or `timestamp`) is a schema error.
+    pub rename: HashMap<String, String>,
+
+    /// How struct fields declared with an enum (e.g. `enum {off=0, on=1} int8 state`)
+    /// are built.
+    pub enum_output: EnumOutput,
+
+    /// Cast every scalar numeric and boolean column (`int64`, `float`, `boolean`, and
+    /// the narrow integers) to `Float64`, with booleans as 0.0 and 1.0.
+    ///
+    /// This gives a homogeneous numeric frame, e.g. for ML feature pipelines. String,
+    /// array, and struct columns, `timestamp`, and presence columns keep their types.
+    pub numeric_as_f64: bool,
+}
+
+impl fmt::Debug for ParseOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParseOptions")
+            .field("drop_orphan_records", &self.drop_orphan_records)
+            .field("string_encoding", &self.string_encoding)
+            .field("type_overrides", &self.type_overrides)
+            .field("categorical_strings", &self.categorical_strings)
+            .field("boolean_array_bitpacked", &self.boolean_array_bitpacked)
+            .field("drop_empty_columns", &self.drop_empty_columns)
+            .field(
+                "protobuf_decoder",
+                &self.protobuf_decoder.as_ref().map(|_| "<fn>"),
+            )
+            .field("progress", &self.progress.as_ref().map(|_| "<fn>"))
+            .field("on_error", &self.on_error)
+            .field("strict_control_records", &self.strict_control_records)
+            .field("sort_columns", &self.sort_columns)
+            .field("truncate_struct_arrays", &self.truncate_struct_arrays)
+            .field("lenient_booleans", &self.lenient_booleans)
+            .field("utf8_policy", &self.utf8_policy)
+            .field("recover", &self.recover)
+            .field("flatten_structs", &self.flatten_structs)
+            .field("unknown_type_policy", &self.unknown_type_policy)
+            .field("presence_columns", &self.presence_columns)
+            .field("timestamp_kind", &self.timestamp_kind)
+            .field("only_types", &self.only_types)
+            .field("rename", &self.rename)
+            .field("enum_output", &self.enum_output)
+            .field("numeric_as_f64", &self.numeric_as_f64)
+            .finish()
+    }
+}
+
+/// Encoding of `string` values within data records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StringEncoding {
+    /// The whole record payload is the string (the WPILog specification).
+    #[default]
+    Raw,
+    /// The payload is a 4-byte little-endian length followed by the string bytes,
+    /// as written by some third-party loggers.
+    LengthPrefixed,
+    /// Strip a length prefix only when it exactly matches the remaining payload size.
+    Auto,
+}
+
+/// How invalid UTF-8 in string values is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Utf8Policy {
+    /// Replace each invalid sequence with U+FFFD, so binary data mislabeled as a
+    /// string still loads.
+    #[default]
+    Lossy,
+    /// Fail to decode the value, which reveals entries that actually hold binary data.
+    Strict,
+    /// Replace each invalid sequence with the given character.
+    ReplaceWith(char),
+}
+
+/// How data record decode failures and malformed control records are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// Stop parsing and return the error.
+    #[default]
+    Abort,
+    /// Record a warning and leave the record's cell null.
+    ///
+    /// `int64[]`, `float[]`, and `double[]` records with a partial trailing element
+    /// are not skipped: they are truncated to their whole elements, with one warning
+    /// per affected entry.
+    SkipRecord,
+}
+
+/// How entries of an unknown WPILog type are decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnknownTypePolicy {
+    /// Decode the payload as a lossy UTF-8 `String` column and record a warning.
+    #[default]
+    String,
+    /// Keep the payload bytes unchanged in a `Binary` column.
+    Binary,
+    /// Fail with a schema error naming the entry and its type.
+    Error,
+}
+
+/// Data type of the `timestamp` column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampKind {
+    /// Signed microseconds. Timestamps above `i64::MAX` are a parse error.
+    #[default]
+    Int64,
+    /// Unsigned microseconds, matching the WPILog format, so every timestamp is
+    /// representable.
+    UInt64,
+}
+
+/// How integer struct fields with an enum specification are built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EnumOutput {
+    /// Keep the raw integer value.
+    #[default]
+    Raw,
+    /// Replace the value with its `String` label. Values missing from the enum are
+    /// written in decimal.
+    Label,
+    /// Keep the raw value and add a `<field>_label` sibling field holding the label.
+    Both,
+}
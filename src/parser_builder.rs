@@ -0,0 +1,333 @@
+//! Builder-style configuration for parsing WPILog files.
+//!
+//! This module provides `WpilogParserBuilder`, which collects `ParseOptions` through
+//! chainable setters and parses bytes or a file with them.
+
+use crate::converter::{ParsedLog, WpilogConverter};
+use crate::diagnostics::Diagnostics;
+use crate::error::Result;
+use crate::file_data::FileData;
+use crate::options::{
+    EnumOutput, ErrorPolicy, ParseOptions, ProtobufDecoder, StringEncoding, TimestampKind,
+    UnknownTypePolicy, Utf8Policy,
+};
+use crate::struct_support::StructRegistry;
+use crate::types::{PolarsDataType, PolarsValue};
+use polars::prelude::{DataFrame, ParquetCompression, ParquetWriter};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Chainable configuration for parsing a WPILog file.
+///
+/// Created by [`WpilogParser::builder`](crate::WpilogParser::builder). Every option
+/// starts at its [`ParseOptions`] default, which matches
+/// [`WpilogParser::from_bytes`](crate::WpilogParser::from_bytes).
+///
+/// # Example
+///
+/// ```no_run
+/// use wpilog_polars::WpilogParser;
+///
+/// let parsed = WpilogParser::builder()
+///     .categorical_strings(true)
+///     .drop_empty_columns(true)
+///     .parse_file("robot.wpilog")?;
+/// println!("{}", parsed.dataframe);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Default)]
+pub struct WpilogParserBuilder {
+    options: ParseOptions,
+    registry: Option<StructRegistry>,
+}
+
+impl WpilogParserBuilder {
+    /// Creates a builder with default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops data records read before their entry's START record. Default: `false`.
+    pub fn drop_orphan_records(mut self, drop: bool) -> Self {
+        self.options.drop_orphan_records = drop;
+        self
+    }
+
+    /// Sets how `string` values are encoded. Default: [`StringEncoding::Raw`].
+    pub fn string_encoding(mut self, encoding: StringEncoding) -> Self {
+        self.options.string_encoding = encoding;
+        self
+    }
+
+    /// Decodes the named entry as `type_name` instead of its declared type.
+    /// Default: no overrides.
+    pub fn type_override(mut self, name: impl Into<String>, type_name: impl Into<String>) -> Self {
+        self.options
+            .type_overrides
+            .insert(name.into(), type_name.into());
+        self
+    }
+
+    /// Builds the named entry's column as `column` instead of the entry name.
+    /// Default: no renames.
+    pub fn rename(mut self, name: impl Into<String>, column: impl Into<String>) -> Self {
+        self.options.rename.insert(name.into(), column.into());
+        self
+    }
+
+    /// Builds `string` columns as `Categorical`. Default: `false`.
+    pub fn categorical_strings(mut self, categorical: bool) -> Self {
+        self.options.categorical_strings = categorical;
+        self
+    }
+
+    /// Decodes `boolean[]` payloads as bit-packed. Default: `false` (one byte per boolean).
+    pub fn boolean_array_bitpacked(mut self, bitpacked: bool) -> Self {
+        self.options.boolean_array_bitpacked = bitpacked;
+        self
+    }
+
+    /// Omits columns for entries that were never written. Default: `false`.
+    pub fn drop_empty_columns(mut self, drop: bool) -> Self {
+        self.options.drop_empty_columns = drop;
+        self
+    }
+
+    /// Sets a decoder for `proto:*` entries from a function giving each message's
+    /// column type and a function decoding its payloads (see [`ProtobufDecoder`]).
+    /// Default: none (kept as strings).
+    pub fn protobuf_decoder<T, D>(mut self, dtype: T, decode: D) -> Self
+    where
+        T: Fn(&str) -> Option<PolarsDataType> + Send + Sync + 'static,
+        D: Fn(&str, &[u8]) -> Option<PolarsValue> + Send + Sync + 'static,
+    {
+        self.options.protobuf_decoder = Some(ProtobufDecoder::new(dtype, decode));
+        self
+    }
+
+    /// Sets a callback receiving `(bytes_processed, total_bytes)`. Default: none.
+    pub fn progress<F>(mut self, progress: F) -> Self
+    where
+        F: Fn(u64, u64) + Send + Sync + 'static,
+    {
+        self.options.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Sets how data record decode failures are handled. Default: [`ErrorPolicy::Abort`].
+    pub fn on_error(mut self, policy: ErrorPolicy) -> Self {
+        self.options.on_error = policy;
+        self
+    }
+
+    /// Applies the [`on_error`](Self::on_error) policy to truncated or unknown
+    /// control records. Default: `false` (they are skipped with a warning).
+    pub fn strict_control_records(mut self, strict: bool) -> Self {
+        self.options.strict_control_records = strict;
+        self
+    }
+
+    /// Orders entry columns alphabetically. Default: `false` (first START order).
+    pub fn sort_columns(mut self, sort: bool) -> Self {
+        self.options.sort_columns = sort;
+        self
+    }
+
+    /// Decodes the whole structs of struct-array records with trailing partial bytes,
+    /// with a warning. Default: `false` (fail).
+    pub fn truncate_struct_arrays(mut self, truncate: bool) -> Self {
+        self.options.truncate_struct_arrays = truncate;
+        self
+    }
+
+    /// Decodes padded `boolean` records from their first byte. Default: `false`
+    /// (exactly 1 byte required).
+    pub fn lenient_booleans(mut self, lenient: bool) -> Self {
+        self.options.lenient_booleans = lenient;
+        self
+    }
+
+    /// Sets how invalid UTF-8 in string values is handled. Default: [`Utf8Policy::Lossy`].
+    pub fn utf8_policy(mut self, policy: Utf8Policy) -> Self {
+        self.options.utf8_policy = policy;
+        self
+    }
+
+    /// Resyncs after records with impossible sizes instead of stopping there.
+    /// Default: `false` (stop at the first such record).
+    pub fn recover(mut self, recover: bool) -> Self {
+        self.options.recover = recover;
+        self
+    }
+
+    /// Splits struct columns into one column per leaf field, e.g. `pose.translation.x`.
+    /// Default: `false` (one `Struct` column per entry).
+    pub fn flatten_structs(mut self, flatten: bool) -> Self {
+        self.options.flatten_structs = flatten;
+        self
+    }
+
+    /// Sets how entries of unknown WPILog types are decoded.
+    /// Default: [`UnknownTypePolicy::String`].
+    pub fn unknown_type_policy(mut self, policy: UnknownTypePolicy) -> Self {
+        self.options.unknown_type_policy = policy;
+        self
+    }
+
+    /// Adds a `<name>__present` column after each entry column, marking the rows
+    /// where the entry was logged. Default: `false`.
+    pub fn presence_columns(mut self, presence: bool) -> Self {
+        self.options.presence_columns = presence;
+        self
+    }
+
+    /// Sets how struct fields declared with an enum are built. Default:
+    /// [`EnumOutput::Raw`].
+    pub fn enum_output(mut self, enum_output: EnumOutput) -> Self {
+        self.options.enum_output = enum_output;
+        self
+    }
+
+    /// Casts scalar numeric and boolean columns to `Float64`. Default: `false`.
+    pub fn numeric_as_f64(mut self, numeric_as_f64: bool) -> Self {
+        self.options.numeric_as_f64 = numeric_as_f64;
+        self
+    }
+
+    /// Sets the data type of the `timestamp` column. Default: [`TimestampKind::Int64`].
+    pub fn timestamp_kind(mut self, kind: TimestampKind) -> Self {
+        self.options.timestamp_kind = kind;
+        self
+    }
+
+    /// Keeps only the columns whose WPILog type matches one of `types`, e.g.
+    /// `["double", "struct"]`. Default: every column.
+    pub fn only_types<I, S>(mut self, types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options.only_types = Some(types.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Seeds struct definitions that the log may reference without defining.
+    /// Default: an empty registry.
+    pub fn registry(mut self, registry: StructRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Returns the options configured so far.
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    /// Parses WPILog data from a byte slice.
+    pub fn parse_bytes(self, data: &[u8]) -> Result<ParsedLog> {
+        WpilogConverter::convert(data, self.registry.unwrap_or_default(), &self.options)
+    }
+
+    /// Parses a WPILog file from disk using memory mapping.
+    pub fn parse_file<P: AsRef<Path>>(self, path: P) -> Result<ParsedLog> {
+        let mut diagnostics = Diagnostics::new();
+        let data = FileData::open(path.as_ref(), &mut diagnostics)?;
+        let mut parsed = self.parse_bytes(&data)?;
+        diagnostics.extend(parsed.diagnostics);
+        parsed.diagnostics = diagnostics;
+        Ok(parsed)
+    }
+
+    /// Converts a WPILog file to Parquet, parsing and writing `chunk_rows` rows at a
+    /// time so the whole DataFrame is never held in memory.
+    ///
+    /// Returns the diagnostics of the conversion.
+    pub fn write_parquet_streaming<P: AsRef<Path>, Q: AsRef<Path>>(
+        self,
+        input: P,
+        output: Q,
+        chunk_rows: usize,
+        compression: ParquetCompression,
+    ) -> Result<Diagnostics> {
+        let mut diagnostics = Diagnostics::new();
+        let data = FileData::open(input.as_ref(), &mut diagnostics)?;
+
+        let file = std::fs::File::create(output.as_ref())?;
+        let mut writer = Some(ParquetWriter::new(file).with_compression(compression));
+        let mut batched = None;
+        let mut write_chunk = |chunk: DataFrame| -> Result<()> {
+            if batched.is_none() {
+                let schema = chunk.schema().clone();
+                batched = writer.take().map(|w| w.batched(&schema)).transpose()?;
+            }
+            if let Some(batched) = batched.as_mut() {
+                batched.write_batch(&chunk)?;
+            }
+            Ok(())
+        };
+
+        let conversion = WpilogConverter::convert_chunked(
+            &data,
+            self.registry.unwrap_or_default(),
+            &self.options,
+            chunk_rows,
+            &mut write_chunk,
+        )?;
+        if let Some(batched) = batched {
+            batched.finish()?;
+        }
+
+        diagnostics.extend(conversion);
+        Ok(diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{encode_record, encode_start, wpilog_header};
+
+    /// Helper to create a WPILog file with an unwritten entry and a string entry
+    fn create_test_wpilog() -> Vec<u8> {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "mode", "string"));
+        data.extend(encode_start(2, "unused", "double"));
+        data.extend(encode_record(1, 2, b"Auto"));
+        data
+    }
+
+    #[test]
+    fn test_builder_defaults() {
+        let builder = WpilogParserBuilder::new();
+        let options = builder.options();
+        assert!(!options.drop_orphan_records);
+        assert_eq!(options.string_encoding, StringEncoding::Raw);
+        assert!(options.type_overrides.is_empty());
+        assert!(!options.categorical_strings);
+        assert!(!options.drop_empty_columns);
+
+        let parsed = builder.parse_bytes(&create_test_wpilog()).unwrap();
+        assert_eq!(parsed.dataframe.width(), 3);
+    }
+
+    #[test]
+    fn test_builder_applies_options() {
+        let parsed = WpilogParserBuilder::new()
+            .drop_empty_columns(true)
+            .categorical_strings(true)
+            .parse_bytes(&create_test_wpilog())
+            .unwrap();
+
+        let names: Vec<&str> = parsed
+            .dataframe
+            .get_column_names()
+            .iter()
+            .map(|n| n.as_str())
+            .collect();
+        assert_eq!(names, vec!["timestamp", "mode"]);
+        assert!(matches!(
+            parsed.dataframe.column("mode").unwrap().dtype(),
+            polars::prelude::DataType::Categorical(..)
+        ));
+    }
+}
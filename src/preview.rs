@@ -0,0 +1,232 @@
+//! Sampled previews of large logs.
+//!
+//! A preview holds a handful of rows spread evenly across the whole log. Record
+//! headers are scanned without decoding, and only the records that supply a preview
+//! row are decoded, so a preview stays cheap for logs far too large to parse just
+//! to show a table.
+
+use crate::builders::DataFrameBuilder;
+use crate::converter::WpilogConverter;
+use crate::datalog::{DataLogIterator, DataLogReader};
+use crate::diagnostics::Diagnostics;
+use crate::error::{Result, WpilogError};
+use crate::options::ParseOptions;
+use crate::schema::WpilogSchema;
+use crate::struct_support::{StructDeserializer, StructRegistry};
+use polars::prelude::DataFrame;
+use std::collections::HashSet;
+
+/// Builds a sampled preview of up to `rows` rows from WPILog data.
+///
+/// The rows are taken at timestamps spread evenly in time from the first data
+/// timestamp to the last, including both. Memory use grows with the number of
+/// columns and rows, not with the size of the log. Each row holds every column's
+/// most recent value at or before its timestamp, or null if the column has no
+/// value yet. This is a sample of the log's state over time, not a set of exact
+/// DataFrame rows: held values appear in rows where the full DataFrame has nulls.
+/// The columns match those of a full parse.
+pub fn preview(data: &[u8], rows: usize) -> Result<DataFrame> {
+    let reader = DataLogReader::new(data);
+
+    if !reader.is_valid() {
+        return Err(WpilogError::InvalidFormat(
+            "Invalid WPILog file header".to_string(),
+        ));
+    }
+
+    let mut diagnostics = Diagnostics::new();
+    let (registry, mut schema) = WpilogConverter::build_registry_and_schema(
+        &reader,
+        StructRegistry::new(),
+        &ParseOptions::default(),
+        &mut diagnostics,
+    )?;
+    WpilogConverter::downgrade_unresolved_structs(&mut schema, &registry, &mut diagnostics);
+    let deserializer = StructDeserializer::new(&registry);
+
+    // A header-only pass finds the time span, and a second keeps, for each column
+    // and sampled timestamp, the latest record since the previous sample
+    let mut span: Option<(i64, i64)> = None;
+    scan_data_records(&reader, &schema, |_, timestamp, _| {
+        span = Some(span.map_or((timestamp, timestamp), |(first, last)| {
+            (first.min(timestamp), last.max(timestamp))
+        }));
+    })?;
+    let sampled = span.map_or_else(Vec::new, |(first, last)| sample_times(first, last, rows));
+
+    let mut held: Vec<Vec<Option<(i64, usize)>>> =
+        vec![vec![None; sampled.len()]; schema.num_columns()];
+    scan_data_records(&reader, &schema, |index, timestamp, offset| {
+        let sample = sampled.partition_point(|&t| t < timestamp);
+        if let Some(slot) = held[index].get_mut(sample) {
+            // Ties keep the record later in the log
+            if slot.is_none_or(|(held_ts, _)| timestamp >= held_ts) {
+                *slot = Some((timestamp, offset));
+            }
+        }
+    })?;
+
+    let column_names = schema.columns().iter().map(|c| c.name.clone()).collect();
+    let column_types = schema.columns().iter().map(|c| c.dtype.clone()).collect();
+    let mut builder =
+        DataFrameBuilder::new(column_names, column_types, sampled.len()).with_registry(&registry);
+
+    for (sample, &timestamp) in sampled.iter().enumerate() {
+        let values = schema
+            .columns()
+            .iter()
+            .zip(&held)
+            .map(|(column, column_held)| {
+                // The last record at or before the sampled timestamp
+                let Some((_, record_offset)) =
+                    column_held[..=sample].iter().rev().find_map(|slot| *slot)
+                else {
+                    return Ok(None);
+                };
+                let record = DataLogIterator::new(data, record_offset)
+                    .next()
+                    .ok_or_else(|| {
+                        WpilogError::InvalidFormat(format!(
+                            "Record at offset {} could not be reread",
+                            record_offset
+                        ))
+                    })??;
+                Ok(Some(
+                    record.get_value_as_polars(&column.dtype, Some(&deserializer))?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        builder.push_row(timestamp, &values);
+    }
+
+    builder.build()
+}
+
+/// Calls `f` with the column index, timestamp, and offset of every data record of
+/// a schema column, reading only record headers. Records after their entry's FINISH
+/// are skipped.
+fn scan_data_records(
+    reader: &DataLogReader,
+    schema: &WpilogSchema,
+    mut f: impl FnMut(usize, i64, usize),
+) -> Result<()> {
+    let mut finished_entries = HashSet::new();
+    let mut records = reader.records()?;
+    let mut offset = records.offset();
+    while let Some(record_result) = records.next() {
+        let record = record_result?;
+        let record_offset = offset;
+        offset = records.offset();
+
+        if record.is_control() {
+            if record.is_finish() {
+                finished_entries.insert(record.get_finish_entry()?);
+            }
+            continue;
+        }
+        if finished_entries.contains(&record.entry) {
+            continue;
+        }
+        let Some(index) = schema.column_index(record.entry) else {
+            continue;
+        };
+        f(index, record.timestamp_i64()?, record_offset);
+    }
+    Ok(())
+}
+
+/// Picks up to `count` evenly spaced timestamps from `first` to `last`, including
+/// both when `count` is at least 2.
+fn sample_times(first: i64, last: i64, count: usize) -> Vec<i64> {
+    match count {
+        0 => Vec::new(),
+        1 => vec![first],
+        _ => {
+            let span = (last - first) as i128;
+            let mut times: Vec<i64> = (0..count)
+                .map(|i| first + (i as i128 * span / (count as i128 - 1)) as i64)
+                .collect();
+            times.dedup();
+            times
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{encode_record, encode_start, wpilog_header};
+
+    /// Helper to create a log with "speed" logged at t=1..=100 and "count" at even t
+    fn create_test_wpilog() -> Vec<u8> {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_start(2, "count", "int64"));
+        for t in 1..=100u8 {
+            data.extend(encode_record(1, t, &(t as f64).to_le_bytes()));
+            if t % 2 == 0 {
+                data.extend(encode_record(2, t, &(t as i64).to_le_bytes()));
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn test_preview() {
+        let df = preview(&create_test_wpilog(), 5).unwrap();
+
+        assert_eq!(df.height(), 5);
+        let names: Vec<&str> = df.get_column_names().iter().map(|n| n.as_str()).collect();
+        assert_eq!(names, vec!["timestamp", "speed", "count"]);
+
+        let timestamps: Vec<i64> = df
+            .column("timestamp")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(timestamps, vec![1, 25, 50, 75, 100]);
+
+        let speeds: Vec<Option<f64>> = df
+            .column("speed")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(
+            speeds,
+            vec![Some(1.0), Some(25.0), Some(50.0), Some(75.0), Some(100.0)]
+        );
+
+        // Values are held from the last record, and null before the first
+        let counts: Vec<Option<i64>> = df
+            .column("count")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(counts, vec![None, Some(24), Some(50), Some(74), Some(100)]);
+    }
+
+    #[test]
+    fn test_preview_small_logs() {
+        let data = create_test_wpilog();
+        assert_eq!(preview(&data, 1_000).unwrap().height(), 100);
+        assert_eq!(preview(&data, 0).unwrap().height(), 0);
+        assert_eq!(preview(&data, 0).unwrap().width(), 3);
+
+        assert!(preview(b"NOTLOG", 5).is_err());
+    }
+
+    #[test]
+    fn test_sample_times() {
+        assert_eq!(sample_times(0, 9, 1), vec![0]);
+        assert_eq!(sample_times(0, 9, 2), vec![0, 9]);
+        assert_eq!(sample_times(0, 9, 4), vec![0, 3, 6, 9]);
+        assert_eq!(sample_times(0, 9, 20), (0..10).collect::<Vec<_>>());
+        assert!(sample_times(0, 9, 0).is_empty());
+    }
+}
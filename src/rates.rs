@@ -0,0 +1,156 @@
+//! Per-entry logging rates.
+//!
+//! The rate of each entry is computed from record headers alone, in a single scan
+//! and without decoding any values. Comparing rates across entries makes dropped
+//! or misconfigured signals easy to spot.
+
+use crate::datalog::DataLogReader;
+use crate::error::{Result, WpilogError};
+use std::collections::{HashMap, HashSet};
+
+/// Record count and time span of one entry.
+#[derive(Default)]
+struct EntrySpan {
+    count: u64,
+    first: i64,
+    last: i64,
+}
+
+/// Computes the average logging rate of every entry, in records per second.
+///
+/// The rate is the number of intervals between an entry's records divided by the
+/// time from its first to its last record, matching the average interval reported
+/// by the `info` command. Entries with fewer than two records, or whose records
+/// all share one timestamp, have no defined rate and map to `None`. Records after
+/// an entry's FINISH record are not counted until a new START reuses its ID.
+/// Entries that share a name are counted together, as they share a column.
+pub fn entry_rates(data: &[u8]) -> Result<HashMap<String, Option<f64>>> {
+    let reader = DataLogReader::new(data);
+
+    if !reader.is_valid() {
+        return Err(WpilogError::InvalidFormat(
+            "Invalid WPILog file header".to_string(),
+        ));
+    }
+
+    let mut names: HashMap<u32, String> = HashMap::new();
+    let mut finished_entries = HashSet::new();
+    let mut spans: HashMap<String, EntrySpan> = HashMap::new();
+
+    for record_result in reader.records()? {
+        let record = record_result?;
+
+        if record.is_control() {
+            if record.is_start() {
+                let start_data = record.get_start_data()?;
+                spans.entry(start_data.name.clone()).or_default();
+                // A START after a FINISH reuses the entry ID for the new name
+                if finished_entries.remove(&start_data.entry) {
+                    names.insert(start_data.entry, start_data.name);
+                } else {
+                    names.entry(start_data.entry).or_insert(start_data.name);
+                }
+            } else if record.is_finish() {
+                finished_entries.insert(record.get_finish_entry()?);
+            }
+            continue;
+        }
+
+        if finished_entries.contains(&record.entry) {
+            continue;
+        }
+        let Some(name) = names.get(&record.entry) else {
+            continue;
+        };
+
+        let timestamp = record.timestamp_i64()?;
+        let span = spans.entry(name.clone()).or_default();
+        if span.count == 0 {
+            span.first = timestamp;
+            span.last = timestamp;
+        } else {
+            span.first = span.first.min(timestamp);
+            span.last = span.last.max(timestamp);
+        }
+        span.count += 1;
+    }
+
+    Ok(spans
+        .into_iter()
+        .map(|(name, span)| {
+            let duration_us = span.last - span.first;
+            let rate = (span.count >= 2 && duration_us > 0)
+                .then(|| (span.count - 1) as f64 * 1_000_000.0 / duration_us as f64);
+            (name, rate)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{
+        encode_record_wide, encode_start, finish_payload, start_payload, wpilog_header,
+    };
+
+    #[test]
+    fn test_entry_rates() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "fast", "double"));
+        data.extend(encode_start(2, "slow", "double"));
+        data.extend(encode_start(3, "once", "double"));
+        data.extend(encode_start(4, "never", "double"));
+        // 50 Hz for one second
+        for i in 0..=50u32 {
+            data.extend(encode_record_wide(1, i * 20_000, &0f64.to_le_bytes()));
+        }
+        // 2 Hz for one second
+        for i in 0..=2u32 {
+            data.extend(encode_record_wide(2, i * 500_000, &0f64.to_le_bytes()));
+        }
+        data.extend(encode_record_wide(3, 10, &0f64.to_le_bytes()));
+
+        let rates = entry_rates(&data).unwrap();
+
+        assert_eq!(rates.len(), 4);
+        assert!((rates["fast"].unwrap() - 50.0).abs() < 1e-9);
+        assert!((rates["slow"].unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(rates["once"], None);
+        assert_eq!(rates["never"], None);
+    }
+
+    #[test]
+    fn test_entry_rates_stop_at_finish() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_record_wide(1, 0, &0f64.to_le_bytes()));
+        data.extend(encode_record_wide(1, 100_000, &0f64.to_le_bytes()));
+        data.extend(encode_record_wide(0, 100_000, &finish_payload(1)));
+        data.extend(encode_record_wide(1, 900_000, &0f64.to_le_bytes()));
+
+        let rates = entry_rates(&data).unwrap();
+
+        assert!((rates["speed"].unwrap() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_entry_rates_reused_entry_id() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_record_wide(1, 0, &0f64.to_le_bytes()));
+        data.extend(encode_record_wide(1, 100_000, &0f64.to_le_bytes()));
+        data.extend(encode_record_wide(0, 100_000, &finish_payload(1)));
+        data.extend(encode_record_wide(
+            0,
+            200_000,
+            &start_payload(1, "voltage", "double", ""),
+        ));
+        data.extend(encode_record_wide(1, 200_000, &0f64.to_le_bytes()));
+        data.extend(encode_record_wide(1, 250_000, &0f64.to_le_bytes()));
+
+        let rates = entry_rates(&data).unwrap();
+
+        assert!((rates["speed"].unwrap() - 10.0).abs() < 1e-9);
+        assert!((rates["voltage"].unwrap() - 20.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,290 @@
+//! Resampling a parsed log onto a regular time grid.
+//!
+//! Parsed logs are sparse: each column is null wherever its entry wasn't updated.
+//! Computing signals that combine entries, like drivetrain voltage at each gyro
+//! update, needs every column to have a value at the same timestamps.
+
+use crate::error::{Result, WpilogError};
+use polars::prelude::*;
+
+/// How values are filled in between the samples of a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResampleStrategy {
+    /// Use the most recent value at or before each grid time.
+    #[default]
+    Forward,
+    /// Interpolate float columns linearly between the surrounding values. Other
+    /// columns, including struct and list columns, are forward-filled.
+    Linear,
+}
+
+/// The largest grid [`resample`] builds, in rows.
+pub const MAX_GRID_ROWS: u64 = 10_000_000;
+
+/// Resamples `df` onto a grid of times spaced `every_us` apart, from the first to
+/// the last value of the time column `on`.
+///
+/// Every other column takes its value at each grid time according to `strategy`.
+/// Grid times before a column's first value are null, and times after its last
+/// value hold that value.
+///
+/// The time column may have any integer type, such as the UInt64 timestamps of
+/// [`TimestampKind::UInt64`](crate::TimestampKind::UInt64); the grid keeps its type.
+///
+/// Returns an error if the interval is zero, a time doesn't fit in an Int64, or the
+/// grid would have more than [`MAX_GRID_ROWS`] rows.
+pub fn resample(
+    df: &DataFrame,
+    on: &str,
+    every_us: u64,
+    strategy: ResampleStrategy,
+) -> Result<DataFrame> {
+    if every_us == 0 {
+        return Err(WpilogError::Other(
+            "Resample interval must be greater than zero".to_string(),
+        ));
+    }
+
+    let df = df.sort([on], SortMultipleOptions::default())?;
+    let time_column = df.column(on)?;
+    let time_dtype = time_column.dtype().clone();
+    let times = time_column
+        .strict_cast(&DataType::Int64)
+        .map_err(|e| {
+            WpilogError::Other(format!(
+                "Time column '{}' of type {} can't be resampled as Int64: {}",
+                on, time_dtype, e
+            ))
+        })?
+        .i64()?
+        .into_iter()
+        .map(|t| {
+            t.ok_or_else(|| WpilogError::Other(format!("Time column '{}' contains nulls", on)))
+        })
+        .collect::<Result<Vec<i64>>>()?;
+
+    let (Some(&first), Some(&last)) = (times.first(), times.last()) else {
+        return Ok(df.clear());
+    };
+    let rows = (last as i128 - first as i128) as u128 / every_us as u128 + 1;
+    if rows > MAX_GRID_ROWS as u128 {
+        return Err(WpilogError::Other(format!(
+            "Resampling every {} us from {} to {} needs {} rows, more than the limit of {}",
+            every_us, first, last, rows, MAX_GRID_ROWS
+        )));
+    }
+    let grid: Vec<i64> = (first..=last).step_by(every_us as usize).collect();
+
+    let mut columns = Vec::with_capacity(df.width());
+    for column in df.get_columns() {
+        if column.name().as_str() == on {
+            columns.push(Column::new(on.into(), &grid).cast(&time_dtype)?);
+            continue;
+        }
+
+        let series = column.as_materialized_series();
+        let present: Vec<usize> = series
+            .is_not_null()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, valid)| (valid == Some(true)).then_some(i))
+            .collect();
+        let samples = Samples {
+            times: &times,
+            present: &present,
+        };
+
+        let resampled = match (strategy, series.dtype()) {
+            (ResampleStrategy::Linear, DataType::Float32 | DataType::Float64) => {
+                samples.interpolate(series, &grid)?
+            }
+            _ => samples.forward_fill(series, &grid)?,
+        };
+        columns.push(resampled.into());
+    }
+
+    Ok(DataFrame::new(columns)?)
+}
+
+/// The rows of one column that hold values, with the time of every row.
+struct Samples<'a> {
+    times: &'a [i64],
+    present: &'a [usize],
+}
+
+impl Samples<'_> {
+    /// Position in `present` of the last value at or before `t`.
+    fn last_at_or_before(&self, t: i64) -> Option<usize> {
+        let count = self.present.partition_point(|&row| self.times[row] <= t);
+        count.checked_sub(1)
+    }
+
+    fn forward_fill(&self, series: &Series, grid: &[i64]) -> Result<Series> {
+        let rows = IdxCa::from_iter_options(
+            series.name().clone(),
+            grid.iter().map(|&t| {
+                self.last_at_or_before(t)
+                    .map(|p| self.present[p] as IdxSize)
+            }),
+        );
+        Ok(series.take(&rows)?)
+    }
+
+    fn interpolate(&self, series: &Series, grid: &[i64]) -> Result<Series> {
+        let values = series.cast(&DataType::Float64)?;
+        let values = values.f64()?;
+
+        let interpolated = Float64Chunked::from_iter_options(
+            series.name().clone(),
+            grid.iter().map(|&t| {
+                let p = self.last_at_or_before(t)?;
+                let (row0, t0) = (self.present[p], self.times[self.present[p]]);
+                let v0 = values.get(row0)?;
+                match self.present.get(p + 1) {
+                    Some(&row1) if t0 != t => {
+                        let t1 = self.times[row1];
+                        let v1 = values.get(row1)?;
+                        Some(v0 + (v1 - v0) * (t - t0) as f64 / (t1 - t0) as f64)
+                    }
+                    _ => Some(v0),
+                }
+            }),
+        );
+        Ok(interpolated.into_series().cast(series.dtype())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> DataFrame {
+        df!(
+            "timestamp" => [0i64, 10, 30],
+            "voltage" => [Some(0.0f64), None, Some(3.0)],
+            "mode" => [Some("auto"), Some("teleop"), None],
+        )
+        .unwrap()
+    }
+
+    fn voltages(df: &DataFrame) -> Vec<Option<f64>> {
+        df.column("voltage")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_resample_forward() {
+        let df = resample(&sample(), "timestamp", 10, ResampleStrategy::Forward).unwrap();
+
+        let timestamps: Vec<Option<i64>> = df
+            .column("timestamp")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(timestamps, vec![Some(0), Some(10), Some(20), Some(30)]);
+        assert_eq!(
+            voltages(&df),
+            vec![Some(0.0), Some(0.0), Some(0.0), Some(3.0)]
+        );
+
+        let modes: Vec<Option<&str>> = df
+            .column("mode")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(
+            modes,
+            vec![Some("auto"), Some("teleop"), Some("teleop"), Some("teleop")]
+        );
+    }
+
+    #[test]
+    fn test_resample_linear() {
+        let df = resample(&sample(), "timestamp", 10, ResampleStrategy::Linear).unwrap();
+
+        assert_eq!(
+            voltages(&df),
+            vec![Some(0.0), Some(1.0), Some(2.0), Some(3.0)]
+        );
+        // Non-float columns are forward-filled
+        assert_eq!(df.column("mode").unwrap().null_count(), 0);
+    }
+
+    #[test]
+    fn test_resample_before_first_value() {
+        let df = df!(
+            "timestamp" => [0i64, 20],
+            "speed" => [None, Some(1.0f32)],
+        )
+        .unwrap();
+
+        let df = resample(&df, "timestamp", 10, ResampleStrategy::Linear).unwrap();
+        let speeds: Vec<Option<f32>> = df
+            .column("speed")
+            .unwrap()
+            .f32()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(speeds, vec![None, None, Some(1.0)]);
+    }
+
+    #[test]
+    fn test_resample_zero_interval() {
+        assert!(resample(&sample(), "timestamp", 0, ResampleStrategy::Forward).is_err());
+    }
+
+    #[test]
+    fn test_resample_grid_too_large() {
+        let df = df!(
+            "timestamp" => [0i64, i64::MAX],
+            "speed" => [Some(1.0f64), Some(2.0)],
+        )
+        .unwrap();
+        assert!(matches!(
+            resample(&df, "timestamp", 1, ResampleStrategy::Forward),
+            Err(WpilogError::Other(_))
+        ));
+    }
+
+    #[test]
+    fn test_resample_uint64_timestamps() {
+        let df = df!(
+            "timestamp" => [0u64, 10, 30],
+            "voltage" => [Some(0.0f64), None, Some(3.0)],
+        )
+        .unwrap();
+        let df = resample(&df, "timestamp", 10, ResampleStrategy::Linear).unwrap();
+
+        let timestamps: Vec<Option<u64>> = df
+            .column("timestamp")
+            .unwrap()
+            .u64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(timestamps, vec![Some(0), Some(10), Some(20), Some(30)]);
+        assert_eq!(
+            voltages(&df),
+            vec![Some(0.0), Some(1.0), Some(2.0), Some(3.0)]
+        );
+
+        let df = df!(
+            "timestamp" => [0u64, u64::MAX],
+            "voltage" => [Some(0.0f64), Some(3.0)],
+        )
+        .unwrap();
+        assert!(matches!(
+            resample(&df, "timestamp", 10, ResampleStrategy::Forward),
+            Err(WpilogError::Other(_))
+        ));
+    }
+}
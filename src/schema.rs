@@ -4,24 +4,137 @@
 //! - `ColumnInfo`: metadata about a single column
 //! - `WpilogSchema`: collection of column information
 //! - Schema inference from START control records
+//! - `SchemaDiff`: columns added, removed, or retyped between two schemas
+//! - `SchemaDocument`: a serializable description of the schema and struct layouts
 
 use crate::datalog::DataLogIterator;
-use crate::error::{Result, WpilogError};
-use crate::types::PolarsDataType;
+use crate::error::Result;
+use crate::struct_support::{PolarsConverter, StructField, StructRegistry};
+use crate::types::{normalize_type_name, PolarsDataType};
 use polars::prelude::*;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
 
 /// Information about a single column in the schema.
 #[derive(Debug, Clone)]
 pub struct ColumnInfo {
+    /// The ID of the entry that produced the column.
     pub entry_id: u32,
+    /// The column name: the entry name, unless renamed through
+    /// [`ParseOptions::rename`](crate::options::ParseOptions::rename).
     pub name: String,
+    /// The Polars type values are decoded to.
     pub dtype: PolarsDataType,
+    /// The WPILog type string from the entry's START record (e.g. `"struct:Pose2d"`).
+    pub type_name: String,
+    /// Whether the column may hold nulls. Entries are sparse, so this is always true
+    /// for columns inferred from a log.
     pub nullable: bool,
+    /// The metadata string from the entry's START record.
     pub metadata: String,
 }
 
+impl ColumnInfo {
+    /// Field metadata describing the column's source entry: `wpilog.type` holds the
+    /// WPILog type string and `wpilog.entry_id` the entry ID in decimal.
+    pub fn field_metadata(&self) -> HashMap<String, String> {
+        HashMap::from([
+            ("wpilog.type".to_string(), self.type_name.clone()),
+            ("wpilog.entry_id".to_string(), self.entry_id.to_string()),
+        ])
+    }
+
+    /// Returns true if the column's WPILog type matches `selector`.
+    ///
+    /// A selector matches its exact type string (e.g. `double[]` or
+    /// `struct:Pose2d`). The family selectors `struct`, `struct[]`, and `proto` also
+    /// match every named struct, struct array, and protobuf type respectively. Both
+    /// sides are normalized first (see [`normalize_type_name`]).
+    pub fn matches_type(&self, selector: &str) -> bool {
+        let type_name = normalize_type_name(&self.type_name);
+        let selector = normalize_type_name(selector);
+        type_name == selector
+            || match selector.as_str() {
+                "struct" => type_name.starts_with("struct:") && !type_name.ends_with("[]"),
+                "struct[]" => type_name.starts_with("struct:") && type_name.ends_with("[]"),
+                "proto" => type_name.starts_with("proto:"),
+                _ => false,
+            }
+    }
+}
+
+/// The differences between two schemas, by column name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SchemaDiff {
+    /// Columns only in the newer schema, in its column order.
+    pub added: Vec<String>,
+    /// Columns only in the older schema, in its column order.
+    pub removed: Vec<String>,
+    /// Columns in both schemas whose WPILog type differs, in the older schema's
+    /// column order.
+    pub retyped: Vec<TypeChange>,
+}
+
+impl SchemaDiff {
+    /// Returns true if the schemas have the same columns with the same types.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.retyped.is_empty()
+    }
+}
+
+/// A column whose WPILog type differs between two schemas.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeChange {
+    pub name: String,
+    /// The WPILog type string in the older schema.
+    pub old_type: String,
+    /// The WPILog type string in the newer schema.
+    pub new_type: String,
+}
+
+/// A serializable description of a log's columns and struct layouts, for writing a
+/// schema sidecar such as `schema.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaDocument {
+    /// The columns in schema order, without the `timestamp` column.
+    pub columns: Vec<ColumnDocument>,
+    /// The layouts of the registered structs, by struct name.
+    pub structs: BTreeMap<String, Vec<StructFieldDocument>>,
+}
+
+/// A column of a [`SchemaDocument`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnDocument {
+    pub name: String,
+    pub entry_id: u32,
+    /// The WPILog type string from the entry's START record.
+    pub wpilog_type: String,
+    /// The Polars type of the column, e.g. `f64` or `Struct(Pose2d)`.
+    pub polars_type: String,
+    pub metadata: String,
+}
+
+/// A field of a struct layout in a [`SchemaDocument`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructFieldDocument {
+    pub name: String,
+    /// The field type as spelled in the struct schema, e.g. `double[4]`, or
+    /// `int8:3` for a bit-field.
+    pub field_type: String,
+    /// Byte offset of the field, or of the storage unit holding a bit-field.
+    pub offset: usize,
+    /// Size in bytes of the field, or of a bit-field's storage unit.
+    pub size: usize,
+    /// Bit offset of a bit-field within its storage unit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bit_offset: Option<usize>,
+}
+
 /// Schema for a WPILog file, containing information about all columns.
+///
+/// Columns are kept in the order their entries' first START records were read,
+/// unless reordered with [`sort_columns_by_name`](Self::sort_columns_by_name).
 #[derive(Debug, Clone)]
 pub struct WpilogSchema {
     columns: Vec<ColumnInfo>,
@@ -51,6 +164,39 @@ impl WpilogSchema {
             .and_then(|&idx| self.columns.get(idx))
     }
 
+    /// Gets the position of an entry's column among [`columns`](Self::columns).
+    pub fn column_index(&self, entry_id: u32) -> Option<usize> {
+        self.entry_to_index.get(&entry_id).copied()
+    }
+
+    /// Gets all columns mutably. Entry IDs must not be changed, as the entry index
+    /// isn't rebuilt.
+    pub(crate) fn columns_mut(&mut self) -> &mut [ColumnInfo] {
+        &mut self.columns
+    }
+
+    /// Keeps only the columns for which `keep` returns true, preserving their order.
+    pub fn retain_columns<F: FnMut(&ColumnInfo) -> bool>(&mut self, keep: F) {
+        self.columns.retain(keep);
+        self.rebuild_index();
+    }
+
+    /// Reorders the columns alphabetically by name. Columns with equal names keep
+    /// their relative order.
+    pub fn sort_columns_by_name(&mut self) {
+        self.columns.sort_by(|a, b| a.name.cmp(&b.name));
+        self.rebuild_index();
+    }
+
+    fn rebuild_index(&mut self) {
+        self.entry_to_index = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| (column.entry_id, index))
+            .collect();
+    }
+
     /// Gets all columns in the schema.
     pub fn columns(&self) -> &[ColumnInfo] {
         &self.columns
@@ -80,6 +226,210 @@ impl WpilogSchema {
         Schema::from_iter(fields)
     }
 
+    /// Converts to an Arrow Schema.
+    ///
+    /// Field types match the parsed DataFrame: struct and struct array columns become
+    /// Arrow `Struct` and `List(Struct)` types built from their layout in `registry`
+    /// (structs missing from it are described as strings). Each entry field carries
+    /// `wpilog.type` and `wpilog.entry_id` metadata so consumers of Parquet or IPC
+    /// output can recover the original WPILog semantics.
+    pub fn to_arrow_schema(&self, registry: &StructRegistry) -> arrow_schema::Schema {
+        let mut fields = Vec::with_capacity(self.columns.len() + 1);
+
+        // Timestamp column is always first
+        fields.push(arrow_schema::Field::new(
+            "timestamp",
+            arrow_schema::DataType::Int64,
+            false,
+        ));
+
+        for col in &self.columns {
+            fields.push(
+                arrow_schema::Field::new(
+                    col.name.as_str(),
+                    Self::arrow_dtype(&col.dtype, registry),
+                    col.nullable,
+                )
+                .with_metadata(col.field_metadata()),
+            );
+        }
+
+        arrow_schema::Schema::new(fields)
+    }
+
+    fn arrow_dtype(dtype: &PolarsDataType, registry: &StructRegistry) -> arrow_schema::DataType {
+        let list_of = |item: arrow_schema::DataType| {
+            arrow_schema::DataType::List(Arc::new(arrow_schema::Field::new("item", item, true)))
+        };
+        let struct_dtype = |struct_name: &str| {
+            PolarsConverter::new(registry)
+                .schema_to_dtype(struct_name)
+                .ok()
+                .map(|dtype| Self::arrow_from_polars(&dtype))
+        };
+
+        match dtype {
+            PolarsDataType::Float64 => arrow_schema::DataType::Float64,
+            PolarsDataType::Float32 => arrow_schema::DataType::Float32,
+            PolarsDataType::Int64 => arrow_schema::DataType::Int64,
+            PolarsDataType::Int8 => arrow_schema::DataType::Int8,
+            PolarsDataType::Int16 => arrow_schema::DataType::Int16,
+            PolarsDataType::Int32 => arrow_schema::DataType::Int32,
+            PolarsDataType::UInt8 => arrow_schema::DataType::UInt8,
+            PolarsDataType::UInt16 => arrow_schema::DataType::UInt16,
+            PolarsDataType::UInt32 => arrow_schema::DataType::UInt32,
+            PolarsDataType::Boolean => arrow_schema::DataType::Boolean,
+            PolarsDataType::Binary => arrow_schema::DataType::Binary,
+            PolarsDataType::BooleanArray => list_of(arrow_schema::DataType::Boolean),
+            PolarsDataType::Int64Array => list_of(arrow_schema::DataType::Int64),
+            PolarsDataType::Float32Array => list_of(arrow_schema::DataType::Float32),
+            PolarsDataType::Float64Array => list_of(arrow_schema::DataType::Float64),
+            PolarsDataType::StringArray => list_of(arrow_schema::DataType::Utf8),
+            PolarsDataType::Struct(name) => {
+                struct_dtype(name).unwrap_or(arrow_schema::DataType::Utf8)
+            }
+            PolarsDataType::StructArray(name) => struct_dtype(name)
+                .map(list_of)
+                .unwrap_or(arrow_schema::DataType::Utf8),
+            // Undecoded protobufs are described as strings, as in the Polars schema
+            PolarsDataType::String
+            | PolarsDataType::Protobuf(_)
+            | PolarsDataType::ProtobufArray(_) => arrow_schema::DataType::Utf8,
+        }
+    }
+
+    /// Maps the Polars type of a struct column (or one of its fields) to Arrow.
+    fn arrow_from_polars(dtype: &DataType) -> arrow_schema::DataType {
+        match dtype {
+            DataType::Boolean => arrow_schema::DataType::Boolean,
+            DataType::Int8 => arrow_schema::DataType::Int8,
+            DataType::Int16 => arrow_schema::DataType::Int16,
+            DataType::Int32 => arrow_schema::DataType::Int32,
+            DataType::Int64 => arrow_schema::DataType::Int64,
+            DataType::UInt8 => arrow_schema::DataType::UInt8,
+            DataType::UInt16 => arrow_schema::DataType::UInt16,
+            DataType::UInt32 => arrow_schema::DataType::UInt32,
+            DataType::UInt64 => arrow_schema::DataType::UInt64,
+            DataType::Float32 => arrow_schema::DataType::Float32,
+            DataType::Float64 => arrow_schema::DataType::Float64,
+            DataType::Binary => arrow_schema::DataType::Binary,
+            DataType::List(inner) => arrow_schema::DataType::List(Arc::new(
+                arrow_schema::Field::new("item", Self::arrow_from_polars(inner), true),
+            )),
+            DataType::Struct(fields) => arrow_schema::DataType::Struct(
+                fields
+                    .iter()
+                    .map(|field| {
+                        arrow_schema::Field::new(
+                            field.name().as_str(),
+                            Self::arrow_from_polars(field.dtype()),
+                            true,
+                        )
+                    })
+                    .collect(),
+            ),
+            _ => arrow_schema::DataType::Utf8,
+        }
+    }
+
+    /// Compares this schema with a newer one, matching columns by name.
+    ///
+    /// Types are compared by WPILog type string, so e.g. a change from
+    /// `struct:Pose2d` to `struct:Pose3d` is reported. When several entries share a
+    /// name, the first one's type is used.
+    pub fn diff(&self, other: &WpilogSchema) -> SchemaDiff {
+        let old_types = self.types_by_name();
+        let new_types = other.types_by_name();
+        let mut diff = SchemaDiff::default();
+
+        let mut seen = HashSet::new();
+        for column in &other.columns {
+            if seen.insert(column.name.as_str()) && !old_types.contains_key(column.name.as_str()) {
+                diff.added.push(column.name.clone());
+            }
+        }
+
+        let mut seen = HashSet::new();
+        for column in &self.columns {
+            if !seen.insert(column.name.as_str()) {
+                continue;
+            }
+            match new_types.get(column.name.as_str()) {
+                None => diff.removed.push(column.name.clone()),
+                Some(&new_type) if new_type != column.type_name => diff.retyped.push(TypeChange {
+                    name: column.name.clone(),
+                    old_type: column.type_name.clone(),
+                    new_type: new_type.to_string(),
+                }),
+                Some(_) => {}
+            }
+        }
+
+        diff
+    }
+
+    /// Maps each column name to the type of its first column.
+    fn types_by_name(&self) -> HashMap<&str, &str> {
+        let mut types = HashMap::new();
+        for column in &self.columns {
+            types
+                .entry(column.name.as_str())
+                .or_insert(column.type_name.as_str());
+        }
+        types
+    }
+
+    /// Describes the columns and the layouts of every struct in `registry` as a
+    /// serializable [`SchemaDocument`].
+    pub fn to_document(&self, registry: &StructRegistry) -> SchemaDocument {
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| ColumnDocument {
+                name: column.name.clone(),
+                entry_id: column.entry_id,
+                wpilog_type: column.type_name.clone(),
+                polars_type: match &column.dtype {
+                    PolarsDataType::Struct(name) => format!("Struct({})", name),
+                    PolarsDataType::StructArray(name) => format!("List(Struct({}))", name),
+                    other => other.to_polars_dtype().to_string(),
+                },
+                metadata: column.metadata.clone(),
+            })
+            .collect();
+
+        let structs = registry
+            .names()
+            .into_iter()
+            .filter_map(|name| registry.get(name).map(|schema| (name, schema)))
+            .map(|(name, schema)| {
+                let fields = schema
+                    .fields
+                    .iter()
+                    .map(|field| match field {
+                        StructField::Standard(f) => StructFieldDocument {
+                            name: f.name.clone(),
+                            field_type: f.field_type.to_string(),
+                            offset: f.offset,
+                            size: f.size,
+                            bit_offset: None,
+                        },
+                        StructField::BitField(b) => StructFieldDocument {
+                            name: b.name.clone(),
+                            field_type: format!("{}:{}", b.int_type, b.bit_width),
+                            offset: b.storage_offset,
+                            size: b.int_type.size(),
+                            bit_offset: Some(b.bit_offset),
+                        },
+                    })
+                    .collect();
+                (name.to_string(), fields)
+            })
+            .collect();
+
+        SchemaDocument { columns, structs }
+    }
+
     /// Infers schema from a WPILog file by reading all START control records.
     pub fn infer_from_records(mut records: DataLogIterator) -> Result<Self> {
         let mut schema = Self::new();
@@ -91,22 +441,30 @@ impl WpilogSchema {
             if record.is_start() {
                 let start_data = record.get_start_data()?;
 
-                // Skip if this entry was already finished and is being reused
-                if finished_entries.contains(&start_data.entry) {
+                // Skip if this entry was already finished and is being reused, or is
+                // started again without a FINISH
+                if finished_entries.contains(&start_data.entry)
+                    || schema.get_column_by_entry(start_data.entry).is_some()
+                {
                     continue;
                 }
 
                 // Skip structschema entries - they are metadata, not data columns
-                if start_data.type_name == "structschema" {
+                if normalize_type_name(&start_data.type_name) == "structschema" {
                     continue;
                 }
 
-                let dtype = PolarsDataType::from_wpilog_type(&start_data.type_name)?;
+                let dtype = PolarsDataType::from_start(
+                    &start_data.type_name,
+                    &start_data.name,
+                    &start_data.metadata,
+                )?;
 
                 let column = ColumnInfo {
                     entry_id: start_data.entry,
                     name: start_data.name,
                     dtype,
+                    type_name: start_data.type_name,
                     nullable: true, // All columns are nullable for sparse data
                     metadata: start_data.metadata,
                 };
@@ -118,12 +476,6 @@ impl WpilogSchema {
             }
         }
 
-        if schema.num_columns() == 0 {
-            return Err(WpilogError::SchemaError(
-                "No columns found in WPILog file".to_string(),
-            ));
-        }
-
         Ok(schema)
     }
 }
@@ -146,6 +498,7 @@ mod tests {
             entry_id: 1,
             name: "test".to_string(),
             dtype: PolarsDataType::Float64,
+            type_name: "double".to_string(),
             nullable: true,
             metadata: String::new(),
         });
@@ -155,6 +508,125 @@ mod tests {
         assert!(schema.get_column_by_entry(2).is_none());
     }
 
+    fn schema_with(columns: &[(&str, &str)]) -> WpilogSchema {
+        let mut schema = WpilogSchema::new();
+        for (i, (name, type_name)) in columns.iter().enumerate() {
+            schema.add_column(ColumnInfo {
+                entry_id: i as u32 + 1,
+                name: name.to_string(),
+                dtype: PolarsDataType::from_wpilog_type(type_name).unwrap(),
+                type_name: type_name.to_string(),
+                nullable: true,
+                metadata: String::new(),
+            });
+        }
+        schema
+    }
+
+    #[test]
+    fn test_diff_added_and_removed() {
+        let old = schema_with(&[("speed", "double"), ("legacy", "int64")]);
+        let new = schema_with(&[("speed", "double"), ("voltage", "double")]);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added, vec!["voltage"]);
+        assert_eq!(diff.removed, vec!["legacy"]);
+        assert!(diff.retyped.is_empty());
+    }
+
+    #[test]
+    fn test_diff_retyped() {
+        let old = schema_with(&[("pose", "struct:Pose2d"), ("speed", "double")]);
+        let new = schema_with(&[("speed", "float"), ("pose", "struct:Pose2d")]);
+
+        let diff = old.diff(&new);
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(
+            diff.retyped,
+            vec![TypeChange {
+                name: "speed".to_string(),
+                old_type: "double".to_string(),
+                new_type: "float".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_unchanged() {
+        let schema = schema_with(&[("speed", "double"), ("speed", "double")]);
+        assert!(schema.diff(&schema).is_empty());
+    }
+
+    #[test]
+    fn test_retain_columns() {
+        let mut schema = WpilogSchema::new();
+        for (entry_id, name) in [(1, "a"), (2, "b"), (3, "c")] {
+            schema.add_column(ColumnInfo {
+                entry_id,
+                name: name.to_string(),
+                dtype: PolarsDataType::Float64,
+                type_name: "double".to_string(),
+                nullable: true,
+                metadata: String::new(),
+            });
+        }
+
+        schema.retain_columns(|c| c.entry_id != 2);
+
+        assert_eq!(schema.num_columns(), 2);
+        assert!(schema.get_column_by_entry(2).is_none());
+        assert_eq!(schema.get_column_by_entry(3).unwrap().name, "c");
+        assert_eq!(schema.column_index(1), Some(0));
+        assert_eq!(schema.column_index(3), Some(1));
+        assert_eq!(schema.column_index(2), None);
+    }
+
+    #[test]
+    fn test_schema_document_round_trip() {
+        let mut registry = StructRegistry::new();
+        registry
+            .register("Translation2d".to_string(), "double x; double y")
+            .unwrap();
+        registry
+            .register("Flags".to_string(), "uint8 mode:3; uint8 level:2")
+            .unwrap();
+
+        let mut schema = WpilogSchema::new();
+        schema.add_column(ColumnInfo {
+            entry_id: 1,
+            name: "speed".to_string(),
+            dtype: PolarsDataType::Float64,
+            type_name: "double".to_string(),
+            nullable: true,
+            metadata: r#"{"unit":"m/s"}"#.to_string(),
+        });
+        schema.add_column(ColumnInfo {
+            entry_id: 2,
+            name: "position".to_string(),
+            dtype: PolarsDataType::Struct("Translation2d".to_string()),
+            type_name: "struct:Translation2d".to_string(),
+            nullable: true,
+            metadata: String::new(),
+        });
+
+        let document = schema.to_document(&registry);
+        assert_eq!(document.columns[0].polars_type, "f64");
+        assert_eq!(document.columns[1].polars_type, "Struct(Translation2d)");
+        assert_eq!(document.columns[1].entry_id, 2);
+
+        let translation = &document.structs["Translation2d"];
+        assert_eq!(translation[1].name, "y");
+        assert_eq!((translation[1].offset, translation[1].size), (8, 8));
+        let flags = &document.structs["Flags"];
+        assert_eq!(flags[0].field_type, "uint8:3");
+        assert_eq!(flags[1].bit_offset, Some(3));
+
+        let json = serde_json::to_string_pretty(&document).unwrap();
+        assert!(json.contains(r#""wpilog_type": "struct:Translation2d""#));
+        let parsed: SchemaDocument = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, document);
+    }
+
     #[test]
     fn test_to_polars_schema() {
         let mut schema = WpilogSchema::new();
@@ -163,6 +635,7 @@ mod tests {
             entry_id: 1,
             name: "value".to_string(),
             dtype: PolarsDataType::Float64,
+            type_name: "double".to_string(),
             nullable: true,
             metadata: String::new(),
         });
@@ -172,4 +645,94 @@ mod tests {
         assert!(polars_schema.get("timestamp").is_some());
         assert!(polars_schema.get("value").is_some());
     }
+
+    #[test]
+    fn test_to_arrow_schema() {
+        let mut schema = WpilogSchema::new();
+
+        schema.add_column(ColumnInfo {
+            entry_id: 7,
+            name: "speeds".to_string(),
+            dtype: PolarsDataType::Float64Array,
+            type_name: "double[]".to_string(),
+            nullable: true,
+            metadata: String::new(),
+        });
+
+        for (entry_id, name, type_name, dtype) in [
+            (
+                8,
+                "pose",
+                "struct:Pose",
+                PolarsDataType::Struct("Pose".to_string()),
+            ),
+            (
+                9,
+                "poses",
+                "struct:Pose[]",
+                PolarsDataType::StructArray("Pose".to_string()),
+            ),
+        ] {
+            schema.add_column(ColumnInfo {
+                entry_id,
+                name: name.to_string(),
+                dtype,
+                type_name: type_name.to_string(),
+                nullable: true,
+                metadata: String::new(),
+            });
+        }
+        let mut registry = StructRegistry::new();
+        registry
+            .register("Point".to_string(), "double x; double y")
+            .unwrap();
+        registry
+            .register("Pose".to_string(), "Point position; int8 flags[2]")
+            .unwrap();
+
+        let arrow_schema = schema.to_arrow_schema(&registry);
+        assert_eq!(arrow_schema.fields().len(), 4); // timestamp + speeds + pose + poses
+
+        let timestamp = arrow_schema.field_with_name("timestamp").unwrap();
+        assert_eq!(timestamp.data_type(), &arrow_schema::DataType::Int64);
+
+        let speeds = arrow_schema.field_with_name("speeds").unwrap();
+        assert!(matches!(
+            speeds.data_type(),
+            arrow_schema::DataType::List(_)
+        ));
+        assert_eq!(speeds.metadata()["wpilog.type"], "double[]");
+        assert_eq!(speeds.metadata()["wpilog.entry_id"], "7");
+
+        let point = arrow_schema::DataType::Struct(
+            vec![
+                arrow_schema::Field::new("x", arrow_schema::DataType::Float64, true),
+                arrow_schema::Field::new("y", arrow_schema::DataType::Float64, true),
+            ]
+            .into(),
+        );
+        let pose = arrow_schema::DataType::Struct(
+            vec![
+                arrow_schema::Field::new("position", point, true),
+                arrow_schema::Field::new(
+                    "flags",
+                    arrow_schema::DataType::List(Arc::new(arrow_schema::Field::new(
+                        "item",
+                        arrow_schema::DataType::Int8,
+                        true,
+                    ))),
+                    true,
+                ),
+            ]
+            .into(),
+        );
+        assert_eq!(
+            arrow_schema.field_with_name("pose").unwrap().data_type(),
+            &pose
+        );
+        assert_eq!(
+            arrow_schema.field_with_name("poses").unwrap().data_type(),
+            &arrow_schema::DataType::List(Arc::new(arrow_schema::Field::new("item", pose, true)))
+        );
+    }
 }
@@ -0,0 +1,457 @@
+//! Incremental parsing for WPILog files that are still being written.
+//!
+//! This module provides:
+//! - `WpilogStreamParser`: a stateful parser that accepts appended bytes
+//! - Buffering of partial trailing records until they are complete
+//! - Schema and struct registry retained across calls
+
+use crate::builders::DataFrameBuilder;
+use crate::datalog::{DataLogIterator, DataLogReader, DataLogRecord};
+use crate::error::{Result, WpilogError};
+use crate::schema::{ColumnInfo, WpilogSchema};
+use crate::struct_support::{SchemaParser, StructDeserializer, StructRegistry};
+use crate::types::{normalize_type_name, PolarsDataType, PolarsValue};
+use polars::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Stateful parser for tailing a growing WPILog file.
+///
+/// Each call to [`feed`](Self::feed) returns only the rows parsed from records completed
+/// since the previous call. The returned DataFrame contains a column for every entry
+/// seen so far, so later chunks may be wider than earlier ones.
+pub struct WpilogStreamParser {
+    buffer: Vec<u8>,
+    header_parsed: bool,
+    bytes_consumed: u64,
+    state: StreamState,
+}
+
+/// Schema and entry state retained across chunks.
+///
+/// A chunk updates a copy of the state, which replaces the parser's only once the
+/// whole chunk has decoded, so a failed feed can be retried with the same buffer.
+#[derive(Clone, Default)]
+struct StreamState {
+    schema: WpilogSchema,
+    registry: StructRegistry,
+    schema_entries: HashMap<u32, String>,
+    pending_structs: HashMap<String, String>,
+    schema_finished: HashSet<u32>,
+    data_finished: HashSet<u32>,
+}
+
+impl WpilogStreamParser {
+    /// Creates a new parser with no data.
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            header_parsed: false,
+            bytes_consumed: 0,
+            state: StreamState::default(),
+        }
+    }
+
+    /// Appends newly available bytes and returns the rows they completed.
+    ///
+    /// Bytes belonging to a partial trailing record are buffered until a later call
+    /// supplies the rest of the record. Rows are flushed at the end of every call, so
+    /// records sharing a timestamp across a chunk boundary produce two rows.
+    pub fn feed(&mut self, new_bytes: &[u8]) -> Result<DataFrame> {
+        self.buffer.extend_from_slice(new_bytes);
+
+        if !self.header_parsed && !self.parse_header()? {
+            return self.state.build_rows(&[]);
+        }
+
+        // Records borrow the buffer, so it is moved out while they are processed
+        let buffer = std::mem::take(&mut self.buffer);
+        let result = self.process_complete_records(&buffer);
+        self.buffer = buffer;
+
+        let (rows, consumed) = result?;
+        self.consume(consumed);
+        Ok(rows)
+    }
+
+    /// Gets the schema inferred from all START records seen so far.
+    pub fn schema(&self) -> &WpilogSchema {
+        &self.state.schema
+    }
+
+    /// Gets the struct registry built from all struct schemas seen so far.
+    pub fn registry(&self) -> &StructRegistry {
+        &self.state.registry
+    }
+
+    /// Returns the number of bytes fully consumed (header plus complete records).
+    pub fn bytes_consumed(&self) -> u64 {
+        self.bytes_consumed
+    }
+
+    /// Returns the number of bytes buffered while waiting for a record to complete.
+    pub fn pending_bytes(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Validates and consumes the file header once enough bytes are available.
+    /// Returns false if more data is needed.
+    fn parse_header(&mut self) -> Result<bool> {
+        if self.buffer.len() < 12 {
+            return Ok(false);
+        }
+
+        let reader = DataLogReader::new(&self.buffer);
+        if !reader.is_valid() {
+            return Err(WpilogError::InvalidFormat(
+                "Invalid WPILog file header".to_string(),
+            ));
+        }
+        reader.check_version()?;
+
+        let extra_header_size = u32::from_le_bytes([
+            self.buffer[8],
+            self.buffer[9],
+            self.buffer[10],
+            self.buffer[11],
+        ]) as usize;
+        let header_len = 12 + extra_header_size;
+        if self.buffer.len() < header_len {
+            return Ok(false);
+        }
+
+        self.consume(header_len);
+        self.header_parsed = true;
+        Ok(true)
+    }
+
+    /// Decodes all complete records in `buffer` into rows. Also returns the number of
+    /// bytes those records occupy.
+    fn process_complete_records(&mut self, buffer: &[u8]) -> Result<(DataFrame, usize)> {
+        let mut iter = DataLogIterator::new(buffer, 0);
+        let mut records = Vec::new();
+        for record_result in iter.by_ref() {
+            records.push(record_result?);
+        }
+
+        let mut state = self.state.clone();
+
+        // First pass over the chunk: update schema and struct registry
+        for record in &records {
+            state.process_schema_record(record)?;
+        }
+        state.register_pending_structs()?;
+
+        // Second pass over the chunk: accumulate data
+        let rows = state.build_rows(&records)?;
+        self.state = state;
+        Ok((rows, iter.offset()))
+    }
+
+    fn consume(&mut self, len: usize) {
+        self.buffer.drain(..len);
+        self.bytes_consumed += len as u64;
+    }
+}
+
+impl StreamState {
+    /// Updates schema state from a START, FINISH, or struct schema data record.
+    fn process_schema_record(&mut self, record: &DataLogRecord<'_>) -> Result<()> {
+        if record.is_start() {
+            let start_data = record.get_start_data()?;
+
+            if normalize_type_name(&start_data.type_name) == "structschema" {
+                // Format: "/.schema/struct:StructName" -> "StructName"
+                let simple_name = start_data
+                    .name
+                    .strip_prefix("/.schema/struct:")
+                    .unwrap_or(&start_data.name)
+                    .to_string();
+                self.schema_entries.insert(start_data.entry, simple_name);
+            } else if !self.schema_finished.contains(&start_data.entry)
+                && self.schema.get_column_by_entry(start_data.entry).is_none()
+            {
+                let dtype = PolarsDataType::from_start(
+                    &start_data.type_name,
+                    &start_data.name,
+                    &start_data.metadata,
+                )?;
+                self.schema.add_column(ColumnInfo {
+                    entry_id: start_data.entry,
+                    name: start_data.name,
+                    dtype,
+                    type_name: start_data.type_name,
+                    nullable: true,
+                    metadata: start_data.metadata,
+                });
+            }
+        } else if record.is_finish() {
+            self.schema_finished.insert(record.get_finish_entry()?);
+        } else if !record.is_control() {
+            if let Some(struct_name) = self.schema_entries.get(&record.entry) {
+                // Definitions that don't parse could never register, so leave them out
+                let raw_text = record.get_string();
+                if let Ok(schema_text) = SchemaParser::prepare(&raw_text) {
+                    self.pending_structs
+                        .insert(struct_name.clone(), schema_text.to_string());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Registers pending struct schemas whose dependencies are available.
+    /// Structs with missing dependencies stay pending for a later chunk, but a
+    /// dependency cycle among pending structs is an error since it can never resolve.
+    fn register_pending_structs(&mut self) -> Result<()> {
+        loop {
+            let mut ready = Vec::new();
+            for (name, text) in &self.pending_structs {
+                if self.registry.register(name.clone(), text).is_ok() {
+                    ready.push(name.clone());
+                }
+            }
+
+            if ready.is_empty() {
+                break;
+            }
+
+            for name in ready {
+                self.pending_structs.remove(&name);
+            }
+        }
+
+        if let Some(cycle) = StructRegistry::find_dependency_cycle(&self.pending_structs) {
+            return Err(WpilogError::SchemaError(format!(
+                "Struct dependency cycle: {}",
+                cycle.join(" -> ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Builds a DataFrame from the data records in this chunk.
+    ///
+    /// As in a batch parse, struct columns whose struct hasn't registered yet hold
+    /// raw bytes in a `Binary` column. Later chunks build them as structs once the
+    /// schema arrives.
+    fn build_rows(&mut self, records: &[DataLogRecord<'_>]) -> Result<DataFrame> {
+        let deserializer = StructDeserializer::new(&self.registry);
+
+        let column_names: Vec<String> = self
+            .schema
+            .columns()
+            .iter()
+            .map(|c| c.name.clone())
+            .collect();
+        let column_types: Vec<PolarsDataType> = self
+            .schema
+            .columns()
+            .iter()
+            .map(|c| match &c.dtype {
+                PolarsDataType::Struct(name) | PolarsDataType::StructArray(name)
+                    if self.registry.get(name).is_none() =>
+                {
+                    PolarsDataType::Binary
+                }
+                dtype => dtype.clone(),
+            })
+            .collect();
+
+        let mut builder = DataFrameBuilder::new(column_names, column_types.clone(), records.len())
+            .with_registry(&self.registry);
+
+        let mut current_timestamp: Option<i64> = None;
+        let mut current_values: Vec<Option<PolarsValue>> = vec![None; self.schema.num_columns()];
+
+        for record in records {
+            if record.is_control() {
+                if record.is_finish() {
+                    self.data_finished.insert(record.get_finish_entry()?);
+                }
+                continue;
+            }
+
+            if self.data_finished.contains(&record.entry) {
+                continue;
+            }
+
+            // Skip entries that aren't in the schema (e.g., structschema entries)
+            let column_index = match self.schema.column_index(record.entry) {
+                Some(index) => index,
+                None => continue,
+            };
+
+            // If this is a new timestamp, flush the previous row
+            let timestamp = record.timestamp_i64()?;
+            if let Some(ts) = current_timestamp {
+                if ts != timestamp {
+                    builder.push_row(ts, &current_values);
+                    current_values = vec![None; self.schema.num_columns()];
+                    current_timestamp = Some(timestamp);
+                }
+            } else {
+                current_timestamp = Some(timestamp);
+            }
+
+            let dtype = &column_types[column_index];
+            let value = record.get_value_as_polars(dtype, Some(&deserializer))?;
+            current_values[column_index] = Some(value);
+        }
+
+        if let Some(ts) = current_timestamp {
+            builder.push_row(ts, &current_values);
+        }
+
+        builder.build()
+    }
+}
+
+impl Default for WpilogStreamParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{encode_finish, encode_record, encode_start, wpilog_header};
+
+    /// Helper to create a WPILog file with one int64 entry and two data records
+    fn create_test_wpilog() -> Vec<u8> {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "test", "int64"));
+        data.extend(encode_record(1, 2, &42i64.to_le_bytes()));
+        data.extend(encode_record(1, 3, &43i64.to_le_bytes()));
+        data
+    }
+
+    #[test]
+    fn test_feed_whole_file() {
+        let data = create_test_wpilog();
+        let mut parser = WpilogStreamParser::new();
+
+        let df = parser.feed(&data).unwrap();
+        assert_eq!(df.height(), 2);
+        assert_eq!(df.width(), 2); // timestamp + test
+        assert_eq!(parser.pending_bytes(), 0);
+        assert_eq!(parser.bytes_consumed(), data.len() as u64);
+    }
+
+    #[test]
+    fn test_feed_buffers_partial_record() {
+        let data = create_test_wpilog();
+        let mut parser = WpilogStreamParser::new();
+
+        // Cut the file in the middle of the last data record
+        let split = data.len() - 5;
+        let first = parser.feed(&data[..split]).unwrap();
+        assert_eq!(first.height(), 1);
+        assert_eq!(parser.pending_bytes(), 7);
+
+        let second = parser.feed(&data[split..]).unwrap();
+        assert_eq!(second.height(), 1);
+        let values = second.column("test").unwrap().i64().unwrap();
+        assert_eq!(values.get(0), Some(43));
+    }
+
+    #[test]
+    fn test_feed_byte_by_byte() {
+        let data = create_test_wpilog();
+        let mut parser = WpilogStreamParser::new();
+
+        let total_rows: usize = data
+            .iter()
+            .map(|byte| parser.feed(std::slice::from_ref(byte)).unwrap().height())
+            .sum();
+        assert_eq!(total_rows, 2);
+    }
+
+    #[test]
+    fn test_feed_normalizes_struct_schema_text() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "/.schema/struct:Point", "structschema"));
+        data.extend(encode_record(
+            1,
+            1,
+            b"struct:Point\n  double x; double y  \0",
+        ));
+        data.extend(encode_start(2, "/.schema/struct:Bad", "structschema"));
+        data.extend(encode_record(2, 1, b"int8 flags:99"));
+        data.extend(encode_start(3, "point", "struct:Point"));
+        let mut point = 1.5f64.to_le_bytes().to_vec();
+        point.extend_from_slice(&2.5f64.to_le_bytes());
+        data.extend(encode_record(3, 2, &point));
+
+        let mut parser = WpilogStreamParser::new();
+        let df = parser.feed(&data).unwrap();
+
+        assert_eq!(df.height(), 1);
+        assert!(matches!(
+            df.column("point").unwrap().dtype(),
+            DataType::Struct(_)
+        ));
+        assert!(parser.state.pending_structs.is_empty());
+    }
+
+    #[test]
+    fn test_feed_struct_without_schema() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "pose", "struct:Foo"));
+        data.extend(encode_start(2, "speed", "double"));
+        data.extend(encode_record(1, 2, &[1, 2, 3]));
+        data.extend(encode_record(2, 2, &1.5f64.to_le_bytes()));
+
+        let mut parser = WpilogStreamParser::new();
+        let first = parser.feed(&data).unwrap();
+        assert_eq!(first.column("pose").unwrap().dtype(), &DataType::Binary);
+        assert_eq!(
+            first.column("speed").unwrap().f64().unwrap().get(0),
+            Some(1.5)
+        );
+        assert_eq!(parser.pending_bytes(), 0);
+
+        // Once the schema arrives, later chunks decode the struct
+        let mut more = encode_start(3, "/.schema/struct:Foo", "structschema");
+        more.extend(encode_record(3, 3, b"uint8 a; uint8 b; uint8 c"));
+        more.extend(encode_record(1, 3, &[4, 5, 6]));
+        let second = parser.feed(&more).unwrap();
+        assert!(matches!(
+            second.column("pose").unwrap().dtype(),
+            DataType::Struct(_)
+        ));
+    }
+
+    #[test]
+    fn test_failed_feed_keeps_state() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "speed", "double"));
+        data.extend(encode_start(2, "count", "int64"));
+        data.extend(encode_record(1, 2, &1.5f64.to_le_bytes()));
+        data.extend(encode_finish(1, 3));
+        data.extend(encode_record(2, 3, &[1, 2, 3]));
+
+        let mut parser = WpilogStreamParser::new();
+        assert!(parser.feed(&data).is_err());
+        assert_eq!(parser.schema().num_columns(), 0);
+        assert!(parser.state.schema_finished.is_empty());
+        assert!(parser.state.data_finished.is_empty());
+        assert_eq!(parser.pending_bytes(), data.len() - 12);
+    }
+
+    #[test]
+    fn test_feed_invalid_header() {
+        let mut parser = WpilogStreamParser::new();
+        let result = parser.feed(b"NOTLOG\x00\x01\x00\x00\x00\x00");
+        assert!(matches!(result, Err(WpilogError::InvalidFormat(_))));
+
+        let mut parser = WpilogStreamParser::new();
+        let result = parser.feed(b"WPILOG\x00\x02\x00\x00\x00\x00");
+        assert!(matches!(
+            result,
+            Err(WpilogError::UnsupportedVersion(0x0200))
+        ));
+    }
+}
@@ -0,0 +1,142 @@
+//! Extracting nested fields from struct columns.
+//!
+//! Struct entries such as `Pose2d` become nested Struct columns, so reaching a leaf
+//! like `pose.translation.x` takes a chain of Polars struct accessors. This walks
+//! the chain from a field path instead.
+
+use crate::error::{Result, WpilogError};
+use polars::prelude::*;
+
+/// Returns the field at `path` inside the struct column `column` of `df`, e.g.
+/// `struct_field(&df, "pose", &["translation", "x"])` for `pose.translation.x`.
+///
+/// The returned series is named after the full dotted path, matching the column
+/// names of `flatten_structs`. Rows where the column or any struct along the path is
+/// null are null. An empty `path` returns the column itself.
+///
+/// # Errors
+///
+/// Returns [`WpilogError::InvalidEntry`] if `df` has no `column` column, a field of
+/// `path` does not exist, or a field before the last is not a struct.
+///
+/// # Example
+///
+/// ```no_run
+/// use wpilog_polars::{struct_field, WpilogParser};
+///
+/// let df = WpilogParser::from_file("robot.wpilog")?;
+/// let x = struct_field(&df, "/RealOutputs/Pose", &["translation", "x"])?;
+/// println!("{}", x);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn struct_field(df: &DataFrame, column: &str, path: &[&str]) -> Result<Series> {
+    let mut series = df
+        .column(column)
+        .map_err(|_| WpilogError::InvalidEntry(format!("Column '{}' not found", column)))?
+        .as_materialized_series()
+        .clone();
+    let mut name = column.to_string();
+
+    for field_name in path {
+        let fields = series.struct_().map_err(|_| {
+            WpilogError::InvalidEntry(format!(
+                "'{}' is a {} column, not a struct, so it has no field '{}'",
+                name,
+                series.dtype(),
+                field_name
+            ))
+        })?;
+        let field = fields.field_by_name(field_name).map_err(|_| {
+            WpilogError::InvalidEntry(format!("Struct '{}' has no field '{}'", name, field_name))
+        })?;
+
+        // Null parent rows are null in the field
+        let valid = series.is_not_null();
+        name = format!("{}.{}", name, field_name);
+        let nulls = Series::full_null(name.as_str().into(), field.len(), field.dtype());
+        series = field.zip_with(&valid, &nulls)?;
+    }
+
+    Ok(series.with_name(name.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::struct_support::{PolarsConverter, StructDeserializer, StructRegistry};
+
+    /// Helper to build a DataFrame with a nested `Pose2d` column and a null row
+    fn pose_dataframe() -> DataFrame {
+        let mut registry = StructRegistry::new();
+        registry
+            .register("Translation2d".to_string(), "double x; double y")
+            .unwrap();
+        registry
+            .register("Rotation2d".to_string(), "double value")
+            .unwrap();
+        registry
+            .register(
+                "Pose2d".to_string(),
+                "Translation2d translation; Rotation2d rotation",
+            )
+            .unwrap();
+
+        let deserializer = StructDeserializer::new(&registry);
+        let pose = |x: f64, y: f64, theta: f64| {
+            let data: Vec<u8> = [x, y, theta].iter().flat_map(|v| v.to_le_bytes()).collect();
+            Some(deserializer.deserialize("Pose2d", &data).unwrap())
+        };
+        let values = vec![pose(1.0, 2.0, 0.5), None, pose(3.0, 4.0, 1.5)];
+
+        let poses = PolarsConverter::new(&registry)
+            .optional_values_to_series("Pose2d", &values)
+            .unwrap()
+            .with_name("pose".into());
+        // Mark the middle row null at the struct level, as a sparse column would be
+        let valid = BooleanChunked::from_slice("".into(), &[true, false, true]);
+        let nulls = Series::full_null("pose".into(), 3, poses.dtype());
+        let poses = poses.zip_with(&valid, &nulls).unwrap();
+
+        DataFrame::new(vec![
+            Series::new("timestamp".into(), [1i64, 2, 3]).into(),
+            poses.into(),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_struct_field() {
+        let df = pose_dataframe();
+
+        let x = struct_field(&df, "pose", &["translation", "x"]).unwrap();
+        assert_eq!(x.name(), "pose.translation.x");
+        assert_eq!(
+            x.f64().unwrap().into_iter().collect::<Vec<_>>(),
+            vec![Some(1.0), None, Some(3.0)]
+        );
+
+        let rotation = struct_field(&df, "pose", &["rotation"]).unwrap();
+        assert!(matches!(rotation.dtype(), DataType::Struct(_)));
+        assert_eq!(rotation.null_count(), 1);
+
+        let pose = struct_field(&df, "pose", &[]).unwrap();
+        assert_eq!(pose.name(), "pose");
+    }
+
+    #[test]
+    fn test_struct_field_errors() {
+        let df = pose_dataframe();
+
+        for (column, path) in [
+            ("missing", &["x"][..]),
+            ("pose", &["translation", "z"][..]),
+            ("pose", &["translation", "x", "y"][..]),
+            ("timestamp", &["x"][..]),
+        ] {
+            assert!(matches!(
+                struct_field(&df, column, path),
+                Err(WpilogError::InvalidEntry(_))
+            ));
+        }
+    }
+}
@@ -1,14 +1,21 @@
 //! Binary deserializer for WPILib packed structs.
 
 use byteorder::{ByteOrder, LittleEndian};
+use indexmap::IndexMap;
 use std::cell::RefCell;
-use std::collections::HashMap;
 
 use super::registry::StructRegistry;
 use super::types::*;
 use crate::error::{Result, WpilogError};
 
 /// Deserializer for binary struct data.
+///
+/// # Threading
+///
+/// The deserializer caches the last schema lookup in a `RefCell`, so it is `Send` but
+/// not `Sync`: it can be moved to another thread but not shared between threads. To
+/// deserialize in parallel, give each thread its own copy with [`Clone`], which only
+/// copies the registry reference and starts with an empty cache.
 pub struct StructDeserializer<'a> {
     registry: &'a StructRegistry,
     /// Cache for last schema lookup to avoid repeated HashMap lookups
@@ -63,17 +70,22 @@ impl<'a> StructDeserializer<'a> {
             )));
         }
 
-        // Pre-allocate HashMap with exact capacity to avoid reallocations
-        let mut fields = HashMap::with_capacity(schema.fields.len());
+        // Pre-allocate with exact capacity to avoid reallocations; insertion order
+        // follows the schema's declaration order
+        let mut fields = IndexMap::with_capacity(schema.fields.len());
+
+        // Bytes taken by variable-length array elements so far; every field after
+        // such an array sits this far past its layout offset
+        let mut shift = 0;
 
         for field in &schema.fields {
             match field {
                 StructField::Standard(std_field) => {
-                    let value = self.deserialize_standard_field(std_field, data)?;
+                    let value = self.deserialize_standard_field(std_field, data, &mut shift)?;
                     fields.insert(std_field.name.clone(), value);
                 }
                 StructField::BitField(bitfield) => {
-                    let value = self.deserialize_bitfield(bitfield, data)?;
+                    let value = self.deserialize_bitfield(bitfield, data, shift)?;
                     fields.insert(bitfield.name.clone(), value);
                 }
             }
@@ -85,98 +97,96 @@ impl<'a> StructDeserializer<'a> {
         })
     }
 
-    /// Deserialize a standard field.
-    fn deserialize_standard_field(&self, field: &StandardField, data: &[u8]) -> Result<FieldValue> {
-        let offset = field.offset;
+    /// Deserialize a standard field, adding the element bytes of a variable-length
+    /// array to `shift`.
+    fn deserialize_standard_field(
+        &self,
+        field: &StandardField,
+        data: &[u8],
+        shift: &mut usize,
+    ) -> Result<FieldValue> {
+        let offset = field.offset + *shift;
 
         match &field.field_type {
-            FieldType::Bool => {
-                let val = data[offset] != 0;
-                Ok(FieldValue::Bool(val))
-            }
-            FieldType::Char => {
-                let val = data[offset] as char;
-                Ok(FieldValue::Char(val))
-            }
-            FieldType::Int8 => {
-                let val = data[offset] as i8;
-                Ok(FieldValue::Int8(val))
-            }
-            FieldType::Int16 => {
-                let val = LittleEndian::read_i16(&data[offset..offset + 2]);
-                Ok(FieldValue::Int16(val))
-            }
-            FieldType::Int32 => {
-                let val = LittleEndian::read_i32(&data[offset..offset + 4]);
-                Ok(FieldValue::Int32(val))
-            }
-            FieldType::Int64 => {
-                let val = LittleEndian::read_i64(&data[offset..offset + 8]);
-                Ok(FieldValue::Int64(val))
-            }
-            FieldType::UInt8 => {
-                let val = data[offset];
-                Ok(FieldValue::UInt8(val))
-            }
-            FieldType::UInt16 => {
-                let val = LittleEndian::read_u16(&data[offset..offset + 2]);
-                Ok(FieldValue::UInt16(val))
-            }
-            FieldType::UInt32 => {
-                let val = LittleEndian::read_u32(&data[offset..offset + 4]);
-                Ok(FieldValue::UInt32(val))
-            }
-            FieldType::UInt64 => {
-                let val = LittleEndian::read_u64(&data[offset..offset + 8]);
-                Ok(FieldValue::UInt64(val))
-            }
-            FieldType::Float32 => {
-                let val = LittleEndian::read_f32(&data[offset..offset + 4]);
-                Ok(FieldValue::Float32(val))
-            }
-            FieldType::Float64 => {
-                let val = LittleEndian::read_f64(&data[offset..offset + 8]);
-                Ok(FieldValue::Float64(val))
-            }
-            FieldType::Array { elem_type, length } => {
+            FieldType::Array {
+                elem_type,
+                length: Some(length),
+            } => {
                 let mut values = Vec::with_capacity(*length);
                 let elem_size = self.calculate_elem_size(elem_type)?;
 
                 for i in 0..*length {
                     let elem_offset = offset + i * elem_size;
-                    let value = self.deserialize_array_element(elem_type, &data[elem_offset..])?;
+                    let value =
+                        self.deserialize_element(&field.name, elem_type, data, elem_offset)?;
                     values.push(value);
                 }
 
                 Ok(FieldValue::Array(values))
             }
-            FieldType::Struct(struct_name) => {
-                let nested_schema = self.get_schema(struct_name)?;
+            FieldType::Array {
+                elem_type,
+                length: None,
+            } => {
+                let count =
+                    LittleEndian::read_u32(field_bytes(data, &field.name, offset, 4)?) as usize;
+                let elem_size = self.calculate_elem_size(elem_type)?;
+                let start = offset + 4;
+
+                // Validate the count before allocating for it
+                let elems_size = count
+                    .checked_mul(elem_size)
+                    .filter(|size| data.len().saturating_sub(start) >= *size)
+                    .ok_or_else(|| {
+                        WpilogError::ParseError(format!(
+                            "Variable array '{}' with {} elements overruns struct data of {} bytes",
+                            field.name,
+                            count,
+                            data.len()
+                        ))
+                    })?;
+
+                let mut values = Vec::with_capacity(count);
+                for i in 0..count {
+                    let elem_offset = start + i * elem_size;
+                    let value =
+                        self.deserialize_element(&field.name, elem_type, data, elem_offset)?;
+                    values.push(value);
+                }
 
-                let struct_data = &data[offset..offset + nested_schema.total_size];
-                let nested_value = self.deserialize(struct_name, struct_data)?;
-                Ok(FieldValue::Struct(Box::new(nested_value)))
+                *shift += elems_size;
+                Ok(FieldValue::Array(values))
             }
+            field_type => self.deserialize_element(&field.name, field_type, data, offset),
         }
     }
 
-    /// Deserialize an array element (non-recursive primitive types).
-    fn deserialize_array_element(&self, elem_type: &FieldType, data: &[u8]) -> Result<FieldValue> {
+    /// Deserialize a single non-array value of `field_name` at `offset`.
+    fn deserialize_element(
+        &self,
+        field_name: &str,
+        elem_type: &FieldType,
+        data: &[u8],
+        offset: usize,
+    ) -> Result<FieldValue> {
+        let size = self.calculate_elem_size(elem_type)?;
+        let bytes = field_bytes(data, field_name, offset, size)?;
+
         match elem_type {
-            FieldType::Bool => Ok(FieldValue::Bool(data[0] != 0)),
-            FieldType::Char => Ok(FieldValue::Char(data[0] as char)),
-            FieldType::Int8 => Ok(FieldValue::Int8(data[0] as i8)),
-            FieldType::Int16 => Ok(FieldValue::Int16(LittleEndian::read_i16(&data[0..2]))),
-            FieldType::Int32 => Ok(FieldValue::Int32(LittleEndian::read_i32(&data[0..4]))),
-            FieldType::Int64 => Ok(FieldValue::Int64(LittleEndian::read_i64(&data[0..8]))),
-            FieldType::UInt8 => Ok(FieldValue::UInt8(data[0])),
-            FieldType::UInt16 => Ok(FieldValue::UInt16(LittleEndian::read_u16(&data[0..2]))),
-            FieldType::UInt32 => Ok(FieldValue::UInt32(LittleEndian::read_u32(&data[0..4]))),
-            FieldType::UInt64 => Ok(FieldValue::UInt64(LittleEndian::read_u64(&data[0..8]))),
-            FieldType::Float32 => Ok(FieldValue::Float32(LittleEndian::read_f32(&data[0..4]))),
-            FieldType::Float64 => Ok(FieldValue::Float64(LittleEndian::read_f64(&data[0..8]))),
+            FieldType::Bool => Ok(FieldValue::Bool(bytes[0] != 0)),
+            FieldType::Char => Ok(FieldValue::Char(bytes[0] as char)),
+            FieldType::Int8 => Ok(FieldValue::Int8(bytes[0] as i8)),
+            FieldType::Int16 => Ok(FieldValue::Int16(LittleEndian::read_i16(bytes))),
+            FieldType::Int32 => Ok(FieldValue::Int32(LittleEndian::read_i32(bytes))),
+            FieldType::Int64 => Ok(FieldValue::Int64(LittleEndian::read_i64(bytes))),
+            FieldType::UInt8 => Ok(FieldValue::UInt8(bytes[0])),
+            FieldType::UInt16 => Ok(FieldValue::UInt16(LittleEndian::read_u16(bytes))),
+            FieldType::UInt32 => Ok(FieldValue::UInt32(LittleEndian::read_u32(bytes))),
+            FieldType::UInt64 => Ok(FieldValue::UInt64(LittleEndian::read_u64(bytes))),
+            FieldType::Float32 => Ok(FieldValue::Float32(LittleEndian::read_f32(bytes))),
+            FieldType::Float64 => Ok(FieldValue::Float64(LittleEndian::read_f64(bytes))),
             FieldType::Struct(struct_name) => {
-                let nested_value = self.deserialize(struct_name, data)?;
+                let nested_value = self.deserialize(struct_name, bytes)?;
                 Ok(FieldValue::Struct(Box::new(nested_value)))
             }
             FieldType::Array { .. } => Err(WpilogError::ParseError(
@@ -202,38 +212,45 @@ impl<'a> StructDeserializer<'a> {
         }
     }
 
-    /// Deserialize a bit-field.
-    fn deserialize_bitfield(&self, bitfield: &BitFieldDecl, data: &[u8]) -> Result<FieldValue> {
+    /// Deserialize a bit-field whose storage unit sits `shift` bytes past its layout
+    /// offset.
+    fn deserialize_bitfield(
+        &self,
+        bitfield: &BitFieldDecl,
+        data: &[u8],
+        shift: usize,
+    ) -> Result<FieldValue> {
         let type_width = bitfield.int_type.width();
         let type_size = bitfield.int_type.size();
+        let storage_offset = bitfield.storage_offset + shift;
 
         // Read the storage unit(s)
         let storage_val = if bitfield.spans_units {
             // Field spans multiple storage units, need to read both
-            let unit1 = self.read_integer_at(data, bitfield.storage_offset, &bitfield.int_type)?;
-            let unit2 = self.read_integer_at(
+            let unit1 = read_integer_at(data, &bitfield.name, storage_offset, bitfield.int_type)?;
+            let unit2 = read_integer_at(
                 data,
-                bitfield.storage_offset + type_size,
-                &bitfield.int_type,
+                &bitfield.name,
+                storage_offset + type_size,
+                bitfield.int_type,
             )?;
 
             // Extract bits from both units
             let bits_in_first = type_width - bitfield.bit_offset;
-            let mask1 = ((1u64 << bits_in_first) - 1) << bitfield.bit_offset;
+            let mask1 = low_bits_mask(bits_in_first) << bitfield.bit_offset;
             let val1 = (unit1 & mask1) >> bitfield.bit_offset;
 
             let bits_in_second = bitfield.bit_width - bits_in_first;
-            let mask2 = (1u64 << bits_in_second) - 1;
+            let mask2 = low_bits_mask(bits_in_second);
             let val2 = unit2 & mask2;
 
             val1 | (val2 << bits_in_first)
         } else {
             // Field is contained in a single storage unit
-            let storage =
-                self.read_integer_at(data, bitfield.storage_offset, &bitfield.int_type)?;
+            let storage = read_integer_at(data, &bitfield.name, storage_offset, bitfield.int_type)?;
 
             // Extract the bits
-            let mask = (1u64 << bitfield.bit_width) - 1;
+            let mask = low_bits_mask(bitfield.bit_width);
             (storage >> bitfield.bit_offset) & mask
         };
 
@@ -244,24 +261,58 @@ impl<'a> StructDeserializer<'a> {
         // Convert to appropriate FieldValue
         Ok(FieldValue::Int64(value))
     }
+}
 
-    /// Read an integer value at a specific offset.
-    fn read_integer_at(&self, data: &[u8], offset: usize, int_type: &IntegerType) -> Result<u64> {
-        match int_type {
-            IntegerType::Bool => Ok(data[offset] as u64),
-            IntegerType::Int8 => Ok(data[offset] as i8 as i64 as u64),
-            IntegerType::UInt8 => Ok(data[offset] as u64),
-            IntegerType::Int16 => {
-                Ok(LittleEndian::read_i16(&data[offset..offset + 2]) as i64 as u64)
-            }
-            IntegerType::UInt16 => Ok(LittleEndian::read_u16(&data[offset..offset + 2]) as u64),
-            IntegerType::Int32 => {
-                Ok(LittleEndian::read_i32(&data[offset..offset + 4]) as i64 as u64)
-            }
-            IntegerType::UInt32 => Ok(LittleEndian::read_u32(&data[offset..offset + 4]) as u64),
-            IntegerType::Int64 => Ok(LittleEndian::read_i64(&data[offset..offset + 8]) as u64),
-            IntegerType::UInt64 => Ok(LittleEndian::read_u64(&data[offset..offset + 8])),
-        }
+/// A mask of the low `bits` bits. `bits` may be the full 64, which a plain
+/// `(1 << bits) - 1` would overflow.
+fn low_bits_mask(bits: usize) -> u64 {
+    if bits >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << bits) - 1
+    }
+}
+
+/// Get `len` bytes of `data` starting at `offset`, or a `ParseError` naming the field
+/// being read if the data ends first.
+fn field_bytes<'d>(
+    data: &'d [u8],
+    field_name: &str,
+    offset: usize,
+    len: usize,
+) -> Result<&'d [u8]> {
+    offset
+        .checked_add(len)
+        .and_then(|end| data.get(offset..end))
+        .ok_or_else(|| {
+            WpilogError::ParseError(format!(
+                "Data too short for field '{}': needs {} bytes at offset {}, got {}",
+                field_name,
+                len,
+                offset,
+                data.len()
+            ))
+        })
+}
+
+/// Read an integer storage unit of `field_name` at a specific offset.
+fn read_integer_at(
+    data: &[u8],
+    field_name: &str,
+    offset: usize,
+    int_type: IntegerType,
+) -> Result<u64> {
+    let bytes = field_bytes(data, field_name, offset, int_type.size())?;
+    match int_type {
+        IntegerType::Bool => Ok(bytes[0] as u64),
+        IntegerType::Int8 => Ok(bytes[0] as i8 as i64 as u64),
+        IntegerType::UInt8 => Ok(bytes[0] as u64),
+        IntegerType::Int16 => Ok(LittleEndian::read_i16(bytes) as i64 as u64),
+        IntegerType::UInt16 => Ok(LittleEndian::read_u16(bytes) as u64),
+        IntegerType::Int32 => Ok(LittleEndian::read_i32(bytes) as i64 as u64),
+        IntegerType::UInt32 => Ok(LittleEndian::read_u32(bytes) as u64),
+        IntegerType::Int64 => Ok(LittleEndian::read_i64(bytes) as u64),
+        IntegerType::UInt64 => Ok(LittleEndian::read_u64(bytes)),
     }
 }
 
@@ -277,11 +328,18 @@ impl IntegerType {
     }
 }
 
+impl Clone for StructDeserializer<'_> {
+    fn clone(&self) -> Self {
+        Self::new(self.registry)
+    }
+}
+
 /// A deserialized struct value.
 #[derive(Debug, Clone, PartialEq)]
 pub struct StructValue {
     pub struct_name: String,
-    pub fields: HashMap<String, FieldValue>,
+    /// Field values keyed by name, in schema declaration order.
+    pub fields: IndexMap<String, FieldValue>,
 }
 
 /// A field value (primitive, array, or nested struct).
@@ -303,6 +361,143 @@ pub enum FieldValue {
     Struct(Box<StructValue>),
 }
 
+impl StructValue {
+    /// Returns the value of the named field.
+    pub fn get(&self, name: &str) -> Option<&FieldValue> {
+        self.fields.get(name)
+    }
+
+    /// Returns the named field as an `f64`, if it is a `float` or `double`.
+    ///
+    /// ```
+    /// use wpilog_polars::struct_support::{StructDeserializer, StructRegistry};
+    ///
+    /// let mut registry = StructRegistry::new();
+    /// registry.register("Translation2d".to_string(), "double x; double y")?;
+    /// let mut data = 1.5f64.to_le_bytes().to_vec();
+    /// data.extend_from_slice(&2.5f64.to_le_bytes());
+    ///
+    /// let value = StructDeserializer::new(&registry).deserialize("Translation2d", &data)?;
+    /// assert_eq!(value.get_f64("x"), Some(1.5));
+    /// assert_eq!(value.get_f64("z"), None);
+    /// # Ok::<(), wpilog_polars::WpilogError>(())
+    /// ```
+    pub fn get_f64(&self, name: &str) -> Option<f64> {
+        self.get(name)?.as_f64()
+    }
+
+    /// Returns the named field as an `i64`, if it is an integer that fits.
+    ///
+    /// ```
+    /// use wpilog_polars::struct_support::{StructDeserializer, StructRegistry};
+    ///
+    /// let mut registry = StructRegistry::new();
+    /// registry.register("Status".to_string(), "uint8 mode; double value")?;
+    /// let mut data = vec![3u8];
+    /// data.extend_from_slice(&0.5f64.to_le_bytes());
+    ///
+    /// let value = StructDeserializer::new(&registry).deserialize("Status", &data)?;
+    /// assert_eq!(value.get_i64("mode"), Some(3));
+    /// assert_eq!(value.get_i64("value"), None);
+    /// # Ok::<(), wpilog_polars::WpilogError>(())
+    /// ```
+    pub fn get_i64(&self, name: &str) -> Option<i64> {
+        self.get(name)?.as_i64()
+    }
+
+    /// Returns the named field as a nested struct.
+    ///
+    /// ```
+    /// use wpilog_polars::struct_support::{StructDeserializer, StructRegistry};
+    ///
+    /// let mut registry = StructRegistry::new();
+    /// registry.register("Translation2d".to_string(), "double x; double y")?;
+    /// registry.register("Pose".to_string(), "Translation2d translation; double heading")?;
+    /// let data: Vec<u8> = [1.0f64, 2.0, 0.5].iter().flat_map(|v| v.to_le_bytes()).collect();
+    ///
+    /// let pose = StructDeserializer::new(&registry).deserialize("Pose", &data)?;
+    /// let translation = pose.get_struct("translation").unwrap();
+    /// assert_eq!(translation.get_f64("y"), Some(2.0));
+    /// # Ok::<(), wpilog_polars::WpilogError>(())
+    /// ```
+    pub fn get_struct(&self, name: &str) -> Option<&StructValue> {
+        self.get(name)?.as_struct()
+    }
+
+    /// Returns the elements of the named array field.
+    ///
+    /// ```
+    /// use wpilog_polars::struct_support::{StructDeserializer, StructRegistry};
+    ///
+    /// let mut registry = StructRegistry::new();
+    /// registry.register("Gains".to_string(), "double k[3]")?;
+    /// let data: Vec<u8> = [0.1f64, 0.2, 0.3].iter().flat_map(|v| v.to_le_bytes()).collect();
+    ///
+    /// let gains = StructDeserializer::new(&registry).deserialize("Gains", &data)?;
+    /// let k: Vec<f64> = gains
+    ///     .get_array("k")
+    ///     .unwrap()
+    ///     .iter()
+    ///     .filter_map(|v| v.as_f64())
+    ///     .collect();
+    /// assert_eq!(k, vec![0.1, 0.2, 0.3]);
+    /// # Ok::<(), wpilog_polars::WpilogError>(())
+    /// ```
+    pub fn get_array(&self, name: &str) -> Option<&[FieldValue]> {
+        self.get(name)?.as_array()
+    }
+}
+
+impl FieldValue {
+    /// Returns this value as an `f64`, if it is a `float` or `double`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            FieldValue::Float32(v) => Some(*v as f64),
+            FieldValue::Float64(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns this value as an `i64`, if it is an integer that fits.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            FieldValue::Int8(v) => Some(*v as i64),
+            FieldValue::Int16(v) => Some(*v as i64),
+            FieldValue::Int32(v) => Some(*v as i64),
+            FieldValue::Int64(v) => Some(*v),
+            FieldValue::UInt8(v) => Some(*v as i64),
+            FieldValue::UInt16(v) => Some(*v as i64),
+            FieldValue::UInt32(v) => Some(*v as i64),
+            FieldValue::UInt64(v) => i64::try_from(*v).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns this value as a `bool`, if it is a `bool`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            FieldValue::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns this value as a nested struct.
+    pub fn as_struct(&self) -> Option<&StructValue> {
+        match self {
+            FieldValue::Struct(v) => Some(v.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of this value, if it is an array.
+    pub fn as_array(&self) -> Option<&[FieldValue]> {
+        match self {
+            FieldValue::Array(v) => Some(v.as_slice()),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -337,6 +532,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_field_value_accessors() {
+        assert_eq!(FieldValue::Float32(0.5).as_f64(), Some(0.5));
+        assert_eq!(FieldValue::Int64(1).as_f64(), None);
+        assert_eq!(FieldValue::UInt32(u32::MAX).as_i64(), Some(u32::MAX as i64));
+        assert_eq!(FieldValue::UInt64(u64::MAX).as_i64(), None);
+        assert_eq!(FieldValue::Bool(true).as_i64(), None);
+        assert_eq!(FieldValue::Bool(true).as_bool(), Some(true));
+        assert_eq!(FieldValue::Array(vec![]).as_array(), Some(&[][..]));
+        assert_eq!(FieldValue::Float64(1.0).as_struct(), None);
+    }
+
+    #[test]
+    fn test_clone_per_thread() {
+        let mut registry = StructRegistry::new();
+        registry
+            .register("Point".to_string(), "double x; double y")
+            .unwrap();
+
+        let deserializer = StructDeserializer::new(&registry);
+        let mut data = vec![0u8; 16];
+        LittleEndian::write_f64(&mut data[0..8], 1.5);
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                let local = deserializer.clone();
+                let data = &data;
+                scope.spawn(move || {
+                    let result = local.deserialize("Point", data).unwrap();
+                    assert_eq!(result.fields["x"], FieldValue::Float64(1.5));
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_fields_in_declaration_order() {
+        let mut registry = StructRegistry::new();
+        registry
+            .register(
+                "Ordered".to_string(),
+                "int8 zeta; int8 alpha; int8 mid; int8 beta",
+            )
+            .unwrap();
+
+        let deserializer = StructDeserializer::new(&registry);
+        let result = deserializer.deserialize("Ordered", &[1, 2, 3, 4]).unwrap();
+
+        let names: Vec<&str> = result.fields.keys().map(|k| k.as_str()).collect();
+        assert_eq!(names, vec!["zeta", "alpha", "mid", "beta"]);
+    }
+
     #[test]
     fn test_deserialize_mixed_types() {
         let mut registry = StructRegistry::new();
@@ -351,7 +598,7 @@ mod tests {
         data[0] = 10i8 as u8;
         LittleEndian::write_i16(&mut data[1..3], 1000);
         LittleEndian::write_i32(&mut data[3..7], 100000);
-        LittleEndian::write_f64(&mut data[7..15], 3.14);
+        LittleEndian::write_f64(&mut data[7..15], 2.5);
 
         let result = deserializer.deserialize("Mixed", &data).unwrap();
 
@@ -371,7 +618,7 @@ mod tests {
         }
 
         match result.fields.get("d").unwrap() {
-            FieldValue::Float64(v) => assert!((v - 3.14).abs() < 1e-10),
+            FieldValue::Float64(v) => assert!((v - 2.5).abs() < 1e-10),
             _ => panic!("Expected Float64"),
         }
     }
@@ -408,6 +655,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_deserialize_variable_array() {
+        let mut registry = StructRegistry::new();
+        registry
+            .register(
+                "Samples".to_string(),
+                "int32 id; double values[]; int16 tail; uint8 a:4; uint8 b:4",
+            )
+            .unwrap();
+
+        let deserializer = StructDeserializer::new(&registry);
+
+        // id, count = 3, three doubles, tail, packed bit-fields
+        let mut data = vec![0u8; 4 + 4 + 24 + 2 + 1];
+        LittleEndian::write_i32(&mut data[0..4], 7);
+        LittleEndian::write_u32(&mut data[4..8], 3);
+        LittleEndian::write_f64(&mut data[8..16], 1.5);
+        LittleEndian::write_f64(&mut data[16..24], 2.5);
+        LittleEndian::write_f64(&mut data[24..32], 3.5);
+        LittleEndian::write_i16(&mut data[32..34], -2);
+        data[34] = 0x5A;
+
+        let result = deserializer.deserialize("Samples", &data).unwrap();
+
+        assert_eq!(result.fields.get("id"), Some(&FieldValue::Int32(7)));
+        assert_eq!(
+            result.fields.get("values"),
+            Some(&FieldValue::Array(vec![
+                FieldValue::Float64(1.5),
+                FieldValue::Float64(2.5),
+                FieldValue::Float64(3.5),
+            ]))
+        );
+        assert_eq!(result.fields.get("tail"), Some(&FieldValue::Int16(-2)));
+        assert_eq!(result.fields.get("a"), Some(&FieldValue::Int64(0xA)));
+        assert_eq!(result.fields.get("b"), Some(&FieldValue::Int64(0x5)));
+
+        // An empty array takes only the count
+        let mut empty = vec![0u8; 4 + 4 + 2 + 1];
+        LittleEndian::write_i16(&mut empty[8..10], 9);
+        let result = deserializer.deserialize("Samples", &empty).unwrap();
+        assert_eq!(
+            result.fields.get("values"),
+            Some(&FieldValue::Array(vec![]))
+        );
+        assert_eq!(result.fields.get("tail"), Some(&FieldValue::Int16(9)));
+    }
+
+    #[test]
+    fn test_variable_array_count_overruns_data() {
+        let mut registry = StructRegistry::new();
+        registry
+            .register("Samples".to_string(), "double values[]; int16 tail")
+            .unwrap();
+
+        let deserializer = StructDeserializer::new(&registry);
+
+        // Count claims two doubles but only one follows
+        let mut data = vec![0u8; 4 + 8 + 2];
+        LittleEndian::write_u32(&mut data[0..4], 2);
+        assert!(matches!(
+            deserializer.deserialize("Samples", &data),
+            Err(WpilogError::ParseError(_))
+        ));
+
+        // Elements fit but the fields after them do not
+        LittleEndian::write_u32(&mut data[0..4], 1);
+        data.truncate(4 + 8 + 1);
+        assert!(matches!(
+            deserializer.deserialize("Samples", &data),
+            Err(WpilogError::ParseError(_))
+        ));
+    }
+
     #[test]
     fn test_deserialize_bitfield() {
         let mut registry = StructRegistry::new();
@@ -434,6 +755,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_deserialize_full_width_bitfields() {
+        let mut registry = StructRegistry::new();
+        registry
+            .register("Wide".to_string(), "uint64 wide:64; int32 full:32")
+            .unwrap();
+
+        let deserializer = StructDeserializer::new(&registry);
+
+        let mut data = 0x0123_4567_89AB_CDEFu64.to_le_bytes().to_vec();
+        data.extend_from_slice(&(-1i32).to_le_bytes());
+
+        let result = deserializer.deserialize("Wide", &data).unwrap();
+
+        match result.fields.get("wide").unwrap() {
+            FieldValue::Int64(v) => assert_eq!(*v, 0x0123_4567_89AB_CDEF),
+            _ => panic!("Expected Int64"),
+        }
+
+        // Bit-fields are not sign-extended
+        match result.fields.get("full").unwrap() {
+            FieldValue::Int64(v) => assert_eq!(*v, 0xFFFF_FFFF),
+            _ => panic!("Expected Int64"),
+        }
+    }
+
+    #[test]
+    fn test_low_bits_mask() {
+        assert_eq!(low_bits_mask(1), 0b1);
+        assert_eq!(low_bits_mask(32), 0xFFFF_FFFF);
+        assert_eq!(low_bits_mask(63), u64::MAX >> 1);
+        assert_eq!(low_bits_mask(64), u64::MAX);
+    }
+
     #[test]
     fn test_deserialize_nested_struct() {
         let mut registry = StructRegistry::new();
@@ -457,7 +812,7 @@ mod tests {
         let mut data = vec![0u8; 24];
         LittleEndian::write_f64(&mut data[0..8], 1.0);
         LittleEndian::write_f64(&mut data[8..16], 2.0);
-        LittleEndian::write_f64(&mut data[16..24], 3.14);
+        LittleEndian::write_f64(&mut data[16..24], 2.5);
 
         let result = deserializer.deserialize("Pose2d", &data).unwrap();
 
@@ -479,7 +834,7 @@ mod tests {
         }
 
         match result.fields.get("rotation").unwrap() {
-            FieldValue::Float64(v) => assert!((v - 3.14).abs() < 1e-10),
+            FieldValue::Float64(v) => assert!((v - 2.5).abs() < 1e-10),
             _ => panic!("Expected Float64"),
         }
     }
@@ -500,6 +855,28 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_short_data_mid_field() {
+        let mut registry = StructRegistry::new();
+        registry
+            .register("Samples".to_string(), "double values[]; int32 tail")
+            .unwrap();
+
+        let deserializer = StructDeserializer::new(&registry);
+
+        // One element, then `tail` is one byte short
+        let mut data = vec![0u8; 4 + 8 + 3];
+        LittleEndian::write_u32(&mut data[0..4], 1);
+
+        match deserializer.deserialize("Samples", &data) {
+            Err(WpilogError::ParseError(msg)) => {
+                assert!(msg.contains("'tail'"), "{}", msg);
+                assert!(msg.contains("offset 12"), "{}", msg);
+            }
+            other => panic!("Expected ParseError, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_deserialize_struct_array() {
         // Test deserializing multiple structs from a single buffer
@@ -10,8 +10,25 @@ pub struct SchemaParser;
 impl SchemaParser {
     /// Parse a schema definition string.
     /// Example: "double x; double y; double theta"
+    ///
+    /// Padding declarations are left out; see [`parse_declarations`](Self::parse_declarations).
     pub fn parse(schema_text: &str) -> Result<Vec<StructField>> {
-        let mut fields = Vec::new();
+        Ok(Self::parse_declarations(schema_text)?
+            .into_iter()
+            .filter_map(|decl| match decl {
+                Declaration::Field(field) => Some(field),
+                Declaration::Padding(_) => None,
+            })
+            .collect())
+    }
+
+    /// Parse a schema definition string, keeping padding declarations.
+    ///
+    /// Besides the WPILib field declarations, `pad[N]` (optionally followed by a
+    /// name, e.g. `pad[3] reserved`) declares N bytes of padding, as some writers
+    /// emit for reserved space. Example: `"uint8 mode; pad[3]; float value"`
+    pub fn parse_declarations(schema_text: &str) -> Result<Vec<Declaration>> {
+        let mut declarations = Vec::new();
 
         // Split by semicolons and parse each declaration
         for decl in schema_text.split(';') {
@@ -20,11 +37,47 @@ impl SchemaParser {
                 continue; // Skip empty declarations
             }
 
-            let field = Self::parse_declaration(trimmed)?;
-            fields.push(field);
+            let declaration = match Self::parse_padding(trimmed)? {
+                Some(size) => Declaration::Padding(size),
+                None => Declaration::Field(Self::parse_declaration(trimmed)?),
+            };
+            declarations.push(declaration);
         }
 
-        Ok(fields)
+        Ok(declarations)
+    }
+
+    /// Normalize raw schema text read from a `structschema` entry.
+    /// Strips a leading byte-order mark, surrounding whitespace, NUL terminators, and
+    /// a leading type header line such as `struct:Point` that some writers prepend.
+    pub fn normalize(schema_text: &str) -> &str {
+        let text = Self::trim_schema_text(schema_text);
+
+        match text.split_once('\n') {
+            Some((header, rest))
+                if !header.contains(';')
+                    && (header.trim_start().starts_with("struct:")
+                        || header.trim_start().starts_with("structschema")) =>
+            {
+                Self::trim_schema_text(rest)
+            }
+            _ => text,
+        }
+    }
+
+    /// Strips a leading byte-order mark, surrounding whitespace, and NUL terminators.
+    fn trim_schema_text(schema_text: &str) -> &str {
+        schema_text
+            .trim_start_matches('\u{feff}')
+            .trim_matches(|c: char| c.is_whitespace() || c == '\0')
+    }
+
+    /// Normalize raw schema text and check that it parses, returning the text to
+    /// register. Every path that collects `structschema` records goes through this.
+    pub fn prepare(schema_text: &str) -> Result<&str> {
+        let text = Self::normalize(schema_text);
+        Self::parse_declarations(text)?;
+        Ok(text)
     }
 
     /// Parse a single declaration.
@@ -39,6 +92,32 @@ impl SchemaParser {
         }
     }
 
+    /// Parse a `pad[N]` declaration into its size, or return `None` for any other
+    /// declaration.
+    fn parse_padding(decl: &str) -> Result<Option<usize>> {
+        let Some(rest) = decl
+            .strip_prefix("pad")
+            .and_then(|rest| rest.trim_start().strip_prefix('['))
+        else {
+            return Ok(None);
+        };
+
+        let (size_str, name) = rest
+            .split_once(']')
+            .ok_or_else(|| WpilogError::ParseError(format!("Invalid padding syntax: {}", decl)))?;
+        if name.split_whitespace().count() > 1 {
+            return Err(WpilogError::ParseError(format!(
+                "Invalid padding syntax: {}",
+                decl
+            )));
+        }
+
+        let size = size_str.trim().parse::<usize>().map_err(|_| {
+            WpilogError::ParseError(format!("Invalid padding size: {}", size_str.trim()))
+        })?;
+        Ok(Some(size))
+    }
+
     /// Parse a standard (non-bit-field) declaration.
     fn parse_standard_declaration(decl: &str) -> Result<StructField> {
         // Format: [enum {...}] type name[size]
@@ -70,10 +149,15 @@ impl SchemaParser {
                 )));
             }
 
+            // An empty size (`name[]`) declares a length-prefixed variable array
             let size_str = array_part[1..array_part.len() - 1].trim();
-            let length = size_str.parse::<usize>().map_err(|_| {
-                WpilogError::ParseError(format!("Invalid array size: {}", size_str))
-            })?;
+            let length = if size_str.is_empty() {
+                None
+            } else {
+                Some(size_str.parse::<usize>().map_err(|_| {
+                    WpilogError::ParseError(format!("Invalid array size: {}", size_str))
+                })?)
+            };
 
             let elem_type = Self::parse_type(type_str)?;
             let field_type = FieldType::Array {
@@ -307,7 +391,27 @@ mod tests {
                 match &f.field_type {
                     FieldType::Array { elem_type, length } => {
                         assert_eq!(**elem_type, FieldType::Float64);
-                        assert_eq!(*length, 4);
+                        assert_eq!(*length, Some(4));
+                    }
+                    _ => panic!("Expected array type"),
+                }
+            }
+            _ => panic!("Expected standard field"),
+        }
+    }
+
+    #[test]
+    fn test_parse_variable_array() {
+        let fields = SchemaParser::parse("int32 id; double samples[]").unwrap();
+        assert_eq!(fields.len(), 2);
+
+        match &fields[1] {
+            StructField::Standard(f) => {
+                assert_eq!(f.name, "samples");
+                match &f.field_type {
+                    FieldType::Array { elem_type, length } => {
+                        assert_eq!(**elem_type, FieldType::Float64);
+                        assert_eq!(*length, None);
                     }
                     _ => panic!("Expected array type"),
                 }
@@ -362,6 +466,73 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_normalize_schema_text() {
+        assert_eq!(
+            SchemaParser::normalize("\u{feff}  double x; double y\n\0"),
+            "double x; double y"
+        );
+        assert_eq!(SchemaParser::normalize("double x"), "double x");
+        assert_eq!(
+            SchemaParser::normalize("struct:Point\n double x; double y "),
+            "double x; double y"
+        );
+        assert_eq!(
+            SchemaParser::normalize("double x;\ndouble y"),
+            "double x;\ndouble y"
+        );
+    }
+
+    #[test]
+    fn test_prepare_schema_text() {
+        assert_eq!(
+            SchemaParser::prepare("structschema\ndouble x\0").unwrap(),
+            "double x"
+        );
+        assert!(SchemaParser::prepare("int8 flags:99").is_err());
+    }
+
+    #[test]
+    fn test_field_type_display() {
+        let fields =
+            SchemaParser::parse("float32 a; uint16 b[3]; Translation2d c[]; int8 d:3").unwrap();
+        let types: Vec<String> = fields
+            .iter()
+            .map(|field| match field {
+                StructField::Standard(f) => f.field_type.to_string(),
+                StructField::BitField(b) => format!("{}:{}", b.int_type, b.bit_width),
+            })
+            .collect();
+        assert_eq!(types, ["float", "uint16[3]", "Translation2d[]", "int8:3"]);
+    }
+
+    #[test]
+    fn test_parse_padding() {
+        let declarations =
+            SchemaParser::parse_declarations("uint8 mode; pad[3]; float value; pad [2] reserved")
+                .unwrap();
+        assert_eq!(declarations.len(), 4);
+        assert!(matches!(declarations[1], Declaration::Padding(3)));
+        assert!(matches!(declarations[3], Declaration::Padding(2)));
+
+        // Padding is not a field
+        let fields = SchemaParser::parse("uint8 mode; pad[3]; float value").unwrap();
+        assert_eq!(fields.len(), 2);
+
+        // A struct type named `pad` is still a field
+        let fields = SchemaParser::parse("pad p").unwrap();
+        match &fields[0] {
+            StructField::Standard(field) => {
+                assert_eq!(field.field_type, FieldType::Struct("pad".to_string()))
+            }
+            _ => panic!("Expected standard field"),
+        }
+
+        for invalid in ["pad[x]", "pad[3", "pad[3] a b"] {
+            assert!(SchemaParser::parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+
     #[test]
     fn test_empty_declarations() {
         let fields = SchemaParser::parse("double x;; ; double y;").unwrap();
@@ -1,21 +1,106 @@
 //! Conversion from struct values to Polars types.
 
+use polars::chunked_array::builder::get_list_builder;
 use polars::prelude::*;
 
 use super::deserializer::{FieldValue, StructValue};
 use super::registry::StructRegistry;
 use super::types::*;
 use crate::error::{Result, WpilogError};
+use crate::options::EnumOutput;
 
 /// Converter for struct values to Polars types.
 pub struct PolarsConverter<'a> {
     registry: &'a StructRegistry,
+    enum_output: EnumOutput,
 }
 
 impl<'a> PolarsConverter<'a> {
     /// Create a new converter with the given registry.
     pub fn new(registry: &'a StructRegistry) -> Self {
-        Self { registry }
+        Self {
+            registry,
+            enum_output: EnumOutput::Raw,
+        }
+    }
+
+    /// Sets how integer fields with an enum specification are built.
+    pub fn with_enum_output(mut self, enum_output: EnumOutput) -> Self {
+        self.enum_output = enum_output;
+        self
+    }
+
+    /// Get the enum specification of a field, if it declares one.
+    fn enum_spec(field: &StructField) -> Option<&EnumSpec> {
+        match field {
+            StructField::Standard(f) => f.enum_spec.as_ref(),
+            StructField::BitField(f) => f.enum_spec.as_ref(),
+        }
+    }
+
+    /// Push the output fields of a struct field: its raw type, a `String` label
+    /// field, or both, depending on the enum output mode.
+    fn push_field_dtype(
+        &self,
+        name: &str,
+        field: &StructField,
+        dtype: DataType,
+        out: &mut Vec<Field>,
+    ) {
+        if Self::enum_spec(field).is_none() || !dtype.is_integer() {
+            out.push(Field::new(name.into(), dtype));
+            return;
+        }
+        match self.enum_output {
+            EnumOutput::Raw => out.push(Field::new(name.into(), dtype)),
+            EnumOutput::Label => out.push(Field::new(name.into(), DataType::String)),
+            EnumOutput::Both => {
+                out.push(Field::new(name.into(), dtype));
+                out.push(Field::new(
+                    format!("{}_label", name).into(),
+                    DataType::String,
+                ));
+            }
+        }
+    }
+
+    /// Push the output series of a struct field built from its raw values, matching
+    /// the fields from `push_field_dtype`.
+    fn push_field_series(
+        &self,
+        field: &StructField,
+        raw: Series,
+        out: &mut Vec<Series>,
+    ) -> Result<()> {
+        let spec = match Self::enum_spec(field) {
+            Some(spec) if raw.dtype().is_integer() && self.enum_output != EnumOutput::Raw => spec,
+            _ => {
+                out.push(raw);
+                return Ok(());
+            }
+        };
+
+        let labels: Vec<Option<String>> = raw
+            .cast(&DataType::Int64)?
+            .i64()?
+            .into_iter()
+            .map(|value| {
+                value.map(|v| {
+                    spec.values
+                        .get(&v)
+                        .cloned()
+                        .unwrap_or_else(|| v.to_string())
+                })
+            })
+            .collect();
+        if self.enum_output == EnumOutput::Both {
+            let label_name = format!("{}_label", raw.name());
+            out.push(raw);
+            out.push(Series::new(label_name.into(), labels));
+        } else {
+            out.push(Series::new(raw.name().clone(), labels));
+        }
+        Ok(())
     }
 
     /// Convert a struct schema to a Polars DataType (Struct).
@@ -30,11 +115,11 @@ impl<'a> PolarsConverter<'a> {
             match field {
                 StructField::Standard(std_field) => {
                     let dtype = self.field_type_to_dtype(&std_field.field_type)?;
-                    fields.push(Field::new(std_field.name.as_str().into(), dtype));
+                    self.push_field_dtype(&std_field.name, field, dtype, &mut fields);
                 }
                 StructField::BitField(bitfield) => {
                     // Bit-fields are always represented as Int64
-                    fields.push(Field::new(bitfield.name.as_str().into(), DataType::Int64));
+                    self.push_field_dtype(&bitfield.name, field, DataType::Int64, &mut fields);
                 }
             }
         }
@@ -87,8 +172,8 @@ impl<'a> PolarsConverter<'a> {
                 WpilogError::ParseError(format!("Field '{}' not found in struct value", field_name))
             })?;
 
-            let series = self.field_value_to_series(field_name, field_value)?;
-            series_vec.push(series);
+            let series = self.field_value_to_series(field_name, field, field_value)?;
+            self.push_field_series(field, series, &mut series_vec)?;
         }
 
         // Create a StructChunked with a single row
@@ -102,7 +187,12 @@ impl<'a> PolarsConverter<'a> {
     }
 
     /// Convert a FieldValue to a Polars Series (single value).
-    fn field_value_to_series(&self, name: &str, value: &FieldValue) -> Result<Series> {
+    fn field_value_to_series(
+        &self,
+        name: &str,
+        field: &StructField,
+        value: &FieldValue,
+    ) -> Result<Series> {
         let series = match value {
             FieldValue::Bool(v) => Series::new(name.into(), [*v].as_slice()),
             FieldValue::Char(v) => {
@@ -119,6 +209,9 @@ impl<'a> PolarsConverter<'a> {
             FieldValue::UInt64(v) => Series::new(name.into(), [*v].as_slice()),
             FieldValue::Float32(v) => Series::new(name.into(), [*v].as_slice()),
             FieldValue::Float64(v) => Series::new(name.into(), [*v].as_slice()),
+            FieldValue::Array(values) if values.is_empty() => {
+                self.empty_array_series(name, field)?
+            }
             FieldValue::Array(values) => self.array_to_series(name, values)?,
             FieldValue::Struct(nested) => self.value_to_series(nested)?.with_name(name.into()),
         };
@@ -126,6 +219,21 @@ impl<'a> PolarsConverter<'a> {
         Ok(series)
     }
 
+    /// Convert an empty variable-length array to a single-row Series holding an empty
+    /// list, taking the element type from the field declaration.
+    fn empty_array_series(&self, name: &str, field: &StructField) -> Result<Series> {
+        let elem_dtype = match field {
+            StructField::Standard(StandardField {
+                field_type: FieldType::Array { elem_type, .. },
+                ..
+            }) => self.field_type_to_dtype(elem_type)?,
+            _ => return Err(WpilogError::ParseError("Empty array".to_string())),
+        };
+
+        let elem_series = Series::new_empty("".into(), &elem_dtype);
+        Ok(Series::new(name.into(), &[elem_series]))
+    }
+
     /// Convert an array of FieldValues to a Polars Series with Array dtype.
     fn array_to_series(&self, name: &str, values: &[FieldValue]) -> Result<Series> {
         if values.is_empty() {
@@ -270,50 +378,44 @@ impl<'a> PolarsConverter<'a> {
             return Ok(Series::new_empty(PlSmallStr::from(""), &dtype));
         }
 
+        let rows: Vec<Option<&StructValue>> = values.iter().map(Some).collect();
+        self.rows_to_series(struct_name, &rows)
+    }
+
+    /// Convert rows of struct values, with `None` for null rows, to a struct Series.
+    ///
+    /// Each field is built from all rows at once rather than appended row by row.
+    fn rows_to_series(&self, struct_name: &str, rows: &[Option<&StructValue>]) -> Result<Series> {
         let schema = self.registry.get(struct_name).ok_or_else(|| {
             WpilogError::SchemaError(format!("Struct '{}' not found in registry", struct_name))
         })?;
 
-        // Collect all values for each field
         let mut field_series_vec = Vec::new();
-
         for field in &schema.fields {
             let field_name = match field {
                 StructField::Standard(f) => &f.name,
                 StructField::BitField(f) => &f.name,
             };
 
-            // Collect values for this field from all struct values
-            let mut series_vec = Vec::new();
-            for value in values {
-                let field_value = value.fields.get(field_name).ok_or_else(|| {
-                    WpilogError::ParseError(format!(
-                        "Field '{}' not found in struct value",
-                        field_name
-                    ))
-                })?;
-
-                let series = self.field_value_to_series(field_name, field_value)?;
-                series_vec.push(series);
-            }
-
-            // Concatenate all series for this field
-            let concatenated = if series_vec.len() == 1 {
-                series_vec.into_iter().next().unwrap()
-            } else {
-                // Concatenate series vertically using append
-                let mut base = series_vec[0].clone();
-                for s in &series_vec[1..] {
-                    base.append(s)?;
-                }
-                base
-            };
-
-            field_series_vec.push(concatenated);
+            let field_values = rows
+                .iter()
+                .map(|row| match row {
+                    Some(value) => value.fields.get(field_name).map(Some).ok_or_else(|| {
+                        WpilogError::ParseError(format!(
+                            "Field '{}' not found in struct value",
+                            field_name
+                        ))
+                    }),
+                    None => Ok(None),
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let series =
+                self.field_to_series(field_name, &self.field_dtype(field)?, &field_values)?;
+            self.push_field_series(field, series, &mut field_series_vec)?;
         }
 
-        // Create a StructChunked from the field series
-        // The second parameter should be the length of the series (number of rows), not number of fields
+        // The second parameter is the number of rows, not the number of fields
         let len = field_series_vec.first().map(|s| s.len()).unwrap_or(0);
         let series_refs: Vec<&Series> = field_series_vec.iter().collect();
         let struct_chunked =
@@ -322,8 +424,122 @@ impl<'a> PolarsConverter<'a> {
         Ok(struct_chunked.into_series())
     }
 
+    /// Convert one field's value in each row, with `None` for null rows, to a Series.
+    ///
+    /// Primitive values are collected into a typed vector and built in one step.
+    /// Nested structs are built recursively. Arrays are built by converting every
+    /// row's elements as one Series and slicing it into a List column. `dtype` is the
+    /// field's type, used when every row is null.
+    fn field_to_series(
+        &self,
+        name: &str,
+        dtype: &DataType,
+        values: &[Option<&FieldValue>],
+    ) -> Result<Series> {
+        macro_rules! collect_primitive {
+            ($variant:ident) => {
+                values
+                    .iter()
+                    .map(|value| match value {
+                        Some(FieldValue::$variant(v)) => Ok(Some(*v)),
+                        None => Ok(None),
+                        Some(_) => Err(WpilogError::ParseError(format!(
+                            "Inconsistent values for field '{}'",
+                            name
+                        ))),
+                    })
+                    .collect::<Result<Vec<_>>>()?
+            };
+        }
+
+        let series = match values.iter().flatten().next() {
+            None => Series::full_null(name.into(), values.len(), dtype),
+            Some(FieldValue::Bool(_)) => Series::new(name.into(), collect_primitive!(Bool)),
+            Some(FieldValue::Char(_)) => {
+                let chars = collect_primitive!(Char);
+                UInt8Chunked::from_iter_options(
+                    name.into(),
+                    chars.into_iter().map(|c| c.map(|c| c as u8)),
+                )
+                .into_series()
+            }
+            Some(FieldValue::Int8(_)) => Series::new(name.into(), collect_primitive!(Int8)),
+            Some(FieldValue::Int16(_)) => Series::new(name.into(), collect_primitive!(Int16)),
+            Some(FieldValue::Int32(_)) => Series::new(name.into(), collect_primitive!(Int32)),
+            Some(FieldValue::Int64(_)) => Series::new(name.into(), collect_primitive!(Int64)),
+            Some(FieldValue::UInt8(_)) => {
+                UInt8Chunked::from_iter_options(name.into(), collect_primitive!(UInt8).into_iter())
+                    .into_series()
+            }
+            Some(FieldValue::UInt16(_)) => UInt16Chunked::from_iter_options(
+                name.into(),
+                collect_primitive!(UInt16).into_iter(),
+            )
+            .into_series(),
+            Some(FieldValue::UInt32(_)) => Series::new(name.into(), collect_primitive!(UInt32)),
+            Some(FieldValue::UInt64(_)) => Series::new(name.into(), collect_primitive!(UInt64)),
+            Some(FieldValue::Float32(_)) => Series::new(name.into(), collect_primitive!(Float32)),
+            Some(FieldValue::Float64(_)) => Series::new(name.into(), collect_primitive!(Float64)),
+            Some(FieldValue::Struct(first)) => {
+                let nested = values
+                    .iter()
+                    .map(|value| match value {
+                        Some(FieldValue::Struct(v)) => Ok(Some(v.as_ref())),
+                        None => Ok(None),
+                        Some(_) => Err(WpilogError::ParseError(format!(
+                            "Inconsistent values for field '{}'",
+                            name
+                        ))),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                self.rows_to_series(&first.struct_name, &nested)?
+                    .with_name(name.into())
+            }
+            Some(FieldValue::Array(_)) => {
+                let mut elements = Vec::new();
+                let mut lengths = Vec::with_capacity(values.len());
+                for value in values {
+                    match value {
+                        Some(FieldValue::Array(items)) => {
+                            elements.extend(items.iter().map(Some));
+                            lengths.push(Some(items.len()));
+                        }
+                        None => lengths.push(None),
+                        Some(_) => {
+                            return Err(WpilogError::ParseError(format!(
+                                "Inconsistent values for field '{}'",
+                                name
+                            )))
+                        }
+                    }
+                }
+
+                let elem_dtype = match dtype {
+                    DataType::List(inner) => inner.as_ref().clone(),
+                    other => other.clone(),
+                };
+                let flat = self.field_to_series(name, &elem_dtype, &elements)?;
+                let mut builder =
+                    get_list_builder(&elem_dtype, flat.len(), values.len(), name.into());
+                let mut offset = 0;
+                for length in lengths {
+                    match length {
+                        Some(length) => {
+                            builder.append_series(&flat.slice(offset as i64, length))?;
+                            offset += length;
+                        }
+                        None => builder.append_null(),
+                    }
+                }
+                builder.finish().into_series()
+            }
+        };
+
+        Ok(series)
+    }
+
     /// Convert a slice of optional struct values to a Polars Series.
-    /// This preserves null values for sparse data.
+    /// This preserves null values for sparse data: every field of a `None` row is null.
     pub fn optional_values_to_series(
         &self,
         struct_name: &str,
@@ -335,75 +551,17 @@ impl<'a> PolarsConverter<'a> {
             return Ok(Series::new_empty(PlSmallStr::from(""), &dtype));
         }
 
-        let schema = self.registry.get(struct_name).ok_or_else(|| {
-            WpilogError::SchemaError(format!("Struct '{}' not found in registry", struct_name))
-        })?;
-
-        // Collect all values for each field, preserving nulls
-        let mut field_series_vec = Vec::new();
-
-        for field in &schema.fields {
-            let field_name = match field {
-                StructField::Standard(f) => &f.name,
-                StructField::BitField(f) => &f.name,
-            };
-
-            // Collect values for this field from all struct values, with nulls
-            let mut series_vec = Vec::new();
-            for opt_value in values {
-                let series = match opt_value {
-                    Some(value) => {
-                        let field_value = value.fields.get(field_name).ok_or_else(|| {
-                            WpilogError::ParseError(format!(
-                                "Field '{}' not found in struct value",
-                                field_name
-                            ))
-                        })?;
-                        self.field_value_to_series(field_name, field_value)?
-                    }
-                    None => {
-                        // Create a null value for this field
-                        self.create_null_field_series(field_name, field)?
-                    }
-                };
-                series_vec.push(series);
-            }
-
-            // Concatenate all series for this field
-            let concatenated = if series_vec.len() == 1 {
-                series_vec.into_iter().next().unwrap()
-            } else {
-                // Concatenate series vertically using append
-                let mut base = series_vec[0].clone();
-                for s in &series_vec[1..] {
-                    base.append(s)?;
-                }
-                base
-            };
-
-            field_series_vec.push(concatenated);
-        }
-
-        // Create a StructChunked from the field series
-        let len = field_series_vec.first().map(|s| s.len()).unwrap_or(0);
-        let series_refs: Vec<&Series> = field_series_vec.iter().collect();
-        let struct_chunked =
-            StructChunked::from_series(PlSmallStr::from(""), len, series_refs.into_iter())?;
-
-        Ok(struct_chunked.into_series())
+        // A null row is null in every field
+        let rows: Vec<Option<&StructValue>> = values.iter().map(Option::as_ref).collect();
+        self.rows_to_series(struct_name, &rows)
     }
 
-    /// Create a null value series for a field
-    fn create_null_field_series(&self, name: &str, field: &StructField) -> Result<Series> {
+    /// Get the Polars DataType of a field's raw values.
+    fn field_dtype(&self, field: &StructField) -> Result<DataType> {
         match field {
-            StructField::Standard(std_field) => {
-                let dtype = self.field_type_to_dtype(&std_field.field_type)?;
-                Ok(Series::full_null(name.into(), 1, &dtype))
-            }
-            StructField::BitField(_) => {
-                // Bit-fields are represented as Int64
-                Ok(Series::full_null(name.into(), 1, &DataType::Int64))
-            }
+            StructField::Standard(std_field) => self.field_type_to_dtype(&std_field.field_type),
+            // Bit-fields are represented as Int64
+            StructField::BitField(_) => Ok(DataType::Int64),
         }
     }
 }
@@ -464,6 +622,86 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_enum_output() {
+        let mut registry = StructRegistry::new();
+        registry
+            .register(
+                "Status".to_string(),
+                "enum {idle=0, running=1, fault=2} uint8 state:2; uint8 count:6",
+            )
+            .unwrap();
+
+        let deserializer = StructDeserializer::new(&registry);
+        // state=1 (running), count=3; then state=3 (not in the enum), count=0
+        let values = vec![
+            Some(deserializer.deserialize("Status", &[0x0D]).unwrap()),
+            None,
+            Some(deserializer.deserialize("Status", &[0x03]).unwrap()),
+        ];
+
+        let field =
+            |series: &Series, name: &str| series.struct_().unwrap().field_by_name(name).unwrap();
+
+        // Raw keeps only the integer value
+        let raw = PolarsConverter::new(&registry)
+            .optional_values_to_series("Status", &values)
+            .unwrap();
+        let state = field(&raw, "state");
+        assert_eq!(state.dtype(), &DataType::Int64);
+        assert_eq!(
+            state.i64().unwrap().into_iter().collect::<Vec<_>>(),
+            vec![Some(1), None, Some(3)]
+        );
+
+        // Label replaces the value, falling back to decimal outside the enum
+        let converter = PolarsConverter::new(&registry).with_enum_output(EnumOutput::Label);
+        let labeled = converter
+            .optional_values_to_series("Status", &values)
+            .unwrap();
+        assert_eq!(
+            labeled.dtype(),
+            &converter.schema_to_dtype("Status").unwrap()
+        );
+        let state = field(&labeled, "state");
+        assert_eq!(
+            state.str().unwrap().into_iter().collect::<Vec<_>>(),
+            vec![Some("running"), None, Some("3")]
+        );
+
+        // Both keeps the value and adds a label sibling; fields without an enum are
+        // unchanged
+        let converter = PolarsConverter::new(&registry).with_enum_output(EnumOutput::Both);
+        let both = converter
+            .optional_values_to_series("Status", &values)
+            .unwrap();
+        assert_eq!(both.dtype(), &converter.schema_to_dtype("Status").unwrap());
+        assert_eq!(
+            both.dtype(),
+            &DataType::Struct(vec![
+                Field::new("state".into(), DataType::Int64),
+                Field::new("state_label".into(), DataType::String),
+                Field::new("count".into(), DataType::Int64),
+            ])
+        );
+        assert_eq!(
+            field(&both, "state")
+                .i64()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![Some(1), None, Some(3)]
+        );
+        assert_eq!(
+            field(&both, "state_label")
+                .str()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![Some("running"), None, Some("3")]
+        );
+    }
+
     #[test]
     fn test_values_to_series() {
         let mut registry = StructRegistry::new();
@@ -492,6 +730,83 @@ mod tests {
         assert_eq!(series.len(), 2);
     }
 
+    #[test]
+    fn test_values_to_series_matches_schema_dtype() {
+        let mut registry = StructRegistry::new();
+        registry
+            .register("Inner".to_string(), "uint16 id; char tag")
+            .unwrap();
+        registry
+            .register(
+                "Outer".to_string(),
+                "bool ok; uint8 level; int16 delta; float gains[2]; Inner inner",
+            )
+            .unwrap();
+
+        let deserializer = StructDeserializer::new(&registry);
+        let converter = PolarsConverter::new(&registry);
+
+        let encode = |ok: u8, level: u8, delta: i16, id: u16, tag: u8| {
+            let mut data = vec![ok, level];
+            data.extend_from_slice(&delta.to_le_bytes());
+            data.extend_from_slice(&1.5f32.to_le_bytes());
+            data.extend_from_slice(&2.5f32.to_le_bytes());
+            data.extend_from_slice(&id.to_le_bytes());
+            data.push(tag);
+            deserializer.deserialize("Outer", &data).unwrap()
+        };
+        let values = vec![encode(1, 7, -3, 300, b'a'), encode(0, 9, 4, 301, b'b')];
+
+        let series = converter.values_to_series("Outer", &values).unwrap();
+        assert_eq!(series.len(), 2);
+        assert_eq!(series.dtype(), &converter.schema_to_dtype("Outer").unwrap());
+
+        let fields = series.struct_().unwrap();
+        let level = fields.field_by_name("level").unwrap();
+        assert_eq!(
+            level.u8().unwrap().into_iter().collect::<Vec<_>>(),
+            vec![Some(7), Some(9)]
+        );
+        let inner = fields.field_by_name("inner").unwrap();
+        let ids = inner.struct_().unwrap().field_by_name("id").unwrap();
+        assert_eq!(
+            ids.u16().unwrap().into_iter().collect::<Vec<_>>(),
+            vec![Some(300), Some(301)]
+        );
+        let gains = fields.field_by_name("gains").unwrap();
+        assert_eq!(gains.list().unwrap().get_as_series(1).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_variable_array_values_to_series() {
+        let mut registry = StructRegistry::new();
+        registry
+            .register("Samples".to_string(), "double values[]")
+            .unwrap();
+
+        let deserializer = StructDeserializer::new(&registry);
+        let converter = PolarsConverter::new(&registry);
+
+        let mut data1 = vec![0u8; 20];
+        LittleEndian::write_u32(&mut data1[0..4], 2);
+        LittleEndian::write_f64(&mut data1[4..12], 1.0);
+        LittleEndian::write_f64(&mut data1[12..20], 2.0);
+        let value1 = deserializer.deserialize("Samples", &data1).unwrap();
+
+        // Zero-length array still converts, as an empty list
+        let value2 = deserializer.deserialize("Samples", &[0u8; 4]).unwrap();
+
+        let series = converter
+            .values_to_series("Samples", &[value1, value2])
+            .unwrap();
+
+        assert_eq!(series.len(), 2);
+        let values = series.struct_().unwrap().field_by_name("values").unwrap();
+        let lists = values.list().unwrap();
+        assert_eq!(lists.get_as_series(0).unwrap().len(), 2);
+        assert_eq!(lists.get_as_series(1).unwrap().len(), 0);
+    }
+
     #[test]
     fn test_optional_values_to_series() {
         // Test converting optional struct values (for sparse data)
@@ -542,6 +857,56 @@ mod tests {
         assert!(!y_null.get(2).unwrap()); // Third y should not be null
     }
 
+    #[test]
+    fn test_optional_values_to_series_sparse_10k() {
+        let mut registry = StructRegistry::new();
+        registry
+            .register("Sample".to_string(), "double value; int32 count; bool ok")
+            .unwrap();
+
+        let deserializer = StructDeserializer::new(&registry);
+        let converter = PolarsConverter::new(&registry);
+
+        // Every third row holds a value, as for a struct entry logged at a lower rate
+        let rows = 10_000;
+        let values: Vec<Option<StructValue>> = (0..rows)
+            .map(|i| {
+                (i % 3 == 0).then(|| {
+                    let mut data = (i as f64).to_le_bytes().to_vec();
+                    data.extend_from_slice(&(i as i32).to_le_bytes());
+                    data.push((i % 2) as u8);
+                    deserializer.deserialize("Sample", &data).unwrap()
+                })
+            })
+            .collect();
+
+        let series = converter
+            .optional_values_to_series("Sample", &values)
+            .unwrap();
+
+        assert_eq!(series.len(), rows);
+        assert_eq!(
+            series.dtype(),
+            &converter.schema_to_dtype("Sample").unwrap()
+        );
+        let fields = series.struct_().unwrap();
+        let present = rows.div_ceil(3);
+        for name in ["value", "count", "ok"] {
+            let field = fields.field_by_name(name).unwrap();
+            assert_eq!(field.null_count(), rows - present, "field {}", name);
+        }
+
+        let value = fields.field_by_name("value").unwrap();
+        let value = value.f64().unwrap();
+        assert_eq!(value.get(0), Some(0.0));
+        assert_eq!(value.get(1), None);
+        assert_eq!(value.get(9_999), Some(9_999.0));
+        let count = fields.field_by_name("count").unwrap();
+        assert_eq!(count.i32().unwrap().get(3), Some(3));
+        let ok = fields.field_by_name("ok").unwrap();
+        assert_eq!(ok.bool().unwrap().get(3), Some(true));
+    }
+
     #[test]
     fn test_struct_array_to_list_series() {
         // Test converting struct arrays to Polars List(Struct) series
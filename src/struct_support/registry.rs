@@ -1,6 +1,8 @@
+use super::parser::SchemaParser;
 use super::types::*;
 use crate::error::{Result, WpilogError};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
 /// Registry for managing struct schemas and calculating layouts
 #[derive(Clone)]
@@ -17,8 +19,8 @@ impl StructRegistry {
 
     /// Register a struct schema from its text definition
     pub fn register(&mut self, name: String, schema_text: &str) -> Result<()> {
-        let fields = super::parser::SchemaParser::parse(schema_text)?;
-        let layout = self.calculate_layout(&fields)?;
+        let declarations = SchemaParser::parse_declarations(schema_text)?;
+        let layout = self.calculate_layout(&declarations)?;
 
         let schema = StructSchema {
             name: name.clone(),
@@ -30,18 +32,172 @@ impl StructRegistry {
         Ok(())
     }
 
+    /// Build a registry from a directory of `Name.schema` files
+    ///
+    /// Each file holds one struct definition, named after the file stem. Structs may
+    /// reference each other in any order; registration is retried until no further
+    /// progress is made. Returns a `SchemaError` naming any structs left unresolved.
+    pub fn from_schema_dir<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut schema_defs = HashMap::new();
+        for dir_entry in std::fs::read_dir(path)? {
+            let file_path = dir_entry?.path();
+            if file_path.extension().and_then(|e| e.to_str()) != Some("schema") {
+                continue;
+            }
+            let Some(name) = file_path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let text = std::fs::read_to_string(&file_path)?;
+            schema_defs.insert(name.to_string(), SchemaParser::normalize(&text).to_string());
+        }
+
+        let mut registry = Self::new();
+        let mut pending: Vec<&String> = schema_defs.keys().collect();
+
+        // Retry until all structs are registered or no progress is made
+        loop {
+            let start_count = pending.len();
+            pending.retain(|name| {
+                registry
+                    .register((*name).clone(), &schema_defs[*name])
+                    .is_err()
+            });
+            if pending.is_empty() || pending.len() == start_count {
+                break;
+            }
+        }
+
+        if !pending.is_empty() {
+            let mut names: Vec<&str> = pending.iter().map(|n| n.as_str()).collect();
+            names.sort_unstable();
+            return Err(WpilogError::SchemaError(format!(
+                "Unresolved structs in schema directory: {}",
+                names.join(", ")
+            )));
+        }
+
+        Ok(registry)
+    }
+
     /// Get a registered schema by name
     pub fn get(&self, name: &str) -> Option<&StructSchema> {
         self.schemas.get(name)
     }
 
-    /// Calculate the layout (offsets and size) for a set of fields
-    fn calculate_layout(&self, fields: &[StructField]) -> Result<LayoutResult> {
+    /// Get the names of all registered structs, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.schemas.keys().map(|n| n.as_str()).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Get the structs referenced by a schema definition that are not registered,
+    /// sorted and without duplicates
+    pub fn missing_dependencies(&self, schema_text: &str) -> Vec<String> {
+        let mut refs = Vec::new();
+        for field in SchemaParser::parse(schema_text).unwrap_or_default() {
+            if let StructField::Standard(std_field) = field {
+                Self::collect_struct_refs(&std_field.field_type, &mut refs);
+            }
+        }
+        refs.retain(|r| !self.schemas.contains_key(r));
+        refs.sort_unstable();
+        refs.dedup();
+        refs
+    }
+
+    /// Find a cycle among struct definitions that reference each other.
+    ///
+    /// `schema_defs` maps struct names to schema text. Returns the struct names along
+    /// the first cycle found, starting and ending with the same name.
+    pub fn find_dependency_cycle(schema_defs: &HashMap<String, String>) -> Option<Vec<String>> {
+        let mut deps: HashMap<&str, Vec<String>> = HashMap::new();
+        for (name, schema_text) in schema_defs {
+            let mut refs = Vec::new();
+            for field in SchemaParser::parse(schema_text).unwrap_or_default() {
+                if let StructField::Standard(std_field) = field {
+                    Self::collect_struct_refs(&std_field.field_type, &mut refs);
+                }
+            }
+            refs.retain(|r| schema_defs.contains_key(r));
+            deps.insert(name.as_str(), refs);
+        }
+
+        // Visit in sorted order so the reported cycle is deterministic
+        let mut names: Vec<&str> = deps.keys().copied().collect();
+        names.sort_unstable();
+
+        let mut visited = HashSet::new();
+        for name in names {
+            let mut path = Vec::new();
+            if let Some(cycle) = Self::visit_dependencies(name, &deps, &mut visited, &mut path) {
+                return Some(cycle);
+            }
+        }
+
+        None
+    }
+
+    /// Depth-first search for a cycle reachable from `name`.
+    fn visit_dependencies<'a>(
+        name: &'a str,
+        deps: &'a HashMap<&'a str, Vec<String>>,
+        visited: &mut HashSet<&'a str>,
+        path: &mut Vec<&'a str>,
+    ) -> Option<Vec<String>> {
+        if let Some(pos) = path.iter().position(|n| *n == name) {
+            let mut cycle: Vec<String> = path[pos..].iter().map(|n| n.to_string()).collect();
+            cycle.push(name.to_string());
+            return Some(cycle);
+        }
+
+        if !visited.insert(name) {
+            return None;
+        }
+
+        path.push(name);
+        for dep in deps.get(name).into_iter().flatten() {
+            if let Some(cycle) = Self::visit_dependencies(dep, deps, visited, path) {
+                return Some(cycle);
+            }
+        }
+        path.pop();
+
+        None
+    }
+
+    /// Collect the names of structs referenced by a field type
+    fn collect_struct_refs(field_type: &FieldType, refs: &mut Vec<String>) {
+        match field_type {
+            FieldType::Struct(name) => refs.push(name.clone()),
+            FieldType::Array { elem_type, .. } => Self::collect_struct_refs(elem_type, refs),
+            _ => {}
+        }
+    }
+
+    /// Calculate the layout (offsets and size) for a set of declarations. Padding
+    /// advances the offset but adds no field.
+    fn calculate_layout(&self, declarations: &[Declaration]) -> Result<LayoutResult> {
         let mut offset = 0;
         let mut result_fields = Vec::new();
         let mut pending_bitfields: Vec<BitFieldDecl> = Vec::new();
 
-        for field in fields {
+        for declaration in declarations {
+            let field = match declaration {
+                Declaration::Field(field) => field,
+                Declaration::Padding(size) => {
+                    // Padding ends a run of bit-fields like any other declaration
+                    if !pending_bitfields.is_empty() {
+                        let packed = self.pack_bitfields(&pending_bitfields, offset)?;
+                        result_fields.extend(packed.fields);
+                        offset = packed.next_offset;
+                        pending_bitfields.clear();
+                    }
+                    offset += size;
+                    continue;
+                }
+            };
+
             match field {
                 StructField::Standard(std_field) => {
                     // Flush any pending bit-fields first
@@ -93,10 +249,21 @@ impl StructRegistry {
             FieldType::Int32 | FieldType::UInt32 | FieldType::Float32 => Ok(4),
             FieldType::Int64 | FieldType::UInt64 | FieldType::Float64 => Ok(8),
             FieldType::Char => Ok(1),
-            FieldType::Array { elem_type, length } => {
+            FieldType::Array {
+                elem_type,
+                length: Some(length),
+            } => {
                 let elem_size = self.calculate_field_size(elem_type)?;
                 Ok(elem_size * length)
             }
+            FieldType::Array {
+                elem_type,
+                length: None,
+            } => {
+                // Only the 4-byte count is fixed; elements shift the fields after it
+                self.calculate_field_size(elem_type)?;
+                Ok(4)
+            }
             FieldType::Struct(name) => {
                 let schema = self.schemas.get(name).ok_or_else(|| {
                     WpilogError::SchemaError(format!(
@@ -104,6 +271,12 @@ impl StructRegistry {
                         name
                     ))
                 })?;
+                if schema.is_variable_size() {
+                    return Err(WpilogError::SchemaError(format!(
+                        "Struct '{}' has a variable-length array and cannot be nested",
+                        name
+                    )));
+                }
                 Ok(schema.total_size)
             }
         }
@@ -145,7 +318,7 @@ impl StructRegistry {
             let total_bits: usize = group.iter().map(|bf| bf.bit_width).sum();
 
             // Calculate how many storage units we need
-            let num_units = (total_bits + type_width - 1) / type_width;
+            let num_units = total_bits.div_ceil(type_width);
 
             // Create bit-field entries for each field in the group
             let mut bit_offset = 0;
@@ -159,7 +332,7 @@ impl StructRegistry {
                 // The deserializer will handle extracting bits across units
                 let field_with_offset = BitFieldDecl {
                     name: bitfield.name.clone(),
-                    int_type: bitfield.int_type.clone(),
+                    int_type: bitfield.int_type,
                     bit_width: bitfield.bit_width,
                     enum_spec: bitfield.enum_spec.clone(),
                     storage_offset: offset + start_unit * type_size,
@@ -218,6 +391,26 @@ struct PackedBitFields {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::struct_support::{FieldValue, StructDeserializer};
+
+    #[test]
+    fn test_names_and_missing_dependencies() {
+        let mut registry = StructRegistry::new();
+        registry
+            .register("Translation2d".to_string(), "double x; double y")
+            .unwrap();
+        registry
+            .register("Rotation2d".to_string(), "double value")
+            .unwrap();
+
+        assert_eq!(registry.names(), vec!["Rotation2d", "Translation2d"]);
+        assert_eq!(
+            registry.missing_dependencies(
+                "Translation2d t; Twist2d a[2]; Twist2d b; Module m[]; double x"
+            ),
+            vec!["Module", "Twist2d"]
+        );
+    }
 
     #[test]
     fn test_simple_struct_layout() {
@@ -293,7 +486,7 @@ mod tests {
                 assert_eq!(f.size, 32);
                 match &f.field_type {
                     FieldType::Array { elem_type, length } => {
-                        assert_eq!(*length, 4);
+                        assert_eq!(*length, Some(4));
                         assert!(matches!(**elem_type, FieldType::Float64));
                     }
                     _ => panic!("Expected array type"),
@@ -303,6 +496,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_variable_array_field() {
+        let mut registry = StructRegistry::new();
+        registry
+            .register(
+                "Samples".to_string(),
+                "int32 id; double values[]; int16 flags",
+            )
+            .unwrap();
+
+        let schema = registry.get("Samples").unwrap();
+        assert!(schema.is_variable_size());
+        assert_eq!(schema.total_size, 10); // 4 + 4-byte count + 2
+
+        match &schema.fields[1] {
+            StructField::Standard(f) => {
+                assert_eq!(f.offset, 4);
+                assert_eq!(f.size, 4);
+            }
+            _ => panic!("Expected standard field"),
+        }
+
+        // Variable-size structs have no fixed offset within another struct
+        let result = registry.register("Outer".to_string(), "Samples inner; int8 x");
+        assert!(matches!(result, Err(WpilogError::SchemaError(_))));
+    }
+
     #[test]
     fn test_simple_bitfield_packing() {
         let mut registry = StructRegistry::new();
@@ -389,6 +609,48 @@ mod tests {
         assert_eq!(offsets, vec![0, 8, 8, 9]);
     }
 
+    #[test]
+    fn test_padding_shifts_offsets() {
+        let mut registry = StructRegistry::new();
+
+        registry
+            .register(
+                "Padded".to_string(),
+                "uint8 mode; pad[3]; float value; int8 a:4; pad[2] reserved; double x",
+            )
+            .unwrap();
+
+        let schema = registry.get("Padded").unwrap();
+        // uint8(1) + pad(3) + float(4) + int8:4(1) + pad(2) + double(8) = 19 bytes
+        assert_eq!(schema.total_size, 19);
+
+        let offsets: Vec<(&str, usize)> = schema
+            .fields
+            .iter()
+            .map(|f| match f {
+                StructField::Standard(sf) => (sf.name.as_str(), sf.offset),
+                StructField::BitField(bf) => (bf.name.as_str(), bf.storage_offset),
+            })
+            .collect();
+        assert_eq!(
+            offsets,
+            vec![("mode", 0), ("value", 4), ("a", 8), ("x", 11)]
+        );
+
+        // The padded field decodes from its shifted offset
+        let mut data = vec![7u8, 0xAA, 0xAA, 0xAA];
+        data.extend_from_slice(&1.5f32.to_le_bytes());
+        data.extend_from_slice(&[0x05, 0xAA, 0xAA]);
+        data.extend_from_slice(&2.5f64.to_le_bytes());
+        let value = StructDeserializer::new(&registry)
+            .deserialize("Padded", &data)
+            .unwrap();
+        assert_eq!(value.fields.len(), 4);
+        assert!(matches!(value.fields["value"], FieldValue::Float32(v) if v == 1.5));
+        assert!(matches!(value.fields["a"], FieldValue::Int64(5)));
+        assert!(matches!(value.fields["x"], FieldValue::Float64(v) if v == 2.5));
+    }
+
     #[test]
     fn test_nested_struct() {
         let mut registry = StructRegistry::new();
@@ -438,6 +700,69 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_find_dependency_cycle() {
+        let mut defs = HashMap::new();
+        defs.insert("A".to_string(), "B b; double x".to_string());
+        defs.insert("B".to_string(), "A a".to_string());
+        defs.insert("C".to_string(), "double y".to_string());
+
+        let cycle = StructRegistry::find_dependency_cycle(&defs).unwrap();
+        assert_eq!(cycle, vec!["A", "B", "A"]);
+
+        defs.remove("B");
+        assert!(StructRegistry::find_dependency_cycle(&defs).is_none());
+    }
+
+    #[test]
+    fn test_find_self_referential_cycle() {
+        let mut defs = HashMap::new();
+        defs.insert("Node".to_string(), "Node children[2]".to_string());
+
+        let cycle = StructRegistry::find_dependency_cycle(&defs).unwrap();
+        assert_eq!(cycle, vec!["Node", "Node"]);
+    }
+
+    #[test]
+    fn test_from_schema_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Pose2d.schema"),
+            "Translation2d translation; Rotation2d rotation\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("Translation2d.schema"),
+            "double x; double y",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("Rotation2d.schema"), "double value").unwrap();
+        std::fs::write(dir.path().join("README.txt"), "not a schema").unwrap();
+
+        let registry = StructRegistry::from_schema_dir(dir.path()).unwrap();
+        assert_eq!(registry.get("Pose2d").unwrap().total_size, 24);
+        assert!(registry.get("README").is_none());
+    }
+
+    #[test]
+    fn test_from_schema_dir_unresolved() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Pose2d.schema"),
+            "Translation2d t; double r",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("Twist2d.schema"), "double dx; double dy").unwrap();
+
+        match StructRegistry::from_schema_dir(dir.path()) {
+            Err(WpilogError::SchemaError(msg)) => {
+                assert!(msg.contains("Pose2d"), "{}", msg);
+                assert!(!msg.contains("Twist2d"), "{}", msg);
+            }
+            other => panic!("Expected SchemaError, got {:?}", other.err()),
+        }
+    }
+
     #[test]
     fn test_bitfield_spanning_units() {
         let mut registry = StructRegistry::new();
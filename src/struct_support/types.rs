@@ -1,6 +1,7 @@
 //! Core types for WPILib struct support.
 
 use std::collections::HashMap;
+use std::fmt;
 
 /// A complete struct schema definition.
 #[derive(Debug, Clone)]
@@ -10,6 +11,22 @@ pub struct StructSchema {
     pub total_size: usize,
 }
 
+impl StructSchema {
+    /// Returns true if the schema contains a variable-length array field, in which case
+    /// `total_size` is only the minimum encoded size (every such array empty).
+    pub fn is_variable_size(&self) -> bool {
+        self.fields.iter().any(|field| {
+            matches!(
+                field,
+                StructField::Standard(StandardField {
+                    field_type: FieldType::Array { length: None, .. },
+                    ..
+                })
+            )
+        })
+    }
+}
+
 /// A field in a struct (either standard or bit-field).
 #[derive(Debug, Clone)]
 pub enum StructField {
@@ -17,6 +34,15 @@ pub enum StructField {
     BitField(BitFieldDecl),
 }
 
+/// A declaration in a struct schema: a field, or explicit padding.
+#[derive(Debug, Clone)]
+pub enum Declaration {
+    Field(StructField),
+    /// `pad[N]`: N reserved bytes that advance the offset of the following fields
+    /// without producing a field of their own.
+    Padding(usize),
+}
+
 /// A standard field declaration.
 #[derive(Debug, Clone)]
 pub struct StandardField {
@@ -54,9 +80,12 @@ pub enum FieldType {
     UInt64,
     Float32,
     Float64,
+    /// Fixed-size array (`double v[4]`), or a variable-length array (`double v[]`)
+    /// when `length` is `None`. Variable arrays are stored as a 4-byte little-endian
+    /// element count followed by the elements.
     Array {
         elem_type: Box<FieldType>,
-        length: usize,
+        length: Option<usize>,
     },
     Struct(String), // Reference to another struct by name
 }
@@ -98,6 +127,24 @@ impl IntegerType {
     }
 }
 
+impl fmt::Display for IntegerType {
+    /// Formats the type as it is spelled in a struct schema (e.g. `uint16`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            IntegerType::Bool => "bool",
+            IntegerType::Int8 => "int8",
+            IntegerType::Int16 => "int16",
+            IntegerType::Int32 => "int32",
+            IntegerType::Int64 => "int64",
+            IntegerType::UInt8 => "uint8",
+            IntegerType::UInt16 => "uint16",
+            IntegerType::UInt32 => "uint32",
+            IntegerType::UInt64 => "uint64",
+        };
+        f.write_str(name)
+    }
+}
+
 /// Enum specification for integer fields.
 #[derive(Debug, Clone)]
 pub struct EnumSpec {
@@ -116,3 +163,33 @@ impl FieldType {
         }
     }
 }
+
+impl fmt::Display for FieldType {
+    /// Formats the type as it is spelled in a struct schema, with array lengths
+    /// after the element type (e.g. `double[4]` or `Translation2d[]`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldType::Bool => f.write_str("bool"),
+            FieldType::Char => f.write_str("char"),
+            FieldType::Int8 => f.write_str("int8"),
+            FieldType::Int16 => f.write_str("int16"),
+            FieldType::Int32 => f.write_str("int32"),
+            FieldType::Int64 => f.write_str("int64"),
+            FieldType::UInt8 => f.write_str("uint8"),
+            FieldType::UInt16 => f.write_str("uint16"),
+            FieldType::UInt32 => f.write_str("uint32"),
+            FieldType::UInt64 => f.write_str("uint64"),
+            FieldType::Float32 => f.write_str("float"),
+            FieldType::Float64 => f.write_str("double"),
+            FieldType::Array {
+                elem_type,
+                length: Some(length),
+            } => write!(f, "{}[{}]", elem_type, length),
+            FieldType::Array {
+                elem_type,
+                length: None,
+            } => write!(f, "{}[]", elem_type),
+            FieldType::Struct(name) => f.write_str(name),
+        }
+    }
+}
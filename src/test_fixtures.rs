@@ -0,0 +1,59 @@
+//! Builders for small in-memory WPILog files used by the unit tests.
+
+/// Returns a version 1.0 WPILog header with no extra header.
+pub(crate) fn wpilog_header() -> Vec<u8> {
+    b"WPILOG\x00\x01\x00\x00\x00\x00".to_vec()
+}
+
+/// Encodes a record with 1-byte entry, size, and timestamp fields.
+pub(crate) fn encode_record(entry: u8, timestamp: u8, payload: &[u8]) -> Vec<u8> {
+    let mut data = vec![0x00, entry, payload.len() as u8, timestamp];
+    data.extend_from_slice(payload);
+    data
+}
+
+/// Encodes a record with 1-byte entry and size fields and a 4-byte timestamp.
+pub(crate) fn encode_record_wide(entry: u8, timestamp: u32, payload: &[u8]) -> Vec<u8> {
+    let mut data = vec![0x30, entry, payload.len() as u8];
+    data.extend_from_slice(&timestamp.to_le_bytes());
+    data.extend_from_slice(payload);
+    data
+}
+
+/// Returns the payload of a START control record.
+pub(crate) fn start_payload(entry: u32, name: &str, type_name: &str, metadata: &str) -> Vec<u8> {
+    let mut payload = vec![0x00]; // Control type = Start
+    payload.extend_from_slice(&entry.to_le_bytes());
+    for s in [name, type_name, metadata] {
+        payload.extend_from_slice(&(s.len() as u32).to_le_bytes());
+        payload.extend_from_slice(s.as_bytes());
+    }
+    payload
+}
+
+/// Returns the payload of a FINISH control record.
+pub(crate) fn finish_payload(entry: u32) -> Vec<u8> {
+    let mut payload = vec![0x01]; // Control type = Finish
+    payload.extend_from_slice(&entry.to_le_bytes());
+    payload
+}
+
+/// Encodes a START control record at t=1 with empty metadata.
+pub(crate) fn encode_start(entry: u32, name: &str, type_name: &str) -> Vec<u8> {
+    encode_record(0, 1, &start_payload(entry, name, type_name, ""))
+}
+
+/// Encodes a START control record at t=1 with metadata.
+pub(crate) fn encode_start_with_metadata(
+    entry: u32,
+    name: &str,
+    type_name: &str,
+    metadata: &str,
+) -> Vec<u8> {
+    encode_record(0, 1, &start_payload(entry, name, type_name, metadata))
+}
+
+/// Encodes a FINISH control record.
+pub(crate) fn encode_finish(entry: u32, timestamp: u8) -> Vec<u8> {
+    encode_record(0, timestamp, &finish_payload(entry))
+}
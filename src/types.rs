@@ -15,36 +15,90 @@ pub enum PolarsDataType {
     Float64,
     Float32,
     Int64,
+    /// Narrow integer entries (e.g. `uint8`), which some vendors log. Their values
+    /// are decoded into `PolarsValue::Int64` and narrowed when the column is built.
+    Int8,
+    Int16,
+    Int32,
+    UInt8,
+    UInt16,
+    UInt32,
     Boolean,
     String,
+    /// Raw bytes, used for entries of unknown type under `UnknownTypePolicy::Binary`.
+    Binary,
     BooleanArray,
     Int64Array,
     Float32Array,
     Float64Array,
     StringArray,
-    Struct(String),      // Struct with type name (e.g., "Pose2d")
-    StructArray(String), // Array of structs (e.g., "SwerveModuleState[]")
+    Struct(String),        // Struct with type name (e.g., "Pose2d")
+    StructArray(String),   // Array of structs (e.g., "SwerveModuleState[]")
+    Protobuf(String),      // Protobuf message with type name (e.g., "wpi.proto.ProtobufPose2d")
+    ProtobufArray(String), // Array of protobuf messages (e.g., "Pose2d" for "proto:Pose2d[]")
 }
 
 impl PolarsDataType {
     /// Maps a WPILog type string to a PolarsDataType.
     /// Unknown types are treated as String (binary/serialized data).
     pub fn from_wpilog_type(type_name: &str) -> Result<Self> {
-        match type_name {
-            "double" => Ok(PolarsDataType::Float64),
-            "float" => Ok(PolarsDataType::Float32),
-            "int64" => Ok(PolarsDataType::Int64),
-            "boolean" => Ok(PolarsDataType::Boolean),
-            "string" => Ok(PolarsDataType::String),
-            "raw" => Ok(PolarsDataType::String), // Treat raw as string (binary data)
-            "boolean[]" => Ok(PolarsDataType::BooleanArray),
-            "int64[]" => Ok(PolarsDataType::Int64Array),
-            "float[]" => Ok(PolarsDataType::Float32Array),
-            "double[]" => Ok(PolarsDataType::Float64Array),
-            "string[]" => Ok(PolarsDataType::StringArray),
-            "msgpack" => Ok(PolarsDataType::String), // Serialize msgpack as string
-            "json" => Ok(PolarsDataType::String),    // JSON as string
-            "protobuf" => Ok(PolarsDataType::String), // Protobuf as string
+        match Self::from_known_wpilog_type(type_name) {
+            Some(dtype) => Ok(dtype),
+            None => {
+                eprintln!(
+                    "Warning: Unknown WPILog type '{}', treating as binary string",
+                    type_name
+                );
+                Ok(PolarsDataType::String)
+            }
+        }
+    }
+
+    /// Maps a WPILog type string to a PolarsDataType, returning `None` for
+    /// unknown types so the caller can decide how to handle them.
+    ///
+    /// Type strings are normalized first (see [`normalize_type_name`]), so
+    /// `"Double"`, `" int64 "`, and `"Struct:Pose2d"` are recognized.
+    pub fn from_known_wpilog_type(type_name: &str) -> Option<Self> {
+        let type_name = &normalize_type_name(type_name);
+        match type_name.as_str() {
+            "double" => Some(PolarsDataType::Float64),
+            "float" => Some(PolarsDataType::Float32),
+            "int64" => Some(PolarsDataType::Int64),
+            "int8" => Some(PolarsDataType::Int8),
+            "int16" => Some(PolarsDataType::Int16),
+            "int32" => Some(PolarsDataType::Int32),
+            "uint8" => Some(PolarsDataType::UInt8),
+            "uint16" => Some(PolarsDataType::UInt16),
+            "uint32" => Some(PolarsDataType::UInt32),
+            "boolean" => Some(PolarsDataType::Boolean),
+            "string" => Some(PolarsDataType::String),
+            "raw" => Some(PolarsDataType::String), // Treat raw as string (binary data)
+            "boolean[]" => Some(PolarsDataType::BooleanArray),
+            "int64[]" => Some(PolarsDataType::Int64Array),
+            "float[]" => Some(PolarsDataType::Float32Array),
+            "double[]" => Some(PolarsDataType::Float64Array),
+            "string[]" => Some(PolarsDataType::StringArray),
+            "msgpack" => Some(PolarsDataType::String), // Serialize msgpack as string
+            "json" => Some(PolarsDataType::String),    // JSON as string
+            "protobuf" => Some(PolarsDataType::Protobuf(String::new())),
+            // Check for protobuf array types (format: "proto:TypeName[]")
+            _ if type_name.starts_with("proto:") && type_name.ends_with("[]") => {
+                let message_name = type_name
+                    .strip_prefix("proto:")
+                    .unwrap()
+                    .strip_suffix("[]")
+                    .unwrap()
+                    .to_string();
+                Some(PolarsDataType::ProtobufArray(message_name))
+            }
+            // Check for protobuf types (format: "proto:TypeName")
+            _ if type_name.starts_with("proto:") => {
+                let message_name = type_name.strip_prefix("proto:").unwrap().to_string();
+                Some(PolarsDataType::Protobuf(message_name))
+            }
+            // Qualified JSON types (format: "json:SchemaUrl") - JSON as string
+            _ if type_name.starts_with("json:") => Some(PolarsDataType::String),
             // Check for struct array types (format: "struct:TypeName[]")
             _ if type_name.starts_with("struct:") && type_name.ends_with("[]") => {
                 let struct_name = type_name
@@ -53,30 +107,45 @@ impl PolarsDataType {
                     .strip_suffix("[]")
                     .unwrap()
                     .to_string();
-                Ok(PolarsDataType::StructArray(struct_name))
+                Some(PolarsDataType::StructArray(struct_name))
             }
             // Check for struct types (format: "struct:TypeName")
             _ if type_name.starts_with("struct:") => {
                 let struct_name = type_name.strip_prefix("struct:").unwrap().to_string();
-                Ok(PolarsDataType::Struct(struct_name))
+                Some(PolarsDataType::Struct(struct_name))
             }
             // Struct schema entries - store as strings for now (contain text schema definitions)
-            "structschema" => Ok(PolarsDataType::String),
+            "structschema" => Some(PolarsDataType::String),
             // Generic "struct" without type name - treat as binary string
             "struct" => {
                 eprintln!("Warning: Generic 'struct' type without name, treating as binary string");
-                Ok(PolarsDataType::String)
+                Some(PolarsDataType::String)
             }
-            // Unknown/custom types: treat as string (binary/serialized data)
-            // This allows graceful handling of custom WPILog types
-            _ => {
-                eprintln!(
-                    "Warning: Unknown WPILog type '{}', treating as binary string",
-                    type_name
-                );
-                Ok(PolarsDataType::String)
+            // Unknown/custom types are left to the caller
+            _ => None,
+        }
+    }
+
+    /// Maps the type of a START record to a PolarsDataType.
+    ///
+    /// A generic `struct` type names no struct, so the name is recovered from a
+    /// `"struct"` or `"type"` field in the JSON metadata, or from a `struct:Name`
+    /// suffix of the entry name. Otherwise this is the same as `from_wpilog_type`.
+    pub fn from_start(type_name: &str, entry_name: &str, metadata: &str) -> Result<Self> {
+        match Self::from_known_start(type_name, entry_name, metadata) {
+            Some(dtype) => Ok(dtype),
+            None => Self::from_wpilog_type(type_name),
+        }
+    }
+
+    /// Like `from_start`, but returns `None` for unknown types.
+    pub fn from_known_start(type_name: &str, entry_name: &str, metadata: &str) -> Option<Self> {
+        if normalize_type_name(type_name) == "struct" {
+            if let Some(struct_type) = recover_struct_type(entry_name, metadata) {
+                return Self::from_known_wpilog_type(&struct_type);
             }
         }
+        Self::from_known_wpilog_type(type_name)
     }
 
     /// Converts to a Polars DataType.
@@ -85,8 +154,15 @@ impl PolarsDataType {
             PolarsDataType::Float64 => DataType::Float64,
             PolarsDataType::Float32 => DataType::Float32,
             PolarsDataType::Int64 => DataType::Int64,
+            PolarsDataType::Int8 => DataType::Int8,
+            PolarsDataType::Int16 => DataType::Int16,
+            PolarsDataType::Int32 => DataType::Int32,
+            PolarsDataType::UInt8 => DataType::UInt8,
+            PolarsDataType::UInt16 => DataType::UInt16,
+            PolarsDataType::UInt32 => DataType::UInt32,
             PolarsDataType::Boolean => DataType::Boolean,
             PolarsDataType::String => DataType::String,
+            PolarsDataType::Binary => DataType::Binary,
             PolarsDataType::BooleanArray => DataType::List(Box::new(DataType::Boolean)),
             PolarsDataType::Int64Array => DataType::List(Box::new(DataType::Int64)),
             PolarsDataType::Float32Array => DataType::List(Box::new(DataType::Float32)),
@@ -95,6 +171,10 @@ impl PolarsDataType {
             // Structs and struct arrays will be properly converted in the builders
             PolarsDataType::Struct(_) => DataType::String,
             PolarsDataType::StructArray(_) => DataType::String,
+            // Protobuf columns take the type of their decoded values, or String if undecoded
+            PolarsDataType::Protobuf(_) => DataType::String,
+            // Repeated messages have no standard framing, so they are not decoded
+            PolarsDataType::ProtobufArray(_) => DataType::String,
         }
     }
 
@@ -108,6 +188,59 @@ impl PolarsDataType {
                 | PolarsDataType::Float64Array
                 | PolarsDataType::StringArray
                 | PolarsDataType::StructArray(_)
+                | PolarsDataType::ProtobufArray(_)
+        )
+    }
+
+    /// Returns the byte size and signedness of a narrow integer type.
+    pub fn narrow_integer(&self) -> Option<(usize, bool)> {
+        match self {
+            PolarsDataType::Int8 => Some((1, true)),
+            PolarsDataType::Int16 => Some((2, true)),
+            PolarsDataType::Int32 => Some((4, true)),
+            PolarsDataType::UInt8 => Some((1, false)),
+            PolarsDataType::UInt16 => Some((2, false)),
+            PolarsDataType::UInt32 => Some((4, false)),
+            _ => None,
+        }
+    }
+
+    /// Returns the exact payload size of a scalar type, or `None` for variable-size
+    /// types such as strings, arrays, and structs.
+    pub fn fixed_size(&self) -> Option<usize> {
+        match self {
+            PolarsDataType::Float64 | PolarsDataType::Int64 => Some(8),
+            PolarsDataType::Float32 => Some(4),
+            PolarsDataType::Boolean => Some(1),
+            _ => self.narrow_integer().map(|(size, _)| size),
+        }
+    }
+
+    /// Returns the element size of a numeric array type (`int64[]`, `float[]`,
+    /// `double[]`), or `None` for any other type.
+    pub fn numeric_array_element_size(&self) -> Option<usize> {
+        match self {
+            PolarsDataType::Int64Array | PolarsDataType::Float64Array => Some(8),
+            PolarsDataType::Float32Array => Some(4),
+            _ => None,
+        }
+    }
+
+    /// Returns true for scalar numeric and boolean types, which
+    /// `ParseOptions::numeric_as_f64` builds as `Float64`.
+    pub fn is_numeric_scalar(&self) -> bool {
+        matches!(
+            self,
+            PolarsDataType::Float64
+                | PolarsDataType::Float32
+                | PolarsDataType::Int64
+                | PolarsDataType::Int8
+                | PolarsDataType::Int16
+                | PolarsDataType::Int32
+                | PolarsDataType::UInt8
+                | PolarsDataType::UInt16
+                | PolarsDataType::UInt32
+                | PolarsDataType::Boolean
         )
     }
 
@@ -129,6 +262,22 @@ impl PolarsDataType {
         }
     }
 
+    /// Gets the protobuf message name if this is a protobuf type.
+    pub fn protobuf_name(&self) -> Option<&str> {
+        match self {
+            PolarsDataType::Protobuf(name) => Some(name.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Gets the protobuf message name if this is a protobuf array type.
+    pub fn protobuf_array_name(&self) -> Option<&str> {
+        match self {
+            PolarsDataType::ProtobufArray(name) => Some(name.as_str()),
+            _ => None,
+        }
+    }
+
     /// Gets the struct name if this is a struct array type.
     pub fn struct_array_name(&self) -> Option<&str> {
         match self {
@@ -146,6 +295,7 @@ pub enum PolarsValue {
     Int64(i64),
     Boolean(bool),
     String(String),
+    Binary(Vec<u8>),
     BooleanArray(Vec<bool>),
     Int64Array(Vec<i64>),
     Float32Array(Vec<f32>),
@@ -165,6 +315,7 @@ impl PolarsValue {
             PolarsValue::Int64(_) => PolarsDataType::Int64,
             PolarsValue::Boolean(_) => PolarsDataType::Boolean,
             PolarsValue::String(_) => PolarsDataType::String,
+            PolarsValue::Binary(_) => PolarsDataType::Binary,
             PolarsValue::BooleanArray(_) => PolarsDataType::BooleanArray,
             PolarsValue::Int64Array(_) => PolarsDataType::Int64Array,
             PolarsValue::Float32Array(_) => PolarsDataType::Float32Array,
@@ -189,10 +340,140 @@ impl PolarsValue {
     }
 }
 
+/// Normalizes a WPILog type string written by a non-WPILib tool: surrounding
+/// whitespace is trimmed and the type is lowercased, e.g. `" Double "` becomes
+/// `"double"`. For qualified types such as `Struct:Pose2d`, only the prefix before
+/// the first `:` is lowercased, since struct and protobuf names are case-sensitive.
+pub fn normalize_type_name(type_name: &str) -> String {
+    let type_name = type_name.trim();
+    match type_name.split_once(':') {
+        Some((prefix, name)) => format!("{}:{}", prefix.trim().to_ascii_lowercase(), name.trim()),
+        None => type_name.to_ascii_lowercase(),
+    }
+}
+
+/// Recovers a `struct:Name` type string for an entry declared with the generic
+/// `struct` type.
+fn recover_struct_type(entry_name: &str, metadata: &str) -> Option<String> {
+    let metadata = serde_json::from_str::<serde_json::Value>(metadata).ok();
+    let from_metadata = ["struct", "type"]
+        .iter()
+        .filter_map(|key| metadata.as_ref()?.get(key)?.as_str())
+        .map(|value| value.strip_prefix("struct:").unwrap_or(value))
+        .find(|name| is_struct_name(name));
+
+    let from_entry_name = || {
+        let (_, name) = entry_name.rsplit_once("struct:")?;
+        is_struct_name(name).then_some(name)
+    };
+
+    from_metadata
+        .or_else(from_entry_name)
+        .map(|name| format!("struct:{}", name))
+}
+
+/// Returns true for an identifier, optionally followed by `[]`.
+fn is_struct_name(name: &str) -> bool {
+    let name = name.strip_suffix("[]").unwrap_or(name);
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_protobuf_type_mapping() {
+        assert_eq!(
+            PolarsDataType::from_wpilog_type("proto:wpi.proto.ProtobufPose2d").unwrap(),
+            PolarsDataType::Protobuf("wpi.proto.ProtobufPose2d".to_string())
+        );
+        assert_eq!(
+            PolarsDataType::from_wpilog_type("protobuf").unwrap(),
+            PolarsDataType::Protobuf(String::new())
+        );
+    }
+
+    #[test]
+    fn test_protobuf_array_type_mapping() {
+        let dtype = PolarsDataType::from_wpilog_type("proto:Pose2d[]").unwrap();
+        assert_eq!(dtype, PolarsDataType::ProtobufArray("Pose2d".to_string()));
+        assert_eq!(dtype.protobuf_array_name(), Some("Pose2d"));
+        assert_eq!(dtype.protobuf_name(), None);
+        assert!(dtype.is_array());
+        assert_eq!(dtype.to_polars_dtype(), DataType::String);
+    }
+
+    #[test]
+    fn test_qualified_json_type_mapping() {
+        assert_eq!(
+            PolarsDataType::from_wpilog_type("json:https://example.com/schema.json").unwrap(),
+            PolarsDataType::String
+        );
+        assert_eq!(
+            PolarsDataType::from_wpilog_type("json").unwrap(),
+            PolarsDataType::String
+        );
+        assert_eq!(PolarsDataType::String.protobuf_name(), None);
+    }
+
+    #[test]
+    fn test_known_type_mapping() {
+        assert_eq!(
+            PolarsDataType::from_known_wpilog_type("int64"),
+            Some(PolarsDataType::Int64)
+        );
+        assert_eq!(PolarsDataType::from_known_wpilog_type("mytype"), None);
+        assert_eq!(
+            PolarsDataType::from_wpilog_type("mytype").unwrap(),
+            PolarsDataType::String
+        );
+        assert_eq!(PolarsDataType::Binary.to_polars_dtype(), DataType::Binary);
+    }
+
+    #[test]
+    fn test_type_name_normalization() {
+        for (type_name, expected) in [
+            ("Double", PolarsDataType::Float64),
+            ("INT64", PolarsDataType::Int64),
+            ("boolean[] ", PolarsDataType::BooleanArray),
+            ("  Float[]\t", PolarsDataType::Float32Array),
+            (
+                "Struct:Pose2d",
+                PolarsDataType::Struct("Pose2d".to_string()),
+            ),
+            (
+                " STRUCT:Pose2d[] ",
+                PolarsDataType::StructArray("Pose2d".to_string()),
+            ),
+            (
+                "Proto:wpi.proto.ProtobufPose2d",
+                PolarsDataType::Protobuf("wpi.proto.ProtobufPose2d".to_string()),
+            ),
+        ] {
+            assert_eq!(
+                PolarsDataType::from_known_wpilog_type(type_name),
+                Some(expected),
+                "{:?}",
+                type_name
+            );
+        }
+        assert_eq!(normalize_type_name(" Struct: Pose2d "), "struct:Pose2d");
+        assert_eq!(
+            PolarsDataType::from_known_start(" Struct ", "/Pose/struct:Pose2d", ""),
+            Some(PolarsDataType::Struct("Pose2d".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_fixed_size() {
+        assert_eq!(PolarsDataType::Float64.fixed_size(), Some(8));
+        assert_eq!(PolarsDataType::Boolean.fixed_size(), Some(1));
+        assert_eq!(PolarsDataType::UInt16.fixed_size(), Some(2));
+        assert_eq!(PolarsDataType::String.fixed_size(), None);
+        assert_eq!(PolarsDataType::Float64Array.fixed_size(), None);
+    }
+
     #[test]
     fn test_type_mapping() {
         assert_eq!(
@@ -296,6 +577,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_generic_struct_name_recovery() {
+        assert_eq!(
+            PolarsDataType::from_start("struct", "/Pose", r#"{"struct":"Pose2d"}"#).unwrap(),
+            PolarsDataType::Struct("Pose2d".to_string())
+        );
+        assert_eq!(
+            PolarsDataType::from_start("struct", "/Pose", r#"{ "type" : "struct:Pose2d[]" }"#)
+                .unwrap(),
+            PolarsDataType::StructArray("Pose2d".to_string())
+        );
+        assert_eq!(
+            PolarsDataType::from_start("struct", "/Vision/Target struct:Pose3d", "").unwrap(),
+            PolarsDataType::Struct("Pose3d".to_string())
+        );
+
+        // Only top-level keys count, and keys inside string values are ignored
+        assert_eq!(
+            PolarsDataType::from_start(
+                "struct",
+                "/Pose",
+                r#"{"note":"\"struct\":\"Wrong\"","source":{"struct":"Inner"},"struct":"Pose2d"}"#
+            )
+            .unwrap(),
+            PolarsDataType::Struct("Pose2d".to_string())
+        );
+
+        // Unrecoverable names still fall back to String
+        assert_eq!(
+            PolarsDataType::from_start("struct", "/Pose", r#"{"type":"a b"}"#).unwrap(),
+            PolarsDataType::String
+        );
+
+        // Only the generic type is affected
+        assert_eq!(
+            PolarsDataType::from_start("double", "/x struct:Pose2d", "").unwrap(),
+            PolarsDataType::Float64
+        );
+    }
+
     #[test]
     fn test_polars_value_struct_array_dtype() {
         // Test that PolarsValue::StructArray correctly reports its dtype
@@ -303,11 +624,11 @@ mod tests {
 
         let struct_val1 = StructValue {
             struct_name: "TestStruct".to_string(),
-            fields: std::collections::HashMap::new(),
+            fields: indexmap::IndexMap::new(),
         };
         let struct_val2 = StructValue {
             struct_name: "TestStruct".to_string(),
-            fields: std::collections::HashMap::new(),
+            fields: indexmap::IndexMap::new(),
         };
 
         let array_value = PolarsValue::StructArray(vec![struct_val1, struct_val2]);
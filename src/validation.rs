@@ -0,0 +1,282 @@
+//! Dry-run validation of a WPILog file's schema.
+//!
+//! Runs only the schema pass of the converter, so problems such as struct columns
+//! whose schemas can't be resolved are found before a long parse. A second scan
+//! reads record headers only, checking each data record's size against the fixed
+//! size of its entry's type.
+
+use crate::converter::WpilogConverter;
+use crate::datalog::{DataLogReader, StartRecordData};
+use crate::diagnostics::{Diagnostics, UnresolvedStruct};
+use crate::error::{Result, WpilogError};
+use crate::options::ParseOptions;
+use crate::struct_support::StructRegistry;
+use crate::types::PolarsDataType;
+use std::collections::HashMap;
+
+/// The outcome of validating a WPILog file's schema.
+#[derive(Debug, Clone)]
+pub struct ValidationReport {
+    /// Number of entry columns, excluding `timestamp`.
+    pub columns: usize,
+    /// Number of struct columns and struct array columns.
+    pub struct_columns: usize,
+    /// Names of the structs that were registered, sorted.
+    pub registered_structs: Vec<String>,
+    /// Structs that could not be registered, including structs referenced by a
+    /// column but never defined, sorted by name.
+    pub unresolved_structs: Vec<UnresolvedStruct>,
+    /// Entry names used by more than one entry ID, in column order.
+    pub duplicate_names: Vec<String>,
+    /// Data records whose size doesn't match the fixed size of their entry's type,
+    /// in file order.
+    pub size_mismatches: Vec<SizeMismatch>,
+    /// Warnings reported while reading the schema.
+    pub warnings: Vec<String>,
+}
+
+impl ValidationReport {
+    /// Returns true if every struct the file uses could be resolved and every
+    /// fixed-size record has the expected size.
+    pub fn is_valid(&self) -> bool {
+        self.unresolved_structs.is_empty() && self.size_mismatches.is_empty()
+    }
+}
+
+/// A data record whose payload size doesn't match its entry's declared type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeMismatch {
+    /// Name of the record's entry.
+    pub name: String,
+    /// WPILog type string of the entry, e.g. `boolean` or `struct:Pose2d`.
+    pub type_name: String,
+    /// Timestamp of the record in microseconds.
+    pub timestamp: i64,
+    /// Payload size implied by the type, in bytes.
+    pub expected: usize,
+    /// Actual payload size, in bytes.
+    pub actual: usize,
+}
+
+/// Validates the schema of WPILog data without accumulating any values.
+pub fn validate(data: &[u8]) -> Result<ValidationReport> {
+    let reader = DataLogReader::new(data);
+
+    if !reader.is_valid() {
+        return Err(WpilogError::InvalidFormat(
+            "Invalid WPILog file header".to_string(),
+        ));
+    }
+
+    let mut diagnostics = Diagnostics::new();
+    let (registry, schema) = WpilogConverter::build_registry_and_schema(
+        &reader,
+        StructRegistry::new(),
+        &ParseOptions::default(),
+        &mut diagnostics,
+    )?;
+
+    let mut unresolved_structs = diagnostics.unresolved_structs;
+    let mut struct_columns = 0;
+    let mut name_counts: HashMap<&str, usize> = HashMap::new();
+    let mut duplicate_names = Vec::new();
+
+    for column in schema.columns() {
+        let count = name_counts.entry(column.name.as_str()).or_default();
+        *count += 1;
+        if *count == 2 {
+            duplicate_names.push(column.name.clone());
+        }
+
+        let struct_name = match &column.dtype {
+            PolarsDataType::Struct(name) | PolarsDataType::StructArray(name) => name,
+            _ => continue,
+        };
+        struct_columns += 1;
+
+        let known = registry.get(struct_name).is_some()
+            || unresolved_structs.iter().any(|s| &s.name == struct_name);
+        if !known {
+            unresolved_structs.push(UnresolvedStruct {
+                name: struct_name.clone(),
+                missing_dependencies: vec![struct_name.clone()],
+            });
+        }
+    }
+    unresolved_structs.sort_by(|a, b| a.name.cmp(&b.name));
+
+    // Entries with a fixed payload size: scalars and structs without
+    // variable-length arrays. Each record is checked against the START active at
+    // its position, so a reused entry ID is checked against its new type.
+    let fixed_size = |start: &StartRecordData| -> Option<usize> {
+        match PolarsDataType::from_known_start(&start.type_name, &start.name, &start.metadata)? {
+            PolarsDataType::Struct(name) => registry
+                .get(&name)
+                .filter(|s| !s.is_variable_size())
+                .map(|s| s.total_size),
+            dtype => dtype.fixed_size(),
+        }
+    };
+    let mut fixed_sizes: HashMap<u32, (String, String, usize)> = HashMap::new();
+
+    let mut size_mismatches = Vec::new();
+    for record_result in reader.records()? {
+        let record = record_result?;
+        if record.is_start() {
+            // Truncated START records are skipped, as in the schema pass
+            if let Ok(start) = record.get_start_data() {
+                match fixed_size(&start) {
+                    Some(expected) => {
+                        fixed_sizes.insert(start.entry, (start.name, start.type_name, expected));
+                    }
+                    None => {
+                        fixed_sizes.remove(&start.entry);
+                    }
+                }
+            }
+            continue;
+        }
+        if record.is_finish() {
+            if let Ok(entry) = record.get_finish_entry() {
+                fixed_sizes.remove(&entry);
+            }
+            continue;
+        }
+        if record.is_control() {
+            continue;
+        }
+        let Some((name, type_name, expected)) = fixed_sizes.get(&record.entry) else {
+            continue;
+        };
+        let expected = *expected;
+        if record.data.len() != expected {
+            size_mismatches.push(SizeMismatch {
+                name: name.clone(),
+                type_name: type_name.clone(),
+                timestamp: record.timestamp_i64()?,
+                expected,
+                actual: record.data.len(),
+            });
+        }
+    }
+
+    Ok(ValidationReport {
+        columns: schema.num_columns(),
+        struct_columns,
+        registered_structs: registry.names().into_iter().map(String::from).collect(),
+        unresolved_structs,
+        duplicate_names,
+        size_mismatches,
+        warnings: diagnostics.warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{
+        encode_finish, encode_record, encode_start, start_payload, wpilog_header,
+    };
+
+    #[test]
+    fn test_validate() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "/.schema/struct:Point", "structschema"));
+        data.extend(encode_record(1, 1, b"double x; double y"));
+        data.extend(encode_start(2, "/.schema/struct:Pose", "structschema"));
+        data.extend(encode_record(2, 1, b"Point position; Rotation heading"));
+        data.extend(encode_start(3, "point", "struct:Point"));
+        data.extend(encode_start(4, "pose", "struct:Pose"));
+        data.extend(encode_start(5, "modules", "struct:Module[]"));
+        data.extend(encode_start(6, "speed", "double"));
+        data.extend(encode_start(7, "speed", "double"));
+
+        let report = validate(&data).unwrap();
+
+        assert!(!report.is_valid());
+        assert_eq!(report.columns, 5);
+        assert_eq!(report.struct_columns, 3);
+        assert_eq!(report.registered_structs, vec!["Point"]);
+        assert_eq!(
+            report.unresolved_structs,
+            vec![
+                UnresolvedStruct {
+                    name: "Module".to_string(),
+                    missing_dependencies: vec!["Module".to_string()],
+                },
+                UnresolvedStruct {
+                    name: "Pose".to_string(),
+                    missing_dependencies: vec!["Rotation".to_string()],
+                },
+            ]
+        );
+        assert_eq!(report.duplicate_names, vec!["speed"]);
+        assert!(report.size_mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_validate_record_sizes() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "/.schema/struct:Point", "structschema"));
+        data.extend(encode_record(1, 1, b"double x; double y"));
+        data.extend(encode_start(2, "enabled", "boolean"));
+        data.extend(encode_start(3, "point", "struct:Point"));
+        data.extend(encode_start(4, "name", "string"));
+        data.extend(encode_record(2, 2, &[1]));
+        data.extend(encode_record(2, 3, &1.0f64.to_le_bytes()));
+        data.extend(encode_record(3, 3, &[0; 16]));
+        data.extend(encode_record(3, 4, &[0; 12]));
+        data.extend(encode_record(4, 4, b"any length"));
+
+        let report = validate(&data).unwrap();
+
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.size_mismatches,
+            vec![
+                SizeMismatch {
+                    name: "enabled".to_string(),
+                    type_name: "boolean".to_string(),
+                    timestamp: 3,
+                    expected: 1,
+                    actual: 8,
+                },
+                SizeMismatch {
+                    name: "point".to_string(),
+                    type_name: "struct:Point".to_string(),
+                    timestamp: 4,
+                    expected: 16,
+                    actual: 12,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_record_sizes_follow_reused_entry_id() {
+        let mut data = wpilog_header();
+        data.extend(encode_start(1, "enabled", "boolean"));
+        data.extend(encode_record(1, 2, &[1]));
+        data.extend(encode_finish(1, 3));
+        data.extend(encode_record(
+            0,
+            4,
+            &start_payload(1, "speed", "double", ""),
+        ));
+        data.extend(encode_record(1, 5, &1.0f64.to_le_bytes()));
+        data.extend(encode_record(1, 6, &[1]));
+
+        let report = validate(&data).unwrap();
+
+        assert_eq!(
+            report.size_mismatches,
+            vec![SizeMismatch {
+                name: "speed".to_string(),
+                type_name: "double".to_string(),
+                timestamp: 6,
+                expected: 8,
+                actual: 1,
+            }]
+        );
+    }
+}
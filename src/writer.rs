@@ -0,0 +1,730 @@
+//! Writing Polars DataFrames back to WPILog format.
+//!
+//! This module provides:
+//! - `WpilogWriter`: encodes a DataFrame as START and data records
+//! - Mapping from Polars dtypes back to WPILog type strings
+//! - Struct schema generation for struct and struct array columns
+
+use crate::error::{Result, WpilogError};
+use polars::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::Path;
+
+/// Writes a DataFrame with a `timestamp` column as a WPILog file.
+///
+/// Each non-timestamp column becomes one entry, and each non-null value becomes one
+/// data record at its row's timestamp. Rows without any non-null values are not written.
+/// Column types map back to WPILog types as follows:
+///
+/// | Polars Type | WPILog Type |
+/// |-------------|-------------|
+/// | `Boolean` | `boolean` |
+/// | `Int8`, `Int16`, `Int32`, `UInt8`, `UInt16`, `UInt32` | `int8`, `int16`, `int32`, `uint8`, `uint16`, `uint32` |
+/// | `Int64`, `UInt64` | `int64` |
+/// | `Float32` | `float` |
+/// | `Float64` | `double` |
+/// | `String`, `Categorical` | `string` |
+/// | `Binary` | `raw` |
+/// | `List(Boolean)`, `List(Int64)`, `List(Float32)`, `List(Float64)`, `List(String)` | `boolean[]`, `int64[]`, `float[]`, `double[]`, `string[]` |
+/// | `Struct` | `struct:Name` |
+/// | `List(Struct)` | `struct:Name[]` |
+///
+/// `UInt64` values above `i64::MAX` can't be written as `int64` and are an error.
+/// `raw` entries are read back as lossy UTF-8 `String` columns, so `Binary` columns
+/// don't round-trip unchanged.
+///
+/// Struct schemas are generated from the struct dtype. List fields within a struct
+/// become fixed-size arrays, so every value of such a field must have the same length.
+/// Two columns given the same struct name must have the same struct layout.
+#[derive(Debug, Clone, Default)]
+pub struct WpilogWriter {
+    extra_header: String,
+    struct_names: HashMap<String, String>,
+}
+
+impl WpilogWriter {
+    /// Creates a writer with an empty extra header.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the extra header string written after the file header.
+    pub fn with_extra_header(mut self, extra_header: impl Into<String>) -> Self {
+        self.extra_header = extra_header.into();
+        self
+    }
+
+    /// Sets the struct name used for a struct or struct array column.
+    ///
+    /// Defaults to the column name with non-alphanumeric characters replaced by `_`.
+    pub fn with_struct_name(
+        mut self,
+        column: impl Into<String>,
+        struct_name: impl Into<String>,
+    ) -> Self {
+        self.struct_names.insert(column.into(), struct_name.into());
+        self
+    }
+
+    /// Encodes the DataFrame as WPILog bytes.
+    pub fn to_bytes(&self, df: &DataFrame) -> Result<Vec<u8>> {
+        let timestamps = Self::timestamps(df)?;
+        let start_timestamp = timestamps.first().copied().unwrap_or(0);
+
+        // Map each column to a WPILog type, collecting struct schemas along the way
+        let mut struct_defs = Vec::new();
+        let mut columns = Vec::new();
+        for column in df.get_columns() {
+            let name = column.name().as_str();
+            if name == "timestamp" {
+                continue;
+            }
+
+            let series = Self::normalize_series(column.as_materialized_series())?;
+            let type_name = self.wpilog_type(&series, &mut struct_defs)?;
+            columns.push((name.to_string(), type_name, series));
+        }
+
+        let mut out = Vec::new();
+        out.extend_from_slice(b"WPILOG");
+        out.extend_from_slice(&0x0100u16.to_le_bytes());
+        out.extend_from_slice(&(self.extra_header.len() as u32).to_le_bytes());
+        out.extend_from_slice(self.extra_header.as_bytes());
+
+        let mut next_entry = 1u32;
+
+        // Struct schemas come first so readers can register them before use
+        for (struct_name, schema_text) in &struct_defs {
+            let schema_name = format!("/.schema/struct:{}", struct_name);
+            write_start(
+                &mut out,
+                next_entry,
+                &schema_name,
+                "structschema",
+                start_timestamp,
+            )?;
+            write_record(
+                &mut out,
+                next_entry,
+                start_timestamp,
+                schema_text.as_bytes(),
+            )?;
+            next_entry += 1;
+        }
+
+        let mut entries = Vec::with_capacity(columns.len());
+        for (name, type_name, series) in &columns {
+            write_start(&mut out, next_entry, name, type_name, start_timestamp)?;
+            entries.push((next_entry, series, missing_mask(series)?));
+            next_entry += 1;
+        }
+
+        let mut payload = Vec::new();
+        for (row, &timestamp) in timestamps.iter().enumerate() {
+            for (entry, series, missing) in &entries {
+                if missing[row] {
+                    continue;
+                }
+                payload.clear();
+                encode_value(series, row, &mut payload)?;
+                write_record(&mut out, *entry, timestamp, &payload)?;
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Writes the DataFrame in WPILog format to `writer`.
+    pub fn write<W: Write>(&self, df: &DataFrame, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.to_bytes(df)?)?;
+        Ok(())
+    }
+
+    /// Writes the DataFrame in WPILog format to a file.
+    pub fn write_file<P: AsRef<Path>>(&self, df: &DataFrame, path: P) -> Result<()> {
+        std::fs::write(path, self.to_bytes(df)?)?;
+        Ok(())
+    }
+
+    /// Reads the timestamp column as unsigned microseconds.
+    fn timestamps(df: &DataFrame) -> Result<Vec<u64>> {
+        let column = df.column("timestamp").map_err(|_| {
+            WpilogError::SchemaError("DataFrame has no 'timestamp' column".to_string())
+        })?;
+        let column = column.cast(&DataType::Int64)?;
+
+        column
+            .i64()?
+            .into_iter()
+            .enumerate()
+            .map(|(row, ts)| match ts {
+                Some(ts) if ts >= 0 => Ok(ts as u64),
+                _ => Err(WpilogError::ParseError(format!(
+                    "Invalid timestamp at row {}: {:?}",
+                    row, ts
+                ))),
+            })
+            .collect()
+    }
+
+    /// Casts column types without a direct WPILog equivalent to ones that have one.
+    fn normalize_series(series: &Series) -> Result<Series> {
+        let target = match series.dtype() {
+            DataType::Categorical(..) => Some(DataType::String),
+            DataType::UInt64 => Some(DataType::Int64),
+            DataType::List(inner) if inner.is_integer() && **inner != DataType::Int64 => {
+                Some(DataType::List(Box::new(DataType::Int64)))
+            }
+            _ => None,
+        };
+
+        // A strict cast fails instead of nulling values outside the int64 range
+        match target {
+            Some(dtype) => series.strict_cast(&dtype).map_err(|e| {
+                WpilogError::ParseError(format!(
+                    "Column '{}' can't be written as {}: {}",
+                    series.name(),
+                    dtype,
+                    e
+                ))
+            }),
+            None => Ok(series.clone()),
+        }
+    }
+
+    /// Maps a column to its WPILog type string.
+    fn wpilog_type(
+        &self,
+        series: &Series,
+        struct_defs: &mut Vec<(String, String)>,
+    ) -> Result<String> {
+        let type_name = match series.dtype() {
+            DataType::Boolean => "boolean".to_string(),
+            DataType::Int8 => "int8".to_string(),
+            DataType::Int16 => "int16".to_string(),
+            DataType::Int32 => "int32".to_string(),
+            DataType::Int64 => "int64".to_string(),
+            DataType::UInt8 => "uint8".to_string(),
+            DataType::UInt16 => "uint16".to_string(),
+            DataType::UInt32 => "uint32".to_string(),
+            DataType::Float32 => "float".to_string(),
+            DataType::Float64 => "double".to_string(),
+            DataType::String => "string".to_string(),
+            DataType::Binary => "raw".to_string(),
+            DataType::Struct(_) => {
+                let struct_name = self.struct_name(series.name().as_str());
+                register_struct(&struct_name, series, struct_defs)?;
+                format!("struct:{}", struct_name)
+            }
+            DataType::List(inner) => match inner.as_ref() {
+                DataType::Boolean => "boolean[]".to_string(),
+                DataType::Int64 => "int64[]".to_string(),
+                DataType::Float32 => "float[]".to_string(),
+                DataType::Float64 => "double[]".to_string(),
+                DataType::String => "string[]".to_string(),
+                DataType::Struct(_) => {
+                    let struct_name = self.struct_name(series.name().as_str());
+                    let values = series.list()?.get_inner();
+                    register_struct(&struct_name, &values, struct_defs)?;
+                    format!("struct:{}[]", struct_name)
+                }
+                other => return Err(unsupported(series.name().as_str(), series.dtype(), other)),
+            },
+            other => return Err(unsupported(series.name().as_str(), other, other)),
+        };
+
+        Ok(type_name)
+    }
+
+    fn struct_name(&self, column: &str) -> String {
+        self.struct_names
+            .get(column)
+            .cloned()
+            .unwrap_or_else(|| sanitize_struct_name(column))
+    }
+}
+
+fn unsupported(column: &str, dtype: &DataType, inner: &DataType) -> WpilogError {
+    if dtype == inner {
+        WpilogError::SchemaError(format!(
+            "Column '{}' has type {:?}, which has no WPILog equivalent",
+            column, dtype
+        ))
+    } else {
+        WpilogError::SchemaError(format!(
+            "Column '{}' has type {:?}, whose element type {:?} has no WPILog equivalent",
+            column, dtype, inner
+        ))
+    }
+}
+
+/// Converts a column name like "/Drive/Pose" into a struct name like "Drive_Pose".
+fn sanitize_struct_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let trimmed = sanitized.trim_matches('_');
+
+    if trimmed.is_empty() {
+        "Struct".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Generates the schema for a struct series and any nested structs it contains.
+/// Nested schemas are pushed before the schemas that reference them. A name that is
+/// already registered is reused only if its schema is the same.
+fn register_struct(
+    name: &str,
+    series: &Series,
+    struct_defs: &mut Vec<(String, String)>,
+) -> Result<()> {
+    let mut declarations = Vec::new();
+    for field in series.struct_()?.fields_as_series() {
+        declarations.push(struct_field_declaration(name, &field, struct_defs)?);
+    }
+    let schema_text = declarations.join(";");
+
+    match struct_defs.iter().find(|(existing, _)| existing == name) {
+        Some((_, existing_text)) if *existing_text == schema_text => Ok(()),
+        Some((_, existing_text)) => Err(WpilogError::SchemaError(format!(
+            "Struct name '{}' is used for different layouts: '{}' and '{}'",
+            name, existing_text, schema_text
+        ))),
+        None => {
+            struct_defs.push((name.to_string(), schema_text));
+            Ok(())
+        }
+    }
+}
+
+/// Generates a declaration like `double x` or `double values[4]` for a struct field.
+fn struct_field_declaration(
+    struct_name: &str,
+    field: &Series,
+    struct_defs: &mut Vec<(String, String)>,
+) -> Result<String> {
+    let field_name = field.name().as_str();
+
+    if let DataType::List(_) = field.dtype() {
+        let length = fixed_list_length(field)?;
+        let elements = field.list()?.get_inner();
+        let elem_type = struct_field_type(struct_name, field_name, &elements, struct_defs)?;
+        Ok(format!("{} {}[{}]", elem_type, field_name, length))
+    } else {
+        let field_type = struct_field_type(struct_name, field_name, field, struct_defs)?;
+        Ok(format!("{} {}", field_type, field_name))
+    }
+}
+
+fn struct_field_type(
+    struct_name: &str,
+    field_name: &str,
+    series: &Series,
+    struct_defs: &mut Vec<(String, String)>,
+) -> Result<String> {
+    let field_type = match series.dtype() {
+        DataType::Boolean => "bool",
+        DataType::Int8 => "int8",
+        DataType::Int16 => "int16",
+        DataType::Int32 => "int32",
+        DataType::Int64 => "int64",
+        DataType::UInt8 => "uint8",
+        DataType::UInt16 => "uint16",
+        DataType::UInt32 => "uint32",
+        DataType::UInt64 => "uint64",
+        DataType::Float32 => "float",
+        DataType::Float64 => "double",
+        DataType::Struct(_) => {
+            let nested_name = format!("{}_{}", struct_name, field_name);
+            register_struct(&nested_name, series, struct_defs)?;
+            return Ok(nested_name);
+        }
+        other => {
+            return Err(WpilogError::SchemaError(format!(
+                "Struct field '{}.{}' has type {:?}, which has no WPILog struct equivalent",
+                struct_name, field_name, other
+            )))
+        }
+    };
+
+    Ok(field_type.to_string())
+}
+
+/// Returns the common length of a list field's values, which must all match.
+fn fixed_list_length(field: &Series) -> Result<usize> {
+    let lengths: HashSet<usize> = field
+        .list()?
+        .into_iter()
+        .flatten()
+        .map(|values| values.len())
+        .collect();
+
+    match lengths.len() {
+        0 => Ok(0),
+        1 => Ok(lengths.into_iter().next().unwrap()),
+        _ => Err(WpilogError::SchemaError(format!(
+            "Struct field '{}' has values of different lengths and can't be a fixed-size array",
+            field.name()
+        ))),
+    }
+}
+
+/// Returns whether each row's value is missing. Struct values are missing when all of
+/// their fields are, since struct columns represent missing values with null fields.
+fn missing_mask(series: &Series) -> Result<Vec<bool>> {
+    let mut missing: Vec<bool> = series.is_null().into_no_null_iter().collect();
+
+    if let DataType::Struct(_) = series.dtype() {
+        let mut all_fields_missing = vec![true; series.len()];
+        for field in series.struct_()?.fields_as_series() {
+            for (all, field_missing) in all_fields_missing.iter_mut().zip(missing_mask(&field)?) {
+                *all &= field_missing;
+            }
+        }
+        for (row, all) in missing.iter_mut().zip(all_fields_missing) {
+            *row |= all;
+        }
+    }
+
+    Ok(missing)
+}
+
+/// Encodes the value at `row` as a data record payload.
+fn encode_value(series: &Series, row: usize, out: &mut Vec<u8>) -> Result<()> {
+    match series.dtype() {
+        DataType::String => {
+            if let Some(value) = series.str()?.get(row) {
+                out.extend_from_slice(value.as_bytes());
+            }
+        }
+        DataType::Binary => {
+            if let Some(value) = series.binary()?.get(row) {
+                out.extend_from_slice(value);
+            }
+        }
+        DataType::List(inner) => {
+            let Some(values) = series.list()?.get_as_series(row) else {
+                return Ok(());
+            };
+            match inner.as_ref() {
+                DataType::String => {
+                    out.extend_from_slice(&(values.len() as u32).to_le_bytes());
+                    for value in values.str()?.into_iter() {
+                        let value = value.unwrap_or_default();
+                        out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+                        out.extend_from_slice(value.as_bytes());
+                    }
+                }
+                _ => {
+                    for i in 0..values.len() {
+                        encode_struct_value(&values, i, out)?;
+                    }
+                }
+            }
+        }
+        _ => encode_struct_value(series, row, out)?,
+    }
+
+    Ok(())
+}
+
+/// Encodes a fixed-size value (scalar, struct, or fixed array) using the WPILib struct
+/// encoding. Null scalars are written as zeros so the layout stays intact.
+fn encode_struct_value(series: &Series, row: usize, out: &mut Vec<u8>) -> Result<()> {
+    match series.dtype() {
+        DataType::Boolean => out.push(series.bool()?.get(row).unwrap_or(false) as u8),
+        DataType::Int8 => out.extend_from_slice(&series.i8()?.get(row).unwrap_or(0).to_le_bytes()),
+        DataType::Int16 => {
+            out.extend_from_slice(&series.i16()?.get(row).unwrap_or(0).to_le_bytes())
+        }
+        DataType::Int32 => {
+            out.extend_from_slice(&series.i32()?.get(row).unwrap_or(0).to_le_bytes())
+        }
+        DataType::Int64 => {
+            out.extend_from_slice(&series.i64()?.get(row).unwrap_or(0).to_le_bytes())
+        }
+        DataType::UInt8 => out.push(series.u8()?.get(row).unwrap_or(0)),
+        DataType::UInt16 => {
+            out.extend_from_slice(&series.u16()?.get(row).unwrap_or(0).to_le_bytes())
+        }
+        DataType::UInt32 => {
+            out.extend_from_slice(&series.u32()?.get(row).unwrap_or(0).to_le_bytes())
+        }
+        DataType::UInt64 => {
+            out.extend_from_slice(&series.u64()?.get(row).unwrap_or(0).to_le_bytes())
+        }
+        DataType::Float32 => {
+            out.extend_from_slice(&series.f32()?.get(row).unwrap_or(0.0).to_le_bytes())
+        }
+        DataType::Float64 => {
+            out.extend_from_slice(&series.f64()?.get(row).unwrap_or(0.0).to_le_bytes())
+        }
+        DataType::Struct(_) => {
+            for field in series.struct_()?.fields_as_series() {
+                encode_struct_value(&field, row, out)?;
+            }
+        }
+        DataType::List(_) => {
+            let values = series.list()?.get_as_series(row).ok_or_else(|| {
+                WpilogError::ParseError(format!(
+                    "Null array in struct field '{}' at row {}",
+                    series.name(),
+                    row
+                ))
+            })?;
+            for i in 0..values.len() {
+                encode_struct_value(&values, i, out)?;
+            }
+        }
+        other => {
+            return Err(WpilogError::SchemaError(format!(
+                "Cannot encode '{}' of type {:?} as WPILog data",
+                series.name(),
+                other
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a START control record with empty metadata.
+fn write_start(
+    out: &mut Vec<u8>,
+    entry: u32,
+    name: &str,
+    type_name: &str,
+    timestamp: u64,
+) -> Result<()> {
+    let mut payload = vec![0x00]; // Control type = Start
+    payload.extend_from_slice(&entry.to_le_bytes());
+    for s in [name, type_name, ""] {
+        payload.extend_from_slice(&(s.len() as u32).to_le_bytes());
+        payload.extend_from_slice(s.as_bytes());
+    }
+    write_record(out, 0, timestamp, &payload)
+}
+
+/// Writes a record using the smallest field widths that fit.
+fn write_record(out: &mut Vec<u8>, entry: u32, timestamp: u64, payload: &[u8]) -> Result<()> {
+    let size = u32::try_from(payload.len()).map_err(|_| {
+        WpilogError::ParseError(format!(
+            "Record for entry {} is too large: {} bytes",
+            entry,
+            payload.len()
+        ))
+    })?;
+
+    let entry_len = varint_len(entry as u64);
+    let size_len = varint_len(size as u64);
+    let timestamp_len = varint_len(timestamp);
+
+    out.push(((entry_len - 1) | ((size_len - 1) << 2) | ((timestamp_len - 1) << 4)) as u8);
+    out.extend_from_slice(&(entry as u64).to_le_bytes()[..entry_len]);
+    out.extend_from_slice(&(size as u64).to_le_bytes()[..size_len]);
+    out.extend_from_slice(&timestamp.to_le_bytes()[..timestamp_len]);
+    out.extend_from_slice(payload);
+    Ok(())
+}
+
+/// Returns the number of bytes (at least 1) needed to store `value`.
+fn varint_len(value: u64) -> usize {
+    let bits = 64 - value.leading_zeros() as usize;
+    bits.div_ceil(8).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::WpilogConverter;
+
+    #[test]
+    fn test_varint_len() {
+        assert_eq!(varint_len(0), 1);
+        assert_eq!(varint_len(255), 1);
+        assert_eq!(varint_len(256), 2);
+        assert_eq!(varint_len(u64::MAX), 8);
+    }
+
+    #[test]
+    fn test_round_trip_scalars_and_arrays() {
+        let values: Vec<Option<Series>> = vec![
+            Some(Series::new("".into(), [1.0f64, 2.0])),
+            None,
+            Some(Series::new("".into(), [3.0f64])),
+        ];
+        let names: Vec<Option<Series>> = vec![
+            None,
+            Some(Series::new("".into(), ["a", "bc"])),
+            Some(Series::new("".into(), Vec::<String>::new())),
+        ];
+        let df = DataFrame::new(vec![
+            Series::new("timestamp".into(), [10i64, 20, 300_000]).into(),
+            Series::new("speed".into(), [Some(1.5f64), None, Some(2.5)]).into(),
+            Series::new("count".into(), [Some(1i64), Some(-2), None]).into(),
+            Series::new("enabled".into(), [Some(true), Some(false), None]).into(),
+            Series::new("mode".into(), [Some("Auto"), None, Some("")]).into(),
+            Series::new("values".into(), values).into(),
+            Series::new("names".into(), names).into(),
+        ])
+        .unwrap();
+
+        let bytes = WpilogWriter::new().to_bytes(&df).unwrap();
+        let read = WpilogConverter::from_bytes(&bytes).unwrap();
+
+        assert!(read.equals_missing(&df), "{}\n{}", read, df);
+    }
+
+    #[test]
+    fn test_round_trip_struct() {
+        let x = Series::new("x".into(), [1.0f64, 3.0]);
+        let y = Series::new("y".into(), [2.0f64, 4.0]);
+        let pose = StructChunked::from_series("pose".into(), 2, [&x, &y].into_iter())
+            .unwrap()
+            .into_series();
+        let df = DataFrame::new(vec![
+            Series::new("timestamp".into(), [1i64, 2]).into(),
+            pose.into(),
+        ])
+        .unwrap();
+
+        let bytes = WpilogWriter::new()
+            .with_struct_name("pose", "Point")
+            .to_bytes(&df)
+            .unwrap();
+        let read = WpilogConverter::from_bytes(&bytes).unwrap();
+
+        let read_pose = read.column("pose").unwrap().as_materialized_series();
+        let fields = read_pose.struct_().unwrap();
+        assert!(fields.field_by_name("x").unwrap().equals(&x));
+        assert!(fields.field_by_name("y").unwrap().equals(&y));
+    }
+
+    #[test]
+    fn test_round_trip_narrow_integers() {
+        let df = DataFrame::new(vec![
+            Series::new("timestamp".into(), [1i64, 2]).into(),
+            Series::new("small".into(), [Some(-3i8), None]).into(),
+            Series::new("medium".into(), [Some(1000i16), Some(-1000)]).into(),
+            Series::new("wide".into(), [Some(-70_000i32), Some(70_000)]).into(),
+            Series::new("byte".into(), [Some(255u8), Some(0)]).into(),
+            Series::new("word".into(), [None, Some(65_535u16)]).into(),
+            Series::new("dword".into(), [Some(4_000_000_000u32), None]).into(),
+        ])
+        .unwrap();
+
+        let bytes = WpilogWriter::new().to_bytes(&df).unwrap();
+        let parsed = WpilogConverter::from_bytes_with_options(&bytes, &Default::default()).unwrap();
+
+        let types: Vec<&str> = parsed
+            .schema()
+            .columns()
+            .iter()
+            .map(|c| c.type_name.as_str())
+            .collect();
+        assert_eq!(
+            types,
+            vec!["int8", "int16", "int32", "uint8", "uint16", "uint32"]
+        );
+        assert!(
+            parsed.dataframe.equals_missing(&df),
+            "{}\n{}",
+            parsed.dataframe,
+            df
+        );
+    }
+
+    #[test]
+    fn test_write_binary_as_raw() {
+        let df = DataFrame::new(vec![
+            Series::new("timestamp".into(), [1i64, 2]).into(),
+            Series::new("blob".into(), [Some(&b"ab"[..]), None]).into(),
+        ])
+        .unwrap();
+
+        let bytes = WpilogWriter::new().to_bytes(&df).unwrap();
+        let parsed = WpilogConverter::from_bytes_with_options(&bytes, &Default::default()).unwrap();
+
+        assert_eq!(parsed.schema().columns()[0].type_name, "raw");
+        let blob = parsed.dataframe.column("blob").unwrap().str().unwrap();
+        assert_eq!(blob.get(0), Some("ab"));
+        assert_eq!(parsed.dataframe.height(), 1);
+    }
+
+    #[test]
+    fn test_uint64_above_int64_range() {
+        let df = DataFrame::new(vec![
+            Series::new("timestamp".into(), [1i64, 2]).into(),
+            Series::new("count".into(), [1u64, u64::MAX]).into(),
+        ])
+        .unwrap();
+        assert!(matches!(
+            WpilogWriter::new().to_bytes(&df),
+            Err(WpilogError::ParseError(_))
+        ));
+
+        let list = Series::new("counts".into(), [Series::new("".into(), [u64::MAX])]);
+        let df = DataFrame::new(vec![
+            Series::new("timestamp".into(), [1i64]).into(),
+            list.into(),
+        ])
+        .unwrap();
+        assert!(matches!(
+            WpilogWriter::new().to_bytes(&df),
+            Err(WpilogError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_struct_name_collision_with_different_layout() {
+        let x = Series::new("x".into(), [1.0f64]);
+        let y = Series::new("y".into(), [2.0f64]);
+        let point = StructChunked::from_series("a".into(), 1, [&x, &y].into_iter())
+            .unwrap()
+            .into_series();
+        let other = StructChunked::from_series("b".into(), 1, [&x].into_iter())
+            .unwrap()
+            .into_series();
+        let df = DataFrame::new(vec![
+            Series::new("timestamp".into(), [1i64]).into(),
+            point.clone().into(),
+            other.into(),
+        ])
+        .unwrap();
+
+        let writer = WpilogWriter::new()
+            .with_struct_name("a", "Point")
+            .with_struct_name("b", "Point");
+        match writer.to_bytes(&df) {
+            Err(WpilogError::SchemaError(msg)) => assert!(msg.contains("Point")),
+            other => panic!("Expected SchemaError, got {:?}", other),
+        }
+
+        // The same layout under one name is shared
+        let same = point.with_name("c".into());
+        let df = DataFrame::new(vec![
+            Series::new("timestamp".into(), [1i64]).into(),
+            df.column("a").unwrap().clone(),
+            same.into(),
+        ])
+        .unwrap();
+        let writer = WpilogWriter::new()
+            .with_struct_name("a", "Point")
+            .with_struct_name("c", "Point");
+        assert!(writer.to_bytes(&df).is_ok());
+    }
+
+    #[test]
+    fn test_missing_timestamp_column() {
+        let df = DataFrame::new(vec![Series::new("speed".into(), [1.0f64]).into()]).unwrap();
+
+        match WpilogWriter::new().to_bytes(&df) {
+            Err(WpilogError::SchemaError(msg)) => assert!(msg.contains("timestamp")),
+            other => panic!("Expected SchemaError, got {:?}", other),
+        }
+    }
+}
@@ -14,6 +14,7 @@ pub struct TestFileMetadata {
     pub expected_cols: Option<usize>,
     pub min_rows: Option<usize>,
     pub min_cols: Option<usize>,
+    #[allow(dead_code)]
     pub sha256: Option<&'static str>,
 }
 